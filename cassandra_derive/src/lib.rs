@@ -0,0 +1,112 @@
+//! Custom derives for the cassandra crate
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+/// Derives `FromRow` for a struct with named fields, reading each field from the column of the
+/// same name. A field can read a differently named column with `#[cass(rename = "column")]`.
+///
+/// A `FromValue` impl is derived as well, reading the struct from a user defined type value by
+/// field name, so the struct can itself be nested in another one.
+#[proc_macro_derive(FromRow, attributes(cass))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_row(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn expand_from_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (idents, columns) = named_fields(input)?;
+    let row_idents = idents.clone();
+    let row_columns = columns.clone();
+
+    Ok(quote! {
+        impl #impl_generics ::cassandra::FromRow for #name #ty_generics #where_clause {
+            fn from_row(row: &::cassandra::Row) -> ::cassandra::Result<Self> {
+                Ok(#name {
+                    #( #row_idents: row.get_by_name(#row_columns)?, )*
+                })
+            }
+        }
+
+        impl #impl_generics ::cassandra::FromValue for #name #ty_generics #where_clause {
+            fn from_value(value: &::cassandra::Value) -> ::cassandra::Result<Self> {
+                Ok(#name {
+                    #( #idents: value.get_user_type_field(#columns)?, )*
+                })
+            }
+        }
+    })
+}
+
+/// Derives `ToCqlValues` for a struct with named fields, binding each field to the statement
+/// parameter of the same name. A field can bind a differently named parameter with
+/// `#[cass(rename = "parameter")]`.
+#[proc_macro_derive(ToCqlValues, attributes(cass))]
+pub fn derive_to_cql_values(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_cql_values(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn expand_to_cql_values(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (idents, columns) = named_fields(input)?;
+    let count = idents.len();
+
+    Ok(quote! {
+        impl #impl_generics ::cassandra::ToCqlValues for #name #ty_generics #where_clause {
+            fn value_count(&self) -> usize { #count }
+
+            fn bind_to(self, statement: &mut ::cassandra::Statement) -> ::cassandra::Result<()> {
+                #( ::cassandra::BindRustType::bind_by_name(statement, #columns, self.#idents)?; )*
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Collects the field identifiers of a struct along with the column each one maps to
+fn named_fields(input: &DeriveInput) -> syn::Result<(Vec<syn::Ident>, Vec<LitStr>)> {
+    let fields = match input.data {
+        Data::Struct(ref data) => {
+            match data.fields {
+                Fields::Named(ref fields) => &fields.named,
+                _ => return Err(syn::Error::new_spanned(input, "can only be derived for structs with named fields")),
+            }
+        }
+        _ => return Err(syn::Error::new_spanned(input, "can only be derived for structs")),
+    };
+
+    let mut idents = Vec::new();
+    let mut columns = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().expect("named field");
+        let mut column = LitStr::new(ident.to_string().trim_start_matches("r#"), ident.span());
+        for attr in &field.attrs {
+            if !attr.path().is_ident("cass") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    column = meta.value()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported cass attribute, expected `rename`"))
+                }
+            })?;
+        }
+        idents.push(ident);
+        columns.push(column);
+    }
+    Ok((idents, columns))
+}
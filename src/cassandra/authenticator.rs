@@ -0,0 +1,405 @@
+//! SASL authentication of the connections to the cluster.
+//!
+//! `Cluster::set_credentials` answers Cassandra's `PasswordAuthenticator` from within the driver.
+//! Any other server side authenticator is answered by an `AuthProvider` registered with
+//! `Cluster::set_auth_provider`: the driver asks it for an `Authenticator` for each connection it
+//! opens, and hands that authenticator the server's challenges until the server accepts it.
+
+use cassandra::error::*;
+use cassandra::inet::Inet;
+use cassandra::util::{CassErrorExt, Protected, to_owned_string};
+use cassandra_sys::{CASS_ERROR_LIB_INTERNAL_ERROR, CASS_OK};
+use cassandra_sys::CassAuthenticator as _Authenticator;
+use cassandra_sys::CassAuthenticatorCallbacks;
+use cassandra_sys::CassCluster as _Cluster;
+use cassandra_sys::cass_authenticator_address;
+use cassandra_sys::cass_authenticator_class_name;
+use cassandra_sys::cass_authenticator_exchange_data;
+use cassandra_sys::cass_authenticator_set_error_n;
+use cassandra_sys::cass_authenticator_set_exchange_data;
+use cassandra_sys::cass_authenticator_set_response;
+use cassandra_sys::cass_cluster_set_authenticator_callbacks;
+use std::mem;
+use std::os::raw::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// One side of the SASL exchange that authenticates a connection
+pub trait Authenticator: Send {
+    /// The token sent to the server to start the exchange
+    fn initial_response(&mut self) -> Result<Vec<u8>>;
+
+    /// The token answering a challenge sent by the server
+    fn evaluate_challenge(&mut self, challenge: &[u8]) -> Result<Vec<u8>>;
+
+    /// Called with the server's last token once it accepted the connection
+    fn on_success(&mut self, _token: &[u8]) {}
+}
+
+/// Creates the authenticators of the connections the driver opens
+pub trait AuthProvider: Send + Sync {
+    /// Creates an authenticator for a connection to the node at `address`, whose server side
+    /// authenticator is the Java class `class_name`
+    fn new_authenticator(&self, address: Inet, class_name: &str) -> Box<dyn Authenticator>;
+}
+
+/// Authenticates with a username and password, as Cassandra's `PasswordAuthenticator` expects
+#[derive(Debug, Clone)]
+pub struct PlainTextAuthProvider {
+    username: String,
+    password: String,
+}
+
+impl PlainTextAuthProvider {
+    /// Creates a provider authenticating every connection as `username`
+    pub fn new<S: Into<String>>(username: S, password: S) -> Self {
+        PlainTextAuthProvider {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+impl AuthProvider for PlainTextAuthProvider {
+    fn new_authenticator(&self, _address: Inet, _class_name: &str) -> Box<dyn Authenticator> {
+        let mut token = vec![0];
+        token.extend_from_slice(self.username.as_bytes());
+        token.push(0);
+        token.extend_from_slice(self.password.as_bytes());
+        Box::new(PlainTextAuthenticator(token))
+    }
+}
+
+/// Sends the SASL PLAIN token, whatever the server asks
+struct PlainTextAuthenticator(Vec<u8>);
+
+impl Authenticator for PlainTextAuthenticator {
+    fn initial_response(&mut self) -> Result<Vec<u8>> { Ok(self.0.clone()) }
+
+    fn evaluate_challenge(&mut self, _challenge: &[u8]) -> Result<Vec<u8>> { Ok(self.0.clone()) }
+}
+
+/// The provider handed to the driver as the callbacks' data
+type Provider = Box<dyn AuthProvider>;
+
+/// The authenticator of a connection, handed to the driver as the exchange data
+type Exchange = Box<dyn Authenticator>;
+
+/// Runs an authenticator hook, turning a panic into an error so it does not unwind into the driver
+fn guarded<T, F: FnOnce() -> Result<T>>(hook: F) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(hook)).unwrap_or_else(|_| {
+        Err(CassError::with_message(CASS_ERROR_LIB_INTERNAL_ERROR, "panic in authenticator").into())
+    })
+}
+
+/// Creates the authenticator of a new connection and the token that starts its exchange
+fn start(provider: &dyn AuthProvider, address: Inet, class_name: &str) -> Result<(Exchange, Vec<u8>)> {
+    guarded(|| {
+        let mut exchange = provider.new_authenticator(address, class_name);
+        let response = exchange.initial_response()?;
+        Ok((exchange, response))
+    })
+}
+
+/// Answers a challenge of the server
+fn challenge(exchange: &mut Exchange, token: &[u8]) -> Result<Vec<u8>> {
+    guarded(|| exchange.evaluate_challenge(token))
+}
+
+/// Tells the authenticator the server accepted the connection
+fn success(exchange: &mut Exchange, token: &[u8]) -> Result<()> {
+    guarded(|| {
+        exchange.on_success(token);
+        Ok(())
+    })
+}
+
+/// Registers `provider` to answer the authentication exchanges of the cluster's connections. The
+/// driver frees it when the cluster is freed or another provider is registered.
+pub(crate) fn set_auth_provider(cluster: *mut _Cluster, provider: Provider) -> Result<()> {
+    let callbacks = CassAuthenticatorCallbacks {
+        initial_callback: Some(on_initial::<*mut _Authenticator>),
+        challenge_callback: Some(on_challenge::<*mut _Authenticator>),
+        success_callback: Some(on_success::<*mut _Authenticator>),
+        cleanup_callback: Some(on_cleanup::<*mut _Authenticator>),
+    };
+    unsafe {
+        let data = Box::into_raw(Box::new(provider)) as *mut c_void;
+        match cass_cluster_set_authenticator_callbacks(cluster, &callbacks, Some(free_provider), data) {
+            CASS_OK => Ok(()),
+            err => {
+                drop(Box::from_raw(data as *mut Provider));
+                err.wrap(())
+            }
+        }
+    }
+}
+
+/// A connection's authenticator in the driver, as the authentication callbacks use it. Kept apart
+/// from the driver so the callbacks can be tested without it.
+trait RawAuthenticator: Copy {
+    /// The address of the node the connection is to
+    fn address(self) -> Inet;
+
+    /// The Java class of the node's authenticator
+    fn class_name(self) -> String;
+
+    /// The data attached to the exchange, null until the exchange started
+    fn exchange_data(self) -> *mut c_void;
+
+    /// Attaches data to the exchange, to be cleaned up with the connection
+    fn set_exchange_data(self, data: *mut c_void);
+
+    /// Hands the driver a token to send to the server
+    fn set_response(self, token: &[u8]);
+
+    /// Fails the exchange
+    fn set_error(self, err: &Error);
+}
+
+impl RawAuthenticator for *mut _Authenticator {
+    fn address(self) -> Inet {
+        unsafe {
+            let mut address = mem::zeroed();
+            cass_authenticator_address(self, &mut address);
+            Inet::build(address)
+        }
+    }
+
+    fn class_name(self) -> String {
+        unsafe {
+            let mut length = 0;
+            let class_name = cass_authenticator_class_name(self, &mut length);
+            to_owned_string(class_name, length).unwrap_or_default()
+        }
+    }
+
+    fn exchange_data(self) -> *mut c_void { unsafe { cass_authenticator_exchange_data(self) } }
+
+    fn set_exchange_data(self, data: *mut c_void) { unsafe { cass_authenticator_set_exchange_data(self, data) } }
+
+    fn set_response(self, token: &[u8]) {
+        unsafe { cass_authenticator_set_response(self, token.as_ptr() as *const c_char, token.len()) }
+    }
+
+    fn set_error(self, err: &Error) {
+        let message = err.to_string();
+        unsafe { cass_authenticator_set_error_n(self, message.as_ptr() as *const c_char, message.len()) }
+    }
+}
+
+/// Hands the driver a token to send to the server, or fails the exchange
+fn respond<A: RawAuthenticator>(auth: A, response: Result<Vec<u8>>) {
+    match response {
+        Ok(token) => auth.set_response(&token),
+        Err(err) => auth.set_error(&err),
+    }
+}
+
+unsafe extern "C" fn on_initial<A: RawAuthenticator>(auth: A, data: *mut c_void) {
+    let provider = &**(data as *const Provider);
+    let response = start(provider, auth.address(), &auth.class_name()).map(|(exchange, response)| {
+        auth.set_exchange_data(Box::into_raw(Box::new(exchange)) as *mut c_void);
+        response
+    });
+    respond(auth, response);
+}
+
+unsafe extern "C" fn on_challenge<A: RawAuthenticator>(auth: A,
+                                                       _data: *mut c_void,
+                                                       token: *const c_char,
+                                                       token_size: usize) {
+    let response = match exchange(auth) {
+        Some(exchange) => challenge(exchange, bytes(token, token_size)),
+        None => {
+            Err(CassError::with_message(CASS_ERROR_LIB_INTERNAL_ERROR, "challenge before the exchange started")
+                .into())
+        }
+    };
+    respond(auth, response);
+}
+
+unsafe extern "C" fn on_success<A: RawAuthenticator>(auth: A,
+                                                     _data: *mut c_void,
+                                                     token: *const c_char,
+                                                     token_size: usize) {
+    if let Some(exchange) = exchange(auth) {
+        if let Err(err) = success(exchange, bytes(token, token_size)) {
+            error!("authenticator failed on success: {}", err);
+        }
+    }
+}
+
+unsafe extern "C" fn on_cleanup<A: RawAuthenticator>(auth: A, _data: *mut c_void) {
+    let exchange = auth.exchange_data() as *mut Exchange;
+    if !exchange.is_null() {
+        auth.set_exchange_data(ptr::null_mut());
+        drop(Box::from_raw(exchange));
+    }
+}
+
+unsafe extern "C" fn free_provider(data: *mut c_void) { drop(Box::from_raw(data as *mut Provider)); }
+
+/// The authenticator of the connection, unless its exchange failed to start
+unsafe fn exchange<'a, A: RawAuthenticator>(auth: A) -> Option<&'a mut Exchange> {
+    (auth.exchange_data() as *mut Exchange).as_mut()
+}
+
+/// The token the driver passed in, which may be null when the server sent none
+unsafe fn bytes<'a>(token: *const c_char, token_size: usize) -> &'a [u8] {
+    if token.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(token as *const u8, token_size)
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra_sys::CASS_ERROR_SERVER_BAD_CREDENTIALS;
+    use std::cell::{Cell, RefCell};
+
+    /// A token authenticator answering the server's nonce with `token:nonce`, which fails on an
+    /// unexpected success token
+    pub struct TokenAuthenticator(Vec<u8>);
+
+    impl Authenticator for TokenAuthenticator {
+        fn initial_response(&mut self) -> Result<Vec<u8>> { Ok(b"token".to_vec()) }
+
+        fn evaluate_challenge(&mut self, challenge: &[u8]) -> Result<Vec<u8>> {
+            let mut response = self.0.clone();
+            response.push(b':');
+            response.extend_from_slice(challenge);
+            Ok(response)
+        }
+
+        fn on_success(&mut self, token: &[u8]) { assert_eq!(token, b"welcome") }
+    }
+
+    pub struct TokenAuthProvider(pub &'static [u8]);
+
+    impl AuthProvider for TokenAuthProvider {
+        fn new_authenticator(&self, _address: Inet, class_name: &str) -> Box<dyn Authenticator> {
+            assert_eq!(class_name, "com.example.TokenAuthenticator");
+            Box::new(TokenAuthenticator(self.0.to_vec()))
+        }
+    }
+
+    /// Plays the server side of the exchange the way the driver relays it, failing if the
+    /// connection is not accepted
+    pub fn handshake(provider: &dyn AuthProvider) -> Result<()> {
+        let (mut exchange, response) = start(provider, Inet::default(), "com.example.TokenAuthenticator")?;
+        assert_eq!(response, b"token");
+        let response = challenge(&mut exchange, b"nonce-1")?;
+        if response != b"secret:nonce-1" {
+            return Err(CassError::with_message(CASS_ERROR_SERVER_BAD_CREDENTIALS, "bad token").into());
+        }
+        success(&mut exchange, b"welcome")
+    }
+
+    /// Stands in for the driver's authenticator, recording what the callbacks hand it
+    #[derive(Default)]
+    pub struct FakeAuthenticator {
+        pub exchange: Cell<usize>,
+        pub response: RefCell<Option<Vec<u8>>>,
+        pub failed: Cell<bool>,
+    }
+
+    impl RawAuthenticator for &FakeAuthenticator {
+        fn address(self) -> Inet { Inet::default() }
+
+        fn class_name(self) -> String { "com.example.TokenAuthenticator".to_owned() }
+
+        fn exchange_data(self) -> *mut c_void { self.exchange.get() as *mut c_void }
+
+        fn set_exchange_data(self, data: *mut c_void) { self.exchange.set(data as usize) }
+
+        fn set_response(self, token: &[u8]) { *self.response.borrow_mut() = Some(token.to_vec()) }
+
+        fn set_error(self, _err: &Error) { self.failed.set(true) }
+    }
+
+    /// Registers a provider the way `set_auth_provider` does, to be freed with `free_provider`
+    pub fn provider_data(provider: Provider) -> *mut c_void { Box::into_raw(Box::new(provider)) as *mut c_void }
+
+    /// Relays a token from the server to `callback`
+    pub unsafe fn relay<'a>(callback: unsafe extern "C" fn(&'a FakeAuthenticator, *mut c_void, *const c_char, usize),
+                            auth: &'a FakeAuthenticator,
+                            data: *mut c_void,
+                            token: &[u8]) {
+        callback(auth, data, token.as_ptr() as *const c_char, token.len())
+    }
+}
+
+#[test]
+fn test_authenticator_exchange() {
+    use self::fake::*;
+
+    assert!(handshake(&TokenAuthProvider(b"secret")).is_ok());
+    assert!(handshake(&TokenAuthProvider(b"wrong")).is_err());
+
+    struct Panicking;
+    impl AuthProvider for Panicking {
+        fn new_authenticator(&self, _address: Inet, _class_name: &str) -> Box<dyn Authenticator> {
+            panic!("no authenticator")
+        }
+    }
+    assert!(handshake(&Panicking).is_err());
+
+    let provider = PlainTextAuthProvider::new("cassandra", "pa55");
+    let (mut exchange, response) = start(&provider, Inet::default(), "PasswordAuthenticator").unwrap();
+    assert_eq!(response, b"\0cassandra\0pa55");
+    assert_eq!(challenge(&mut exchange, b"").unwrap(), b"\0cassandra\0pa55");
+}
+
+#[test]
+fn test_authenticator_callbacks() {
+    use self::fake::*;
+
+    struct Panicking;
+    impl AuthProvider for Panicking {
+        fn new_authenticator(&self, _address: Inet, _class_name: &str) -> Box<dyn Authenticator> {
+            panic!("no authenticator")
+        }
+    }
+
+    unsafe {
+        let data = provider_data(Box::new(TokenAuthProvider(b"secret")));
+
+        let auth = FakeAuthenticator::default();
+        on_initial(&auth, data);
+        assert_eq!(auth.response.borrow_mut().take().unwrap(), b"token");
+        assert!(auth.exchange.get() != 0);
+        relay(on_challenge, &auth, data, b"nonce-1");
+        assert_eq!(auth.response.borrow_mut().take().unwrap(), b"secret:nonce-1");
+        relay(on_success, &auth, data, b"welcome");
+        assert!(!auth.failed.get());
+        on_cleanup(&auth, data);
+        assert_eq!(auth.exchange.get(), 0);
+        on_cleanup(&auth, data);
+
+        // a panic on success is caught and logged
+        let auth = FakeAuthenticator::default();
+        on_initial(&auth, data);
+        relay(on_success, &auth, data, b"unexpected");
+        on_cleanup(&auth, data);
+        assert!(!auth.failed.get());
+
+        let auth = FakeAuthenticator::default();
+        relay(on_challenge, &auth, data, b"nonce-1");
+        assert!(auth.failed.get());
+        assert!(auth.response.borrow().is_none());
+        relay(on_success, &auth, data, b"welcome");
+
+        free_provider(data);
+
+        let data = provider_data(Box::new(Panicking));
+        let auth = FakeAuthenticator::default();
+        on_initial(&auth, data);
+        assert!(auth.failed.get());
+        assert_eq!(auth.exchange.get(), 0);
+        free_provider(data);
+    }
+}
@@ -1,37 +1,47 @@
 use cassandra::consistency::Consistency;
+use cassandra::execution_profile::{ExecutionProfile, ProfileTarget};
+use cassandra::policy::load_balancing::LoadBalancingPolicy;
 use cassandra::policy::retry::RetryPolicy;
+use cassandra::session::Session;
 use cassandra::statement::Statement;
-use cassandra::util::Protected;
+use cassandra::error::*;
+use cassandra::util::{CLUSTER_REQUEST_TIMEOUT, CassErrorExt, Protected, timeout_millis};
 
-use cassandra_sys::CASS_OK;
 pub use cassandra_sys::CassBatch as _Batch;
 pub use cassandra_sys::CassBatchType as BatchType;
 use cassandra_sys::CassConsistency;
 use cassandra_sys::CassCustomPayload as _CassCustomPayload;
-use cassandra_sys::CassError;
 use cassandra_sys::cass_batch_add_statement;
 use cassandra_sys::cass_batch_free;
 use cassandra_sys::cass_batch_new;
 use cassandra_sys::cass_batch_set_consistency;
 use cassandra_sys::cass_batch_set_custom_payload;
+use cassandra_sys::cass_batch_set_request_timeout;
 use cassandra_sys::cass_batch_set_retry_policy;
 use cassandra_sys::cass_batch_set_serial_consistency;
 use cassandra_sys::cass_batch_set_timestamp;
 use cassandra_sys::cass_custom_payload_free;
 use cassandra_sys::cass_custom_payload_new;
-use cassandra_sys::cass_custom_payload_set;
-use std::ffi::CString;
-use std::ffi::NulError;
+use cassandra_sys::cass_custom_payload_set_n;
+use std::os::raw::c_char;
+use std::sync::Arc;
+use time::Duration;
 
 
 /// A group of statements that are executed as a single batch.
 /// <b>Note:</b> Batches are not supported by the binary protocol version 1.
 #[derive(Debug)]
-pub struct Batch(*mut _Batch);
+pub struct Batch {
+    inner: *mut _Batch,
+}
 
 impl Protected<*mut _Batch> for Batch {
-    fn inner(&self) -> *mut _Batch { self.0 }
-    fn build(inner: *mut _Batch) -> Self { Batch(inner) }
+    fn inner(&self) -> *mut _Batch { self.inner }
+    fn build(inner: *mut _Batch) -> Self {
+        Batch {
+            inner,
+        }
+    }
 }
 
 /// Custom payloads not fully supported yet
@@ -49,13 +59,15 @@ impl Default for CustomPayload {
 }
 impl CustomPayload {
     /// Sets an item to the custom payload.
-    pub fn set(&self, name: String, value: &[u8]) -> Result<(), NulError> {
+    pub fn set(&self, name: String, value: &[u8]) -> Result<()> {
         unsafe {
-            Ok(cass_custom_payload_set(self.0,
-                                       CString::new(name)?.as_ptr(),
-                                       value.as_ptr(),
-                                       value.len()))
+            cass_custom_payload_set_n(self.0,
+                                      name.as_ptr() as *const c_char,
+                                      name.len(),
+                                      value.as_ptr(),
+                                      value.len());
         }
+        Ok(())
     }
 }
 
@@ -76,72 +88,93 @@ impl Drop for CustomPayload {
 impl Drop for Batch {
     /// Frees a batch instance. Batches can be immediately freed after being
     /// executed.
-    fn drop(&mut self) { unsafe { cass_batch_free(self.0) } }
+    fn drop(&mut self) { unsafe { cass_batch_free(self.inner) } }
 }
 
 impl Batch {
     /// Creates a new batch statement with batch type.
-    pub fn new(batch_type: BatchType) -> Batch { unsafe { Batch(cass_batch_new(batch_type)) } }
+    pub fn new(batch_type: BatchType) -> Batch { unsafe { Batch::build(cass_batch_new(batch_type)) } }
 
     /// Sets the batch's consistency level
-    pub fn set_consistency(&mut self, consistency: CassConsistency) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_set_consistency(self.0, consistency) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    pub fn set_consistency(&mut self, consistency: CassConsistency) -> Result<&Self> {
+        unsafe { cass_batch_set_consistency(self.inner, consistency).wrap(self) }
     }
 
     /// Sets the batch's serial consistency level.
     ///
     /// <b>Default:</b> Not set
-    pub fn set_serial_consistency(&mut self, consistency: Consistency) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_set_serial_consistency(self.0, consistency.inner()) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    pub fn set_serial_consistency(&mut self, consistency: Consistency) -> Result<&Self> {
+        unsafe { cass_batch_set_serial_consistency(self.inner, consistency.inner()).wrap(self) }
     }
 
     /// Sets the batch's timestamp.
-    pub fn set_timestamp(&mut self, timestamp: i64) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_set_timestamp(self.0, timestamp) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    pub fn set_timestamp(&mut self, timestamp: i64) -> Result<&Self> {
+        unsafe { cass_batch_set_timestamp(self.inner, timestamp).wrap(self) }
     }
 
-    /// Sets the batch's retry policy.
-    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_set_retry_policy(self.0, retry_policy.inner()) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    /// Sets the batch's retry policy. A policy written in Rust is refused: pass it to
+    /// `Session::execute_batch_with_retry` instead.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> Result<&Self> {
+        self.apply_retry_policy(&retry_policy)?;
+        Ok(self)
+    }
+
+    /// Sets the batch's timeout for waiting for a response from a node. A zero timeout
+    /// disables it, and a negative one is rejected.
+    ///
+    /// <b>Default:</b> The cluster's request timeout
+    pub fn set_request_timeout(&mut self, timeout: Duration) -> Result<&Self> {
+        let timeout = timeout_millis(timeout)?;
+        unsafe { cass_batch_set_request_timeout(self.inner, timeout).wrap(self) }
+    }
+
+    /// Makes the batch use the cluster's request timeout again.
+    pub fn reset_request_timeout(&mut self) -> Result<&Self> {
+        unsafe { cass_batch_set_request_timeout(self.inner, CLUSTER_REQUEST_TIMEOUT).wrap(self) }
+    }
+
+    /// Applies the settings of an execution profile, as returned by `Session::execution_profile`,
+    /// to the batch. Settings the profile leaves unset are not changed, and its load balancing
+    /// policy is not applied, batches being routed by the driver.
+    pub fn set_execution_profile(&mut self, profile: &ExecutionProfile) -> Result<&Self> {
+        profile.apply_to(self)?;
+        Ok(self)
+    }
+
+    /// Applies the settings of the execution profile registered under `name` on the cluster
+    /// `session` was connected to. Fails if there is no such profile.
+    pub fn set_execution_profile_name(&mut self, session: &Session, name: &str) -> Result<&Self> {
+        self.set_execution_profile(session.execution_profile(name)?)
     }
 
     /// Sets the batch's custom payload.
-    pub fn set_custom_payload(&mut self, custom_payload: CustomPayload) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_set_custom_payload(self.0, custom_payload.0) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    pub fn set_custom_payload(&mut self, custom_payload: CustomPayload) -> Result<&Self> {
+        unsafe { cass_batch_set_custom_payload(self.inner, custom_payload.0).wrap(self) }
     }
 
     /// Adds a statement to a batch.
-    pub fn add_statement(&mut self, statement: &Statement) -> Result<&Self, CassError> {
-        unsafe {
-            match cass_batch_add_statement(self.0, statement.inner()) {
-                CASS_OK => Ok(self),
-                err => Err(err),
-            }
-        }
+    pub fn add_statement(&mut self, statement: &Statement) -> Result<&Self> {
+        unsafe { cass_batch_add_statement(self.inner, statement.inner()).wrap(self) }
     }
 }
+
+impl ProfileTarget for Batch {
+    fn apply_consistency(&mut self, consistency: Consistency) -> Result<()> {
+        unsafe { cass_batch_set_consistency(self.inner, consistency.inner()).wrap(()) }
+    }
+
+    fn apply_serial_consistency(&mut self, serial_consistency: Consistency) -> Result<()> {
+        unsafe { cass_batch_set_serial_consistency(self.inner, serial_consistency.inner()).wrap(()) }
+    }
+
+    fn apply_request_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.set_request_timeout(timeout).map(|_| ())
+    }
+
+    fn apply_retry_policy(&mut self, retry_policy: &RetryPolicy) -> Result<()> {
+        retry_policy.check_driver_policy()?;
+        unsafe { cass_batch_set_retry_policy(self.inner, retry_policy.inner()).wrap(()) }
+    }
+
+    fn apply_load_balancing_policy(&mut self, _policy: &Arc<dyn LoadBalancingPolicy>) {}
+}
@@ -1,25 +1,32 @@
 
+use cassandra::authenticator::{self, AuthProvider};
+use cassandra::execution_profile::{ExecutionProfile, ExecutionProfiles};
 #[macro_use]
 use cassandra::future::ConnectFuture;
+use cassandra::policy::load_balancing::{BalancedHosts, LoadBalancingPolicy};
 use cassandra::policy::retry::RetryPolicy;
+use cassandra::policy::speculative::ConstantSpeculativeExecutionPolicy;
 use cassandra::session::Session;
 use cassandra::ssl::Ssl;
 use cassandra::time::TimestampGen;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
+use cassandra_sys::CASS_ERROR_LIB_BAD_PARAMS;
 use cassandra_sys::CassCluster as _Cluster;
 use cassandra_sys::cass_bool_t;
 use cassandra_sys::cass_cluster_free;
 use cassandra_sys::cass_cluster_new;
+use cassandra_sys::cass_cluster_set_blacklist_dc_filtering_n;
+use cassandra_sys::cass_cluster_set_blacklist_filtering_n;
 use cassandra_sys::cass_cluster_set_connect_timeout;
 
 use cassandra_sys::cass_cluster_set_connection_heartbeat_interval;
 use cassandra_sys::cass_cluster_set_connection_idle_timeout;
-use cassandra_sys::cass_cluster_set_contact_points;
+use cassandra_sys::cass_cluster_set_contact_points_n;
 use cassandra_sys::cass_cluster_set_core_connections_per_host;
-use cassandra_sys::cass_cluster_set_credentials;
+use cassandra_sys::cass_cluster_set_credentials_n;
 use cassandra_sys::cass_cluster_set_latency_aware_routing;
 use cassandra_sys::cass_cluster_set_latency_aware_routing_settings;
-use cassandra_sys::cass_cluster_set_load_balance_dc_aware;
+use cassandra_sys::cass_cluster_set_load_balance_dc_aware_n;
 use cassandra_sys::cass_cluster_set_load_balance_round_robin;
 use cassandra_sys::cass_cluster_set_max_concurrent_creation;
 use cassandra_sys::cass_cluster_set_max_concurrent_requests_threshold;
@@ -42,26 +49,23 @@ use cassandra_sys::cass_cluster_set_tcp_nodelay;
 use cassandra_sys::cass_cluster_set_timestamp_gen;
 use cassandra_sys::cass_cluster_set_token_aware_routing;
 use cassandra_sys::cass_cluster_set_use_schema;
-use cassandra_sys::cass_cluster_set_whitelist_filtering;
+use cassandra_sys::cass_cluster_set_whitelist_dc_filtering_n;
+use cassandra_sys::cass_cluster_set_whitelist_filtering_n;
 use cassandra_sys::cass_cluster_set_write_bytes_high_water_mark;
 use cassandra_sys::cass_cluster_set_write_bytes_low_water_mark;
 use cassandra_sys::cass_false;
-use cassandra_sys::cass_future_error_code;
 use cassandra_sys::cass_session_connect;
 use cassandra_sys::cass_session_new;
 use cassandra_sys::cass_true;
-use errors::*;
+use cassandra::error::*;
 // use ip::IpAddr;
-use errors::*;
-use std::ffi::CString;
-use std::ffi::NulError;
 use std::fmt;
 use std::iter::Map;
-use std::net::AddrParseError;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, SocketAddr};
 
-use std::result;
+use std::os::raw::c_char;
 use std::str::FromStr;
+use std::sync::Arc;
 use time::Duration;
 
 /// Possible Cql Protocol versions
@@ -73,28 +77,208 @@ pub enum CqlProtocol {
     FOUR = 4,
 }
 
+/// A single cassandra contact point: an IP address or a host name, with an optional port
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContactPoint {
+    /// An IPv4 or IPv6 address
+    Addr(IpAddr, Option<u16>),
+    /// A host name, resolved by the driver when the cluster connects
+    Host(String, Option<u16>),
+}
+
+impl ContactPoint {
+    /// The port given for the contact point, if any
+    pub fn port(&self) -> Option<u16> {
+        match *self {
+            ContactPoint::Addr(_, port) |
+            ContactPoint::Host(_, port) => port,
+        }
+    }
+
+    /// The address or host name, without the port, as the driver expects hosts
+    pub(crate) fn host(&self) -> String {
+        match *self {
+            ContactPoint::Addr(ref addr, _) => addr.to_string(),
+            ContactPoint::Host(ref host, _) => host.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ContactPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ContactPoint::Addr(IpAddr::V6(ref addr), Some(port)) => write!(f, "[{}]:{}", addr, port),
+            ContactPoint::Addr(ref addr, Some(port)) => write!(f, "{}:{}", addr, port),
+            ContactPoint::Addr(ref addr, None) => write!(f, "{}", addr),
+            ContactPoint::Host(ref host, Some(port)) => write!(f, "{}:{}", host, port),
+            ContactPoint::Host(ref host, None) => write!(f, "{}", host),
+        }
+    }
+}
+
+/// Parses "10.0.0.1", "10.0.0.1:9042", "::1", "[::1]:9042", "cassandra.example.com" or
+/// "cassandra.example.com:9042"
+impl FromStr for ContactPoint {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(addr) = IpAddr::from_str(s) {
+            return Ok(ContactPoint::Addr(addr, None));
+        }
+        if let Ok(addr) = SocketAddr::from_str(s) {
+            return Ok(ContactPoint::Addr(addr.ip(), Some(addr.port())));
+        }
+        let (host, port) = match s.rfind(':') {
+            Some(pos) => (&s[..pos], Some(u16::from_str(&s[pos + 1..]).map_err(|_| bad_contact_point(s))?)),
+            None => (s, None),
+        };
+        if s.starts_with('[') || !is_host_name(host) {
+            return Err(bad_contact_point(s));
+        }
+        Ok(ContactPoint::Host(host.to_owned(), port))
+    }
+}
+
+/// Whether `host` is a valid DNS name, made of letters, digits and hyphens
+fn is_host_name(host: &str) -> bool {
+    let host = host.trim_end_matches('.');
+    !host.is_empty() && host.len() <= 253 &&
+    host.split('.').all(|label| {
+        !label.is_empty() && label.len() <= 63 && !label.starts_with('-') && !label.ends_with('-') &&
+        label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    })
+}
+
+fn bad_contact_point(contact_point: &str) -> Error {
+    CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                            format!("invalid contact point {:?}", contact_point))
+        .into()
+}
+
+/// Fails if `port` is not the port already set, the driver reaching every host on the same one
+fn check_port(port: u16, set: Option<u16>) -> Result<()> {
+    match set {
+        Some(set) if set != port => {
+            Err(CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                                        format!("port {} conflicts with port {} already set", port, set))
+                .into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The port set on a cluster, kept to reject contact points that would change it
+#[derive(Debug, Default)]
+struct PortSetting {
+    /// The port set with `set_port` or given with the contact points, if any
+    port: Option<u16>,
+    /// Whether the contact points gave `port`, which `set_port` may then not change
+    from_contact_points: bool,
+}
+
+impl PortSetting {
+    /// Fails if the contact points give a port other than the one set
+    fn check_contact_points(&self, contact_points: &ContactPoints) -> Result<Option<u16>> {
+        let port = contact_points.port()?;
+        if let Some(port) = port {
+            check_port(port, self.port)?;
+        }
+        Ok(port)
+    }
+
+    /// Fails if the contact points gave another port
+    fn check_port(&self, port: u16) -> Result<()> {
+        if self.from_contact_points { check_port(port, self.port) } else { Ok(()) }
+    }
+
+    /// Records contact points set on the driver. Clearing them forgets the port they gave.
+    fn contact_points_set(&mut self, contact_points: &ContactPoints, port: Option<u16>) {
+        if contact_points.points().is_empty() {
+            if self.from_contact_points {
+                self.port = None;
+            }
+            self.from_contact_points = false;
+        }
+        if port.is_some() {
+            self.port = port;
+            self.from_contact_points = true;
+        }
+    }
+
+    /// Records a port set on the driver with `set_port`
+    fn port_set(&mut self, port: u16) { self.port = Some(port); }
+}
+
+fn bad_speculative_execution_policy(reason: &str) -> Error {
+    CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                            format!("invalid speculative execution policy: {}", reason))
+        .into()
+}
+
 /// A set of cassandra contact points
-#[derive(Debug)]
-pub struct ContactPoints(Vec<Ipv4Addr>);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactPoints(Vec<ContactPoint>);
+
+impl ContactPoints {
+    /// The contact points in the set
+    pub fn points(&self) -> &[ContactPoint] { &self.0 }
+
+    /// The port shared by the contact points that give one, failing if they disagree
+    fn port(&self) -> Result<Option<u16>> {
+        let mut ports = self.0.iter().filter_map(ContactPoint::port);
+        match ports.next() {
+            Some(port) if ports.all(|other| other == port) => Ok(Some(port)),
+            Some(_) => {
+                Err(CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                                            format!("contact points {} use different ports", self))
+                    .into())
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The hosts in the form the driver expects: a comma separated list without ports
+    fn hosts(&self) -> String {
+        let hosts: Vec<String> = self.0.iter().map(ContactPoint::host).collect();
+        hosts.join(",")
+    }
+}
 
 impl fmt::Display for ContactPoints {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let contact_points: Vec<String> = self.0.iter().map(|ip| format!("{}", ip)).collect();
-        write!(f, "{} ", contact_points.join(","))
+        let contact_points: Vec<String> = self.0.iter().map(|point| point.to_string()).collect();
+        write!(f, "{}", contact_points.join(","))
     }
 }
 
+/// Parses a comma separated list of contact points. An empty string gives no contact points,
+/// which clears them when set on a cluster.
 impl FromStr for ContactPoints {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        let points: Vec<&str> = s.split(',').collect();
-        let contact_points: result::Result<Vec<Ipv4Addr>, AddrParseError> = points.iter()
-            .map(|addr| Ipv4Addr::from_str(addr))
-            .collect();
-        Ok(ContactPoints(contact_points.unwrap()))
+        if s.trim().is_empty() {
+            return Ok(ContactPoints(Vec::new()));
+        }
+        Ok(ContactPoints(s.split(',').map(ContactPoint::from_str).collect::<Result<_>>()?))
     }
 }
 
+impl From<ContactPoint> for ContactPoints {
+    fn from(contact_point: ContactPoint) -> Self { ContactPoints(vec![contact_point]) }
+}
+
+impl From<Vec<ContactPoint>> for ContactPoints {
+    fn from(contact_points: Vec<ContactPoint>) -> Self { ContactPoints(contact_points) }
+}
+
+impl From<IpAddr> for ContactPoints {
+    fn from(addr: IpAddr) -> Self { ContactPoint::Addr(addr, None).into() }
+}
+
+impl From<SocketAddr> for ContactPoints {
+    fn from(addr: SocketAddr) -> Self { ContactPoint::Addr(addr.ip(), Some(addr.port())).into() }
+}
+
 ///
 /// The main class to use when interacting with a Cassandra cluster.
 /// Typically, one instance of this class will be created for each separate
@@ -109,21 +293,43 @@ impl FromStr for ContactPoints {
 /// let mut session = cluster.connect().unwrap();
 /// ```
 #[derive(Debug)]
-pub struct Cluster(pub *mut _Cluster);
+pub struct Cluster {
+    /// The driver's cluster object
+    pub inner: *mut _Cluster,
+    speculative_execution_policy: Option<ConstantSpeculativeExecutionPolicy>,
+    load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
+    execution_profiles: ExecutionProfiles,
+    port: PortSetting,
+    /// The contact points set on the driver, the hosts a load balancing policy routes to
+    contact_points: Vec<ContactPoint>,
+    /// The whitelist set on the driver, given back once the hosts of a load balancing policy
+    /// are connected
+    whitelist: Vec<String>,
+}
 
 impl Drop for Cluster {
     /// Frees a cluster instance.
-    fn drop(&mut self) { unsafe { cass_cluster_free(self.0) } }
+    fn drop(&mut self) { unsafe { cass_cluster_free(self.inner) } }
 }
 
 impl Protected<*mut _Cluster> for Cluster {
-    fn inner(&self) -> *mut _Cluster { self.0 }
-    fn build(inner: *mut _Cluster) -> Self { Cluster(inner) }
+    fn inner(&self) -> *mut _Cluster { self.inner }
+    fn build(inner: *mut _Cluster) -> Self {
+        Cluster {
+            inner,
+            speculative_execution_policy: None,
+            load_balancing_policy: None,
+            execution_profiles: ExecutionProfiles::default(),
+            port: PortSetting::default(),
+            contact_points: Vec::new(),
+            whitelist: Vec::new(),
+        }
+    }
 }
 
 impl Default for Cluster {
     /// Creates a new cluster
-    fn default() -> Cluster { unsafe { Cluster(cass_cluster_new()) } }
+    fn default() -> Cluster { unsafe { Cluster::build(cass_cluster_new()) } }
 }
 
 impl Cluster {
@@ -133,32 +339,49 @@ impl Cluster {
     /// is stripped from the contact points.
     ///
     ///
-    /// {contact points: "127.0.0.1" "127.0.0.1,127.0.0.2", "server1.domain.com"}
-    ///
+    /// {contact points: "127.0.0.1" "127.0.0.1,[::1]:9042", "server1.domain.com:9042"}
     ///
+    /// Host names are resolved by the driver when the cluster connects. The driver
+    /// reaches every host on a single port, so a port given with the contact points is
+    /// set with `set_port`. Contact points giving different ports, or a port other than
+    /// the one already set, are rejected. Clearing the contact points forgets the port
+    /// they gave.
     pub fn set_contact_points<T: Into<ContactPoints>>(&mut self, contact_points: T) -> Result<&mut Self> {
+        let contact_points = contact_points.into();
+        let port = self.port.check_contact_points(&contact_points)?;
+        let hosts = contact_points.hosts();
         unsafe {
-            let s = CString::new(contact_points.into().to_string()).expect("must be utf8");
-            let err = cass_cluster_set_contact_points(self.0, s.as_ptr());
-            err.to_result(self).chain_err(|| "Could not set contact points")
+            cass_cluster_set_contact_points_n(self.inner, hosts.as_ptr() as *const c_char, hosts.len()).wrap(())?;
+            if let Some(port) = port {
+                cass_cluster_set_port(self.inner, port as i32).wrap(())?;
+            }
         }
+        self.port.contact_points_set(&contact_points, port);
+        if contact_points.points().is_empty() {
+            self.contact_points.clear();
+        }
+        self.contact_points.extend_from_slice(contact_points.points());
+        Ok(self)
     }
 
 
-    /// Sets the port
+    /// Sets the port of every host. Fails if the contact points gave another port.
     ///
     ///
     /// Default: 9042
     ///
     pub fn set_port(&mut self, port: u16) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_port(self.0, port as i32).to_result(self).chain_err(|| "Could not set port") }
+        self.port.check_port(port)?;
+        unsafe { cass_cluster_set_port(self.inner, port as i32).wrap(())? };
+        self.port.port_set(port);
+        Ok(self)
     }
 
 
     /// Sets the SSL context and enables SSL
     pub fn set_ssl(&mut self, ssl: &mut Ssl) -> &Self {
         unsafe {
-            cass_cluster_set_ssl(self.0, ssl.inner());
+            cass_cluster_set_ssl(self.inner, ssl.inner());
             self
         }
     }
@@ -166,9 +389,16 @@ impl Cluster {
     /// Performs a blocking call to connect to Cassandra cluster
     pub fn connect(&mut self) -> Result<Session> {
         unsafe {
-            let session = Session(cass_session_new());
-            let connect_future = ConnectFuture::build(cass_session_connect(session.0, self.0));
-            cass_future_error_code(connect_future.inner()).to_result(session).chain_err(|| "Could not connect")
+            let mut session = Session::build(cass_session_new());
+            let connecting = session.configure(self);
+            ConnectFuture::build(cass_session_connect(session.inner, self.inner)).wait()?;
+            for host in connecting {
+                // a host that cannot be reached is passed over by the query plans
+                if let Err(err) = host.wait() {
+                    debug!("failed to connect a load balanced host: {}", err);
+                }
+            }
+            Ok(session)
         }
     }
 
@@ -180,9 +410,8 @@ impl Cluster {
     ///
     pub fn set_protocol_version(&mut self, protocol_version: CqlProtocol) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_protocol_version(self.0, protocol_version as i32)
-                .to_result(self)
-                .chain_err(|| "Couldn't set protocol version")
+            cass_cluster_set_protocol_version(self.inner, protocol_version as i32)
+                .wrap(self)
         }
     }
 
@@ -194,9 +423,8 @@ impl Cluster {
     ///
     pub fn set_num_threads_io(&mut self, num_threads: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_num_threads_io(self.0, num_threads)
-                .to_result(self)
-                .chain_err(|| "couldn't set thread count")
+            cass_cluster_set_num_threads_io(self.inner, num_threads)
+                .wrap(self)
         }
     }
 
@@ -207,9 +435,8 @@ impl Cluster {
     ///
     pub fn set_queue_size_io(&mut self, queue_size: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_queue_size_io(self.0, queue_size)
-                .to_result(self)
-                .chain_err(|| "couldn't set io queue size")
+            cass_cluster_set_queue_size_io(self.inner, queue_size)
+                .wrap(self)
         }
     }
 
@@ -220,9 +447,8 @@ impl Cluster {
     ///
     pub fn set_queue_size_event(&mut self, queue_size: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_queue_size_event(self.0, queue_size)
-                .to_result(self)
-                .chain_err(|| "couldn't set event queue size")
+            cass_cluster_set_queue_size_event(self.inner, queue_size)
+                .wrap(self)
         }
     }
 
@@ -233,9 +459,8 @@ impl Cluster {
     ///
     pub fn set_queue_size_log(&mut self, queue_size: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_queue_size_log(self.0, queue_size)
-                .to_result(self)
-                .chain_err(|| "couldn't set log queue size")
+            cass_cluster_set_queue_size_log(self.inner, queue_size)
+                .wrap(self)
         }
     }
 
@@ -247,9 +472,8 @@ impl Cluster {
     ///
     pub fn set_core_connections_per_host(&mut self, num_connections: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_core_connections_per_host(self.0, num_connections)
-                .to_result(self)
-                .chain_err(|| "couldn't set connections per host")
+            cass_cluster_set_core_connections_per_host(self.inner, num_connections)
+                .wrap(self)
         }
     }
 
@@ -261,9 +485,8 @@ impl Cluster {
     ///
     pub fn set_max_connections_per_host(&mut self, num_connections: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_connections_per_host(self.0, num_connections)
-                .to_result(self)
-                .chain_err(|| "couldn't set max connections per host")
+            cass_cluster_set_max_connections_per_host(self.inner, num_connections)
+                .wrap(self)
         }
     }
 
@@ -274,7 +497,7 @@ impl Cluster {
     ///
     pub fn set_reconnect_wait_time(&mut self, wait_time: u32) -> &Self {
         unsafe {
-            cass_cluster_set_reconnect_wait_time(self.0, wait_time);
+            cass_cluster_set_reconnect_wait_time(self.inner, wait_time);
         }
         self
     }
@@ -287,9 +510,8 @@ impl Cluster {
     /// Default: 1
     pub fn set_max_concurrent_creation(&mut self, num_connections: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_concurrent_creation(self.0, num_connections)
-                .to_result(self)
-                .chain_err(|| "couldn't set max_concurrent_creation")
+            cass_cluster_set_max_concurrent_creation(self.inner, num_connections)
+                .wrap(self)
         }
     }
 
@@ -301,9 +523,8 @@ impl Cluster {
     /// Default: 100
     pub fn set_max_concurrent_requests_threshold(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_concurrent_requests_threshold(self.0, num_requests)
-                .to_result(self)
-                .chain_err(|| "couldn't set max concurrend requests threshold")
+            cass_cluster_set_max_concurrent_requests_threshold(self.inner, num_requests)
+                .wrap(self)
         }
     }
 
@@ -314,9 +535,8 @@ impl Cluster {
     /// Default: 128
     pub fn set_max_requests_per_flush(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_requests_per_flush(self.0, num_requests)
-                .to_result(self)
-                .chain_err(|| "couldn't set max requests per flush")
+            cass_cluster_set_max_requests_per_flush(self.inner, num_requests)
+                .wrap(self)
         }
     }
 
@@ -328,9 +548,8 @@ impl Cluster {
     /// Default: 64KB
     pub fn set_write_bytes_high_water_mark(&mut self, num_bytes: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_write_bytes_high_water_mark(self.0, num_bytes)
-                .to_result(self)
-                .chain_err(|| "couldn't set write bytes high water mark")
+            cass_cluster_set_write_bytes_high_water_mark(self.inner, num_bytes)
+                .wrap(self)
         }
     }
 
@@ -342,9 +561,8 @@ impl Cluster {
     /// Default: 32KB
     pub fn set_write_bytes_low_water_mark(&mut self, num_bytes: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_write_bytes_low_water_mark(self.0, num_bytes)
-                .to_result(self)
-                .chain_err(|| "couldn't set write bytes low water mark")
+            cass_cluster_set_write_bytes_low_water_mark(self.inner, num_bytes)
+                .wrap(self)
         }
     }
 
@@ -357,9 +575,8 @@ impl Cluster {
     /// Default: 256
     pub fn set_pending_requests_high_water_mark(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_pending_requests_high_water_mark(self.0, num_requests)
-                .to_result(self)
-                .chain_err(|| "couldn't set pending requests high water mark")
+            cass_cluster_set_pending_requests_high_water_mark(self.inner, num_requests)
+                .wrap(self)
         }
     }
 
@@ -372,9 +589,8 @@ impl Cluster {
     /// Default: 128
     pub fn set_pending_requests_low_water_mark(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_pending_requests_low_water_mark(self.0, num_requests)
-                .to_result(self)
-                .chain_err(|| "couldn't set pending requests low water mark")
+            cass_cluster_set_pending_requests_low_water_mark(self.inner, num_requests)
+                .wrap(self)
         }
     }
 
@@ -385,7 +601,7 @@ impl Cluster {
     #[allow(cast_possible_truncation,cast_sign_loss)]
     pub fn set_connect_timeout(&mut self, timeout: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_connect_timeout(self.0, timeout.num_milliseconds() as u32);
+            cass_cluster_set_connect_timeout(self.inner, timeout.num_milliseconds() as u32);
         }
         self
     }
@@ -397,28 +613,53 @@ impl Cluster {
     #[allow(cast_possible_truncation,cast_sign_loss)]
     pub fn set_request_timeout(&mut self, timeout: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_request_timeout(self.0, timeout.num_milliseconds() as u32);
+            cass_cluster_set_request_timeout(self.inner, timeout.num_milliseconds() as u32);
         }
         self
     }
 
+    /// Sets the provider of the authenticators answering the authentication exchange of each
+    /// connection, for server side authenticators other than `PasswordAuthenticator`. Replaces
+    /// any credentials set with `set_credentials`.
+    pub fn set_auth_provider<P: AuthProvider + 'static>(&mut self, provider: P) -> Result<&mut Self> {
+        authenticator::set_auth_provider(self.inner, Box::new(provider))?;
+        Ok(self)
+    }
+
     /// Sets credentials for plain text authentication.
     pub fn set_credentials(&mut self, username: &str, password: &str) -> Result<&Self> {
         unsafe {
-            cass_cluster_set_credentials(self.0,
-                                         CString::new(username).chain_err(|| "username not a valid CString")?.as_ptr(),
-                                         CString::new(password).chain_err(|| "password not a valid CString")?.as_ptr());
+            cass_cluster_set_credentials_n(self.inner,
+                                           username.as_ptr() as *const c_char,
+                                           username.len(),
+                                           password.as_ptr() as *const c_char,
+                                           password.len());
         }
         Ok(self)
     }
 
+    /// Sets a load balancing policy written in Rust, for sessions connected to the cluster
+    /// afterwards to route statements by.
+    ///
+    /// The driver cannot be handed such a policy, so it is only applied by
+    /// `Session::execute_balanced`: each session connects one more session to every contact
+    /// point the policy does not ignore, whitelisted to that host alone, and sends each
+    /// statement through the sessions of the hosts of its query plan.
+    ///
+    ///
+    /// Default: none, statements being routed by the driver's policies
+    pub fn set_load_balancing_policy<P: LoadBalancingPolicy + 'static>(&mut self, policy: P) -> &mut Self {
+        self.load_balancing_policy = Some(Arc::new(policy));
+        self
+    }
+
     /// Configures the cluster to use round-robin load balancing.
     ///
     /// The driver discovers all nodes in a cluster and cycles through
     /// them per request. All are considered 'local'.
     pub fn set_load_balance_round_robin(&mut self) -> &Self {
         unsafe {
-            cass_cluster_set_load_balance_round_robin(self.0);
+            cass_cluster_set_load_balance_round_robin(self.inner);
             self
 
         }
@@ -438,15 +679,12 @@ impl Cluster {
         allow_remote_dcs_for_local_cl: cass_bool_t)
                                         -> Result<&mut Self> {
         unsafe {
-            {
-                    let local_dc = CString::new(local_dc).expect("must be utf8");
-                    cass_cluster_set_load_balance_dc_aware(self.0,
-                                                           local_dc.as_ptr(),
-                                                           used_hosts_per_remote_dc,
-                                                           allow_remote_dcs_for_local_cl)
-                }
-                .to_result(self)
-                .chain_err(|| "couldn't set dc aware load balancing policy")
+            cass_cluster_set_load_balance_dc_aware_n(self.inner,
+                                                     local_dc.as_ptr() as *const c_char,
+                                                     local_dc.len(),
+                                                     used_hosts_per_remote_dc,
+                                                     allow_remote_dcs_for_local_cl)
+                .wrap(self)
         }
     }
 
@@ -465,7 +703,7 @@ impl Cluster {
     /// the base load balancing policy.
     pub fn set_token_aware_routing(&mut self, enabled: bool) -> &Self {
         unsafe {
-            cass_cluster_set_token_aware_routing(self.0, if enabled { cass_true } else { cass_false });
+            cass_cluster_set_token_aware_routing(self.inner, if enabled { cass_true } else { cass_false });
         }
         self
     }
@@ -481,7 +719,7 @@ impl Cluster {
     /// placement (token-aware) before considering the latency.
     pub fn set_latency_aware_routing(&mut self, enabled: bool) -> &Self {
         unsafe {
-            cass_cluster_set_latency_aware_routing(self.0, if enabled { cass_true } else { cass_false });
+            cass_cluster_set_latency_aware_routing(self.inner, if enabled { cass_true } else { cass_false });
         }
         self
     }
@@ -504,7 +742,7 @@ impl Cluster {
         retry_period: Duration, update_rate: Duration, min_measured: u64)
                                               -> &Self {
         unsafe {
-            cass_cluster_set_latency_aware_routing_settings(self.0,
+            cass_cluster_set_latency_aware_routing_settings(self.inner,
                                                             exclusion_threshold,
                                                             scale.num_milliseconds() as u64,
                                                             retry_period.num_milliseconds() as u64,
@@ -528,7 +766,60 @@ impl Cluster {
     pub fn set_whitelist_filtering(&mut self, hosts: Vec<String>) -> &Self {
         // FIXME replace host strings with InetSomethings
         unsafe {
-            cass_cluster_set_whitelist_filtering(self.0, hosts.join(",").as_ptr() as *const i8);
+            let hosts = hosts.join(",");
+            cass_cluster_set_whitelist_filtering_n(self.inner, hosts.as_ptr() as *const c_char, hosts.len());
+        }
+        if hosts.is_empty() {
+            self.whitelist.clear();
+        }
+        self.whitelist.extend(hosts);
+        self
+    }
+
+    /// Sets/Appends blacklist hosts. The first call sets the blacklist hosts and
+    /// any subsequent calls appends additional hosts. Passing an empty list will
+    /// clear and disable the blacklist.
+    ///
+    /// This policy filters requests to all other policies, only allowing requests
+    /// to the hosts not contained in the blacklist. Any host in the blacklist will
+    /// be ignored and a connection will not be established.
+    ///
+    ///
+    /// Examples: "127.0.0.1" "127.0.0.1,127.0.0.2", "server1.domain.com"
+    pub fn set_blacklist_filtering(&mut self, hosts: Vec<String>) -> &Self {
+        unsafe {
+            let hosts = hosts.join(",");
+            cass_cluster_set_blacklist_filtering_n(self.inner, hosts.as_ptr() as *const c_char, hosts.len());
+        }
+        self
+    }
+
+    /// Sets/Appends whitelist data centers. The first call sets the whitelist data
+    /// centers and any subsequent calls appends additional data centers. Passing an
+    /// empty list will clear and disable the data center whitelist.
+    ///
+    /// This policy filters requests to all other policies, only allowing requests
+    /// to the hosts in the whitelisted data centers. Hosts in any other data center
+    /// will be ignored and a connection will not be established.
+    pub fn set_whitelist_dc_filtering(&mut self, dcs: Vec<String>) -> &Self {
+        unsafe {
+            let dcs = dcs.join(",");
+            cass_cluster_set_whitelist_dc_filtering_n(self.inner, dcs.as_ptr() as *const c_char, dcs.len());
+        }
+        self
+    }
+
+    /// Sets/Appends blacklist data centers. The first call sets the blacklist data
+    /// centers and any subsequent calls appends additional data centers. Passing an
+    /// empty list will clear and disable the data center blacklist.
+    ///
+    /// This policy filters requests to all other policies, only allowing requests
+    /// to the hosts outside the blacklisted data centers. Hosts in a blacklisted data
+    /// center will be ignored and a connection will not be established.
+    pub fn set_blacklist_dc_filtering(&mut self, dcs: Vec<String>) -> &Self {
+        unsafe {
+            let dcs = dcs.join(",");
+            cass_cluster_set_blacklist_dc_filtering_n(self.inner, dcs.as_ptr() as *const c_char, dcs.len());
         }
         self
     }
@@ -539,7 +830,7 @@ impl Cluster {
     /// <b>Default:</b> true (disables Nagel's algorithm).
     pub fn set_tcp_nodelay(&mut self, enable: bool) -> &Self {
         unsafe {
-            cass_cluster_set_tcp_nodelay(self.0, if enable { cass_true } else { cass_false });
+            cass_cluster_set_tcp_nodelay(self.inner, if enable { cass_true } else { cass_false });
         }
         self
     }
@@ -551,7 +842,7 @@ impl Cluster {
     #[allow(cast_possible_truncation,cast_sign_loss)]
     pub fn set_tcp_keepalive(&mut self, enable: bool, delay: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_tcp_keepalive(self.0,
+            cass_cluster_set_tcp_keepalive(self.inner,
                                            if enable { cass_true } else { cass_false },
                                            delay.num_seconds() as u32);
         }
@@ -565,7 +856,7 @@ impl Cluster {
     /// Default: server-side timestamp generator.
     pub fn set_timestamp_gen(&mut self, tsg: &TimestampGen) -> &mut Self {
         unsafe {
-            cass_cluster_set_timestamp_gen(self.0, TimestampGen::inner(tsg));
+            cass_cluster_set_timestamp_gen(self.inner, TimestampGen::inner(tsg));
             self
         }
     }
@@ -580,7 +871,7 @@ impl Cluster {
     #[allow(cast_possible_truncation,cast_sign_loss)]
     pub fn set_connection_heartbeat_interval(&mut self, hearbeat: Duration) -> &mut Self {
         unsafe {
-            cass_cluster_set_connection_heartbeat_interval(self.0, hearbeat.num_seconds() as u32);
+            cass_cluster_set_connection_heartbeat_interval(self.inner, hearbeat.num_seconds() as u32);
             self
         }
     }
@@ -593,7 +884,7 @@ impl Cluster {
     #[allow(cast_possible_truncation,cast_sign_loss)]
     pub fn set_connection_idle_timeout(&mut self, timeout: Duration) -> &mut Self {
         unsafe {
-            cass_cluster_set_connection_idle_timeout(self.0, timeout.num_seconds() as u32);
+            cass_cluster_set_connection_idle_timeout(self.inner, timeout.num_seconds() as u32);
             self
         }
     }
@@ -608,13 +899,74 @@ impl Cluster {
     /// logged batch request failed to write the batch log, and on a unavailable
     /// error it retries using a new host. In all other cases the default policy
     /// will return an error.
-    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+    ///
+    /// A policy written in Rust with `RetryPolicy::custom` is refused, since the driver would
+    /// never retry with it: pass it to `Session::execute_with_retry` or
+    /// `Session::execute_batch_with_retry` instead.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> Result<&mut Self> {
+        retry_policy.check_driver_policy()?;
         unsafe {
-            cass_cluster_set_retry_policy(self.0, retry_policy.inner());
-            self
+            cass_cluster_set_retry_policy(self.inner, retry_policy.inner());
+        }
+        Ok(self)
+    }
+
+    /// Sets a constant speculative execution policy, for sessions connected to the cluster
+    /// afterwards to execute idempotent prepared statements again when no answer arrived
+    /// after `delay`, up to `max_executions` times in total.
+    ///
+    /// The driver has no speculative executions of its own, so they are only made by
+    /// `Session::execute_speculative`.
+    ///
+    ///
+    /// Default: no speculative executions
+    pub fn set_constant_speculative_execution_policy(&mut self, delay: Duration, max_executions: u32)
+                                                     -> Result<&mut Self> {
+        let delay = delay.to_std().map_err(|_| bad_speculative_execution_policy("delay must not be negative"))?;
+        if max_executions == 0 {
+            return Err(bad_speculative_execution_policy("max_executions must be at least 1"));
         }
+        self.speculative_execution_policy = Some(ConstantSpeculativeExecutionPolicy {
+            delay,
+            max_executions,
+        });
+        Ok(self)
     }
 
+    /// Registers an execution profile under `name`, replacing any profile of that name.
+    /// Sessions connected to the cluster afterwards look profiles up with
+    /// `Session::execution_profile`.
+    pub fn set_execution_profile(&mut self, name: &str, profile: ExecutionProfile) -> &mut Self {
+        self.execution_profiles.insert(name.to_owned(), profile);
+        self
+    }
+
+    /// The speculative execution policy handed to the sessions connected to the cluster
+    pub(crate) fn speculative_execution_policy(&self) -> Option<ConstantSpeculativeExecutionPolicy> {
+        self.speculative_execution_policy
+    }
+
+    /// Starts connecting a session to each host of the load balancing policies of the cluster
+    /// and its execution profiles, if there are any
+    pub(crate) fn connect_balanced_hosts(&self) -> Option<(BalancedHosts, Vec<ConnectFuture>)> {
+        let policies: Vec<_> = self.load_balancing_policy
+            .iter()
+            .chain(self.execution_profiles.load_balancing_policies())
+            .cloned()
+            .collect();
+        if policies.is_empty() {
+            return None;
+        }
+        Some(BalancedHosts::connect(self.load_balancing_policy.clone(),
+                                    &policies,
+                                    self,
+                                    &self.contact_points,
+                                    &self.whitelist))
+    }
+
+    /// The execution profiles handed to the sessions connected to the cluster
+    pub(crate) fn execution_profiles(&self) -> &ExecutionProfiles { &self.execution_profiles }
+
     /// Enable/Disable retrieving and updating schema metadata. If disabled
     /// this is allows the driver to skip over retrieving and updating schema
     /// metadata, but it also disables the usage of token-aware routing and
@@ -625,8 +977,72 @@ impl Cluster {
     /// Default: true (enabled).
     pub fn set_use_schema(&mut self, enabled: bool) -> &Self {
         unsafe {
-            cass_cluster_set_use_schema(self.0, if enabled { cass_true } else { cass_false });
+            cass_cluster_set_use_schema(self.inner, if enabled { cass_true } else { cass_false });
         }
         self
     }
 }
+
+#[test]
+fn test_contact_points_from_str() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let points = ContactPoints::from_str("127.0.0.1, 10.0.0.2:9043,::1,[fe80::1]:9042, db-1.example.com,db2:9042")
+        .unwrap();
+    assert_eq!(points.points(),
+               &[ContactPoint::Addr(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), None),
+                 ContactPoint::Addr(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), Some(9043)),
+                 ContactPoint::Addr(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), None),
+                 ContactPoint::Addr(IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)), Some(9042)),
+                 ContactPoint::Host("db-1.example.com".to_owned(), None),
+                 ContactPoint::Host("db2".to_owned(), Some(9042))]);
+    assert_eq!(points.to_string(),
+               "127.0.0.1,10.0.0.2:9043,::1,[fe80::1]:9042,db-1.example.com,db2:9042");
+    assert_eq!(points.hosts(), "127.0.0.1,10.0.0.2,::1,fe80::1,db-1.example.com,db2");
+    assert!(points.port().is_err());
+    assert_eq!(ContactPoints::from_str("a:9042,b:9042,c").unwrap().port().unwrap(), Some(9042));
+    assert_eq!(ContactPoints::from_str("a,b").unwrap().port().unwrap(), None);
+
+    let cleared = ContactPoints::from_str(" ").unwrap();
+    assert!(cleared.points().is_empty());
+    assert_eq!(cleared.hosts(), "");
+
+    assert!(check_port(9042, None).is_ok());
+    assert!(check_port(9042, Some(9042)).is_ok());
+    assert!(check_port(9043, Some(9042)).is_err());
+
+    let mut setting = PortSetting::default();
+    for &(points, port) in &[("a:9042", Some(9042)), ("", None), ("b:9043", Some(9043))] {
+        let points = ContactPoints::from_str(points).unwrap();
+        assert_eq!(setting.check_contact_points(&points).unwrap(), port);
+        setting.contact_points_set(&points, port);
+    }
+    assert!(setting.check_contact_points(&ContactPoints::from_str("c:9044").unwrap()).is_err());
+    assert!(setting.check_port(9044).is_err());
+    assert!(setting.check_port(9043).is_ok());
+
+    let mut setting = PortSetting::default();
+    setting.port_set(9043);
+    assert!(setting.check_port(9044).is_ok());
+    assert!(setting.check_contact_points(&ContactPoints::from_str("a:9042").unwrap()).is_err());
+    setting.contact_points_set(&ContactPoints::from_str("").unwrap(), None);
+    assert_eq!(setting.port, Some(9043));
+
+    for bad in &["127.0.0.1,", ",", "host:", "host:65536", "[::1]", "[::1]:x", "-host", "a..b", "a b", "a:b:c"] {
+        assert!(ContactPoints::from_str(bad).is_err(), "{:?} should not parse", bad);
+    }
+}
+
+#[test]
+fn test_contact_points_from_str_fuzz() {
+    use cassandra::util::fuzz_inputs;
+
+    for input in fuzz_inputs(b"0123456789abcdef.:[], -\0", 10_000) {
+        let input = String::from_utf8_lossy(&input);
+        if let Ok(points) = ContactPoints::from_str(&input) {
+            let expected = if input.trim().is_empty() { 0 } else { input.split(',').count() };
+            assert_eq!(points.points().len(), expected);
+            assert_eq!(ContactPoints::from_str(&points.to_string()).unwrap(), points);
+        }
+    }
+}
@@ -3,18 +3,24 @@ use cassandra::data_type::DataType;
 
 use cassandra::error::CassError;
 use cassandra::inet::Inet;
+use cassandra::numeric::{encode_decimal, encode_varint};
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
 use cassandra::tuple::Tuple;
 use cassandra::user_type::UserType;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 use cassandra::uuid::Uuid;
 use cassandra_sys::CASS_COLLECTION_TYPE_LIST;
 use cassandra_sys::CASS_COLLECTION_TYPE_MAP;
 use cassandra_sys::CASS_COLLECTION_TYPE_SET;
+use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
+use cassandra_sys::CASS_VALUE_TYPE_LIST;
+use cassandra_sys::CASS_VALUE_TYPE_MAP;
+use cassandra_sys::CASS_VALUE_TYPE_SET;
+use cassandra_sys::CassCollectionType;
 use cassandra_sys::CassCollection as _CassCollection;
 use cassandra_sys::cass_collection_append_bool;
 use cassandra_sys::cass_collection_append_bytes;
 use cassandra_sys::cass_collection_append_collection;
-#[allow(unused_imports)]
 use cassandra_sys::cass_collection_append_decimal;
 use cassandra_sys::cass_collection_append_double;
 use cassandra_sys::cass_collection_append_float;
@@ -23,7 +29,7 @@ use cassandra_sys::cass_collection_append_int16;
 use cassandra_sys::cass_collection_append_int32;
 use cassandra_sys::cass_collection_append_int64;
 use cassandra_sys::cass_collection_append_int8;
-use cassandra_sys::cass_collection_append_string;
+use cassandra_sys::cass_collection_append_string_n;
 use cassandra_sys::cass_collection_append_tuple;
 use cassandra_sys::cass_collection_append_uint32;
 use cassandra_sys::cass_collection_append_user_type;
@@ -32,10 +38,17 @@ use cassandra_sys::cass_collection_data_type;
 use cassandra_sys::cass_collection_free;
 use cassandra_sys::cass_collection_new;
 use cassandra_sys::cass_collection_new_from_data_type;
+use cassandra_sys::cass_data_type_sub_data_type;
+use cassandra_sys::cass_data_type_type;
 use cassandra_sys::cass_false;
 use cassandra_sys::cass_true;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use decimal::d128;
+use num::BigInt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::os::raw::c_char;
 
 // #[repr(C)]
 // #[derive(Debug,Copy,Clone)]
@@ -90,6 +103,24 @@ pub trait CassCollection {
     /// Appends a "blob", "varint" or "custom" to the collection.
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self>;
 
+    /// Appends a "decimal" to the collection.
+    fn append_decimal(&mut self, value: d128) -> Result<&mut Self>;
+
+    /// Appends a "varint" to the collection.
+    fn append_varint(&mut self, value: &BigInt) -> Result<&mut Self>;
+
+    /// Appends a "date" to the collection.
+    fn append_date(&mut self, value: CqlDate) -> Result<&mut Self> { self.append_uint32(value.0) }
+
+    /// Appends a "time" to the collection.
+    fn append_time(&mut self, value: CqlTime) -> Result<&mut Self> { self.append_int64(value.0) }
+
+    /// Appends a "timestamp" to the collection.
+    fn append_timestamp(&mut self, value: CqlTimestamp) -> Result<&mut Self> { self.append_int64(value.0) }
+
+    /// Appends a "duration" to the collection.
+    fn append_duration(&mut self, value: CqlDuration) -> Result<&mut Self> { self.append_bytes(value.to_bytes()) }
+
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self>;
 
@@ -154,55 +185,52 @@ impl CassCollection for List {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self).chain_err(|| "couldn't append i8") }
+        unsafe { cass_collection_append_int8(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int16(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uint32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int64(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_float(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_double(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "boolean" to the collection.
     fn append_bool(&mut self, value: bool) -> Result<&mut Self> {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Appends an "ascii", "text" or "varchar" to the collection.
     fn append_string(&mut self, value: &str) -> Result<&mut Self> {
         unsafe {
-            let cstr = CString::new(value).expect("must be utf8");
-            let result = cass_collection_append_string(self.inner(), cstr.as_ptr());
-            result.to_result(self).chain_err(|| "")
+            cass_collection_append_string_n(self.inner(), value.as_ptr() as *const c_char, value.len()).wrap(self)
         }
     }
 
@@ -210,43 +238,52 @@ impl CassCollection for List {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self).chain_err(|| "")
+            bytes.wrap(self)
         }
     }
 
+    /// Appends a "decimal" to the collection.
+    fn append_decimal(&mut self, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe { cass_collection_append_decimal(self.inner(), varint.as_ptr(), varint.len(), scale).wrap(self) }
+    }
+
+    /// Appends a "varint" to the collection.
+    fn append_varint(&mut self, value: &BigInt) -> Result<&mut Self> { self.append_bytes(encode_varint(value)) }
+
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_inet(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).wrap(self) }
     }
 }
 
@@ -279,55 +316,52 @@ impl CassCollection for Set {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int8(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int16(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uint32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int64(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_float(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_double(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "boolean" to the collection.
     fn append_bool(&mut self, value: bool) -> Result<&mut Self> {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Appends an "ascii", "text" or "varchar" to the collection.
     fn append_string(&mut self, value: &str) -> Result<&mut Self> {
         unsafe {
-            let cstr = CString::new(value).expect("must be utf8");
-            let result = cass_collection_append_string(self.inner(), cstr.as_ptr());
-            result.to_result(self).chain_err(|| "")
+            cass_collection_append_string_n(self.inner(), value.as_ptr() as *const c_char, value.len()).wrap(self)
         }
     }
 
@@ -335,43 +369,52 @@ impl CassCollection for Set {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self).chain_err(|| "")
+            bytes.wrap(self)
         }
     }
 
+    /// Appends a "decimal" to the collection.
+    fn append_decimal(&mut self, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe { cass_collection_append_decimal(self.inner(), varint.as_ptr(), varint.len(), scale).wrap(self) }
+    }
+
+    /// Appends a "varint" to the collection.
+    fn append_varint(&mut self, value: &BigInt) -> Result<&mut Self> { self.append_bytes(encode_varint(value)) }
+
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_inet(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).wrap(self) }
     }
 }
 
@@ -401,55 +444,52 @@ impl CassCollection for Map {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int8(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int16(self.inner(), value).wrap(self) }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uint32(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_int64(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_float(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_double(self.inner(), value).wrap(self) }
     }
 
     /// Appends a "boolean" to the collection.
     fn append_bool(&mut self, value: bool) -> Result<&mut Self> {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Appends an "ascii", "text" or "varchar" to the collection.
     fn append_string(&mut self, value: &str) -> Result<&mut Self> {
         unsafe {
-            let cstr = CString::new(value).expect("must be utf8");
-            let result = cass_collection_append_string(self.inner(), cstr.as_ptr());
-            result.to_result(self).chain_err(|| "")
+            cass_collection_append_string_n(self.inner(), value.as_ptr() as *const c_char, value.len()).wrap(self)
         }
     }
 
@@ -457,42 +497,252 @@ impl CassCollection for Map {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self).chain_err(|| "")
+            bytes.wrap(self)
         }
     }
 
+    /// Appends a "decimal" to the collection.
+    fn append_decimal(&mut self, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe { cass_collection_append_decimal(self.inner(), varint.as_ptr(), varint.len(), scale).wrap(self) }
+    }
+
+    /// Appends a "varint" to the collection.
+    fn append_varint(&mut self, value: &BigInt) -> Result<&mut Self> { self.append_bytes(encode_varint(value)) }
+
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_inet(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_collection(self.inner(), value.0).wrap(self) }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).wrap(self) }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self).chain_err(|| "") }
+        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).wrap(self) }
+    }
+}
+
+/// A Rust value that can be appended to a list, set or map.
+///
+/// Implemented for the scalar types that bind to a statement, for the driver's own collection,
+/// tuple and user type values, and for `Vec`, `HashSet`, `BTreeSet`, `HashMap` and `BTreeMap` of
+/// items, so that nested collections such as `Vec<HashMap<String, i64>>` convert as a whole.
+pub trait CollectionItem {
+    /// Appends the value to the collection. When `data_type` is given, it is the type the
+    /// collection expects for this item, and nested collections are built against it.
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()>;
+}
+
+macro_rules! collection_item {
+    ( $typ:ty, |$value:ident| $append:ident($arg:expr) ) => {
+        impl CollectionItem for $typ {
+            fn append_to<C: CassCollection>(self, collection: &mut C, _: Option<&ConstDataType>) -> Result<()> {
+                let $value = self;
+                collection.$append($arg).map(|_| ())
+            }
+        }
+    };
+}
+
+collection_item!(bool, |value| append_bool(value));
+collection_item!(i8, |value| append_int8(value));
+collection_item!(i16, |value| append_int16(value));
+collection_item!(i32, |value| append_int32(value));
+collection_item!(i64, |value| append_int64(value));
+collection_item!(f32, |value| append_float(value));
+collection_item!(f64, |value| append_double(value));
+collection_item!(String, |value| append_string(&value));
+collection_item!(Vec<u8>, |value| append_bytes(value));
+collection_item!(d128, |value| append_decimal(value));
+collection_item!(BigInt, |value| append_varint(&value));
+collection_item!(CqlDate, |value| append_date(value));
+collection_item!(CqlTime, |value| append_time(value));
+collection_item!(CqlTimestamp, |value| append_timestamp(value));
+collection_item!(CqlDuration, |value| append_duration(value));
+collection_item!(NaiveDate, |value| append_date(CqlDate::from(value)));
+collection_item!(NaiveTime, |value| append_time(CqlTime::from(value)));
+collection_item!(DateTime<Utc>, |value| append_timestamp(CqlTimestamp::from(value)));
+collection_item!(Uuid, |value| append_uuid(value));
+collection_item!(Inet, |value| append_inet(value));
+collection_item!(List, |value| append_list(value));
+collection_item!(Set, |value| append_set(value));
+collection_item!(Map, |value| append_map(value));
+collection_item!(Tuple, |value| append_tuple(value));
+collection_item!(UserType, |value| append_user_type(&value));
+
+impl CollectionItem for &str {
+    fn append_to<C: CassCollection>(self, collection: &mut C, _: Option<&ConstDataType>) -> Result<()> {
+        collection.append_string(self).map(|_| ())
+    }
+}
+
+impl<T: CollectionItem> CollectionItem for Vec<T> {
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()> {
+        collection.append_list(List::from_items(data_type, self)?).map(|_| ())
+    }
+}
+
+impl<T: CollectionItem> CollectionItem for HashSet<T> {
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()> {
+        collection.append_set(Set::from_items(data_type, self)?).map(|_| ())
+    }
+}
+
+impl<T: CollectionItem> CollectionItem for BTreeSet<T> {
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()> {
+        collection.append_set(Set::from_items(data_type, self)?).map(|_| ())
+    }
+}
+
+impl<K: CollectionItem, V: CollectionItem> CollectionItem for HashMap<K, V> {
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()> {
+        collection.append_map(Map::from_entries(data_type, self)?).map(|_| ())
     }
 }
+
+impl<K: CollectionItem, V: CollectionItem> CollectionItem for BTreeMap<K, V> {
+    fn append_to<C: CassCollection>(self, collection: &mut C, data_type: Option<&ConstDataType>) -> Result<()> {
+        collection.append_map(Map::from_entries(data_type, self)?).map(|_| ())
+    }
+}
+
+/// Creates an empty collection of the given kind. When `data_type` is given the collection is
+/// built from it, so the driver rejects items of any other type as they are appended.
+fn new_collection(kind: CassCollectionType,
+                  data_type: Option<&ConstDataType>,
+                  item_count: usize)
+                  -> Result<*mut _CassCollection> {
+    let data_type = match data_type {
+        Some(data_type) if !data_type.0.is_null() => data_type,
+        _ => return Ok(unsafe { cass_collection_new(kind, item_count) }),
+    };
+    let expected = unsafe { cass_data_type_type(data_type.0) };
+    match (kind, expected) {
+        (CASS_COLLECTION_TYPE_LIST, CASS_VALUE_TYPE_LIST) |
+        (CASS_COLLECTION_TYPE_SET, CASS_VALUE_TYPE_SET) |
+        (CASS_COLLECTION_TYPE_MAP, CASS_VALUE_TYPE_MAP) => unsafe {
+            Ok(cass_collection_new_from_data_type(data_type.0, item_count))
+        },
+        _ => {
+            Err(CassError::with_message(CASS_ERROR_LIB_INVALID_VALUE_TYPE,
+                                        format!("cannot use a {:?} where a {:?} is expected", kind, expected))
+                .into())
+        }
+    }
+}
+
+/// Gets the type expected for the items at `index` of a collection type, if it declares one
+fn item_data_type(data_type: Option<&ConstDataType>, index: usize) -> Option<ConstDataType> {
+    data_type.filter(|data_type| !data_type.0.is_null())
+        .map(|data_type| unsafe { ConstDataType(cass_data_type_sub_data_type(data_type.0, index)) })
+        .filter(|item_type| !item_type.0.is_null())
+}
+
+impl List {
+    /// Creates a list of `items`, checking them against the list type `data_type` if one is given.
+    pub fn from_items<I>(data_type: Option<&ConstDataType>, items: I) -> Result<Self>
+        where I: IntoIterator,
+              I::IntoIter: ExactSizeIterator,
+              I::Item: CollectionItem {
+        let items = items.into_iter();
+        let mut list = List(new_collection(CASS_COLLECTION_TYPE_LIST, data_type, items.len())?);
+        let item_type = item_data_type(data_type, 0);
+        for item in items {
+            item.append_to(&mut list, item_type.as_ref())?;
+        }
+        Ok(list)
+    }
+}
+
+impl Set {
+    /// Creates a set of `items`, checking them against the set type `data_type` if one is given.
+    pub fn from_items<I>(data_type: Option<&ConstDataType>, items: I) -> Result<Self>
+        where I: IntoIterator,
+              I::IntoIter: ExactSizeIterator,
+              I::Item: CollectionItem {
+        let items = items.into_iter();
+        let mut set = Set(new_collection(CASS_COLLECTION_TYPE_SET, data_type, items.len())?);
+        let item_type = item_data_type(data_type, 0);
+        for item in items {
+            item.append_to(&mut set, item_type.as_ref())?;
+        }
+        Ok(set)
+    }
+}
+
+impl Map {
+    /// Creates a map of `entries`, checking them against the map type `data_type` if one is given.
+    pub fn from_entries<I, K, V>(data_type: Option<&ConstDataType>, entries: I) -> Result<Self>
+        where I: IntoIterator<Item = (K, V)>,
+              I::IntoIter: ExactSizeIterator,
+              K: CollectionItem,
+              V: CollectionItem {
+        let entries = entries.into_iter();
+        let mut map = Map(new_collection(CASS_COLLECTION_TYPE_MAP, data_type, entries.len())?);
+        let (key_type, value_type) = (item_data_type(data_type, 0), item_data_type(data_type, 1));
+        for (key, value) in entries {
+            key.append_to(&mut map, key_type.as_ref())?;
+            value.append_to(&mut map, value_type.as_ref())?;
+        }
+        Ok(map)
+    }
+}
+
+/// Fails if an item cannot be encoded at all, such as a non-finite decimal
+impl<T: CollectionItem> TryFrom<Vec<T>> for List {
+    type Error = Error;
+
+    fn try_from(items: Vec<T>) -> Result<Self> { List::from_items(None, items) }
+}
+
+/// Fails if an item cannot be encoded at all, such as a non-finite decimal
+impl<T: CollectionItem> TryFrom<HashSet<T>> for Set {
+    type Error = Error;
+
+    fn try_from(items: HashSet<T>) -> Result<Self> { Set::from_items(None, items) }
+}
+
+/// Fails if an item cannot be encoded at all, such as a non-finite decimal
+impl<T: CollectionItem> TryFrom<BTreeSet<T>> for Set {
+    type Error = Error;
+
+    fn try_from(items: BTreeSet<T>) -> Result<Self> { Set::from_items(None, items) }
+}
+
+/// Fails if an entry cannot be encoded at all, such as a non-finite decimal
+impl<K: CollectionItem, V: CollectionItem> TryFrom<HashMap<K, V>> for Map {
+    type Error = Error;
+
+    fn try_from(entries: HashMap<K, V>) -> Result<Self> { Map::from_entries(None, entries) }
+}
+
+/// Fails if an entry cannot be encoded at all, such as a non-finite decimal
+impl<K: CollectionItem, V: CollectionItem> TryFrom<BTreeMap<K, V>> for Map {
+    type Error = Error;
+
+    fn try_from(entries: BTreeMap<K, V>) -> Result<Self> { Map::from_entries(None, entries) }
+}
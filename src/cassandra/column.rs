@@ -3,14 +3,14 @@
 use cassandra::inet::Inet;
 use cassandra::iterator::MapIterator;
 use cassandra::iterator::SetIterator;
-use cassandra::iterator::UserTypeIterator;
-use cassandra::util::Protected;
+use cassandra::iterator::{LendingIterator, TupleIterator, UserTypeFieldIterator};
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
+use cassandra::util::{CassErrorExt, Protected};
 use cassandra::uuid::Uuid;
-use cassandra::value::ValueType;
+use cassandra::value::{Value, ValueType};
 use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
 
 // use cassandra_sys::Enum_CassValueType_::*;
-use cassandra_sys::CASS_OK;
 use cassandra_sys::CASS_VALUE_TYPE_ASCII;
 use cassandra_sys::CASS_VALUE_TYPE_BIGINT;
 use cassandra_sys::CASS_VALUE_TYPE_BLOB;
@@ -42,7 +42,6 @@ use cassandra_sys::CASS_VALUE_TYPE_UUID;
 use cassandra_sys::CASS_VALUE_TYPE_VARCHAR;
 use cassandra_sys::CASS_VALUE_TYPE_VARINT;
 use cassandra_sys::CassValue as _Value;
-use cassandra_sys::cass_iterator_fields_from_user_type;
 use cassandra_sys::cass_iterator_from_collection;
 use cassandra_sys::cass_iterator_from_map;
 use cassandra_sys::cass_true;
@@ -57,19 +56,20 @@ use cassandra_sys::cass_value_get_int16;
 use cassandra_sys::cass_value_get_int32;
 use cassandra_sys::cass_value_get_int64;
 use cassandra_sys::cass_value_get_int8;
-use cassandra_sys::cass_value_get_string;
 use cassandra_sys::cass_value_get_uint32;
 use cassandra_sys::cass_value_get_uuid;
 use cassandra_sys::cass_value_type;
-use errors::*;
+use cassandra::error::*;
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::marker::PhantomData;
 use std::mem;
-use std::slice;
 use std::str;
-// use decimal::d128;
+use decimal::d128;
+use num::BigInt;
 
 // #[repr(C)]
 // #[derive(Copy,Debug,Clone)]
@@ -89,15 +89,15 @@ use std::str;
 //    UNKNOWN = 5,
 // }
 
-/// Representation of a Cassandra column
-pub struct Column(*const _Value);
+/// Representation of a Cassandra column, borrowed from the row it was read from
+pub struct Column<'a>(*const _Value, PhantomData<&'a _Value>);
 
-impl Protected<*const _Value> for Column {
+impl<'a> Protected<*const _Value> for Column<'a> {
     fn inner(&self) -> *const _Value { self.0 }
-    fn build(inner: *const _Value) -> Self { Column(inner) }
+    fn build(inner: *const _Value) -> Self { Column(inner, PhantomData) }
 }
 
-impl Debug for Column {
+impl<'a> Debug for Column<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self.get_type().inner() {
             CASS_VALUE_TYPE_UNKNOWN => write!(f, "UNKNOWN Cassandra type"),
@@ -123,19 +123,22 @@ impl Debug for Column {
             CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID Cassandra type"),
             CASS_VALUE_TYPE_INET => write!(f, "INET Cassandra type"),
             CASS_VALUE_TYPE_LIST => {
-                for item in self.set_iter().expect("a list should always be able to return a set iterator") {
+                let mut items = self.get_list().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {:?}", item)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_MAP => {
-                for item in self.map_iter().expect("a map should always be able to return a map iterator") {
+                let mut items = self.map_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {:?}", item)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_SET => {
-                for item in self.set_iter().expect("a set must be able to return a set iterator") {
+                let mut items = self.set_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "SET {:?}", item)?
                 }
                 Ok(())
@@ -147,7 +150,7 @@ impl Debug for Column {
     }
 }
 
-impl Display for Column {
+impl<'a> Display for Column<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self.get_type().inner() {
             CASS_VALUE_TYPE_UNKNOWN => write!(f, "UNKNOWN Cassandra type"),
@@ -168,24 +171,27 @@ impl Display for Column {
             CASS_VALUE_TYPE_TEXT => write!(f, "TEXT Cassandra type"),
             CASS_VALUE_TYPE_TIMESTAMP => write!(f, "TIMESTAMP Cassandra type"),
             CASS_VALUE_TYPE_UUID => write!(f, "UUID Cassandra type"),
-            CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string().unwrap()),
+            CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string_lossy().map_err(|_| fmt::Error)?),
             CASS_VALUE_TYPE_VARINT => Ok(()),
             CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID Cassandra type"),
             CASS_VALUE_TYPE_INET => write!(f, "INET Cassandra type"),
             CASS_VALUE_TYPE_LIST => {
-                for item in self.set_iter().expect("a list must be able to return a set iterator") {
+                let mut items = self.get_list().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {}", item)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_MAP => {
-                for item in self.map_iter().expect("a map must be able to return a map iterator") {
+                let mut items = self.map_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "MAP {}:{}", item.0, item.1)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_SET => {
-                for item in self.set_iter().expect("a set must be able to return a set iterator") {
+                let mut items = self.set_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "SET {}", item)?
                 }
                 Ok(())
@@ -202,8 +208,8 @@ impl Display for Column {
 //    fn get(T) -> Result<Self::T, CassError>;
 // }
 
-impl From<Column> for Result<bool> {
-    fn from(col: Column) -> Result<bool> { col.get_bool() }
+impl<'a> From<Column<'a>> for Result<bool> {
+    fn from(col: Column<'a>) -> Result<bool> { col.get_bool() }
 }
 
 // impl Into<Result<bool,CassError>> for Column {
@@ -219,7 +225,7 @@ impl From<Column> for Result<bool> {
 // }
 
 
-impl Column {
+impl<'a> Column<'a> {
     /// Gets the type of this column.
     pub fn get_type(&self) -> ValueType { unsafe { ValueType::build(cass_value_type(self.0)) } }
 
@@ -227,70 +233,74 @@ impl Column {
     pub fn get_inet(&self) -> Result<Inet> {
         unsafe {
             let mut inet = mem::zeroed();
-            cass_value_get_inet(self.0, &mut inet).to_result(Inet::build(inet)).chain_err(|| "")
+            cass_value_get_inet(self.0, &mut inet).wrap(Inet::build(inet))
         }
     }
 
     /// Gets the u32 from this column or errors if you ask for the wrong type
     pub fn get_u32(&self, mut output: u32) -> Result<u32> {
-        unsafe { cass_value_get_uint32(self.0, &mut output).to_result(output).chain_err(|| "") }
+        unsafe { cass_value_get_uint32(self.0, &mut output).wrap(output) }
     }
 
     /// Gets the i8 from this column or errors if you ask for the wrong type
     pub fn get_i8(&self, mut output: i8) -> Result<i8> {
-        unsafe { cass_value_get_int8(self.0, &mut output).to_result(output).chain_err(|| "") }
+        unsafe { cass_value_get_int8(self.0, &mut output).wrap(output) }
     }
 
     /// Gets the i16 from this column or errors if you ask for the wrong type
     pub fn get_i16(&self, mut output: i16) -> Result<i16> {
-        unsafe { cass_value_get_int16(self.0, &mut output).to_result(output).chain_err(|| "") }
+        unsafe { cass_value_get_int16(self.0, &mut output).wrap(output) }
     }
 
-    //    pub fn get_decimal(&self, mut output: d128) -> Result<d128, CassError> {
-    //        let _ = output;
-    //        unimplemented!()
-    //        // unsafe { CassError::build(cass_value_get_decimal(self.0, &mut output)).wrap(output) }
-    //    }
+    /// Gets the d128 from this column or errors if you ask for the wrong type
+    pub fn get_decimal(&self) -> Result<d128> { Value::build(self.0).get_decimal() }
+
+    /// Gets the BigInt from this column or errors if you ask for the wrong type
+    pub fn get_varint(&self) -> Result<BigInt> { Value::build(self.0).get_varint() }
+
+    /// Gets the date from this column or errors if you ask for the wrong type
+    pub fn get_date(&self) -> Result<CqlDate> { Value::build(self.0).get_date() }
+
+    /// Gets the time from this column or errors if you ask for the wrong type
+    pub fn get_time(&self) -> Result<CqlTime> { Value::build(self.0).get_time() }
+
+    /// Gets the timestamp from this column or errors if you ask for the wrong type
+    pub fn get_timestamp(&self) -> Result<CqlTimestamp> { Value::build(self.0).get_timestamp() }
+
+    /// Gets the duration from this column or errors if you ask for the wrong type
+    pub fn get_duration(&self) -> Result<CqlDuration> { Value::build(self.0).get_duration() }
 
     /// Gets the string from this column or errors if you ask for the wrong type
-    #[allow(cast_possible_truncation)]
-    pub fn get_string(&self) -> Result<String> {
-        unsafe {
-            match cass_value_type(self.0) {
-                CASS_VALUE_TYPE_ASCII |
-                CASS_VALUE_TYPE_TEXT |
-                CASS_VALUE_TYPE_VARCHAR => {
-                    let mut message = mem::zeroed();
-                    let mut message_length = mem::zeroed();
-                    match cass_value_get_string(self.0, &mut message, &mut message_length) {
-                        CASS_OK => {
-                            let slice = slice::from_raw_parts(message as *const u8, message_length as usize);
-                            Ok(str::from_utf8(slice).expect("must be utf8").to_owned())
-                        }
-                        err => Err(err.to_result("").unwrap().into()),
-                    }
+    pub fn get_string(&self) -> Result<String> { self.get_str().map(str::to_owned) }
 
+    /// Borrows the string from this column without copying it, or errors if you ask for the
+    /// wrong type or the text is not valid UTF-8
+    pub fn get_str(&self) -> Result<&'a str> { Ok(str::from_utf8(self.get_string_bytes()?)?) }
 
-                }
-                other => panic!("Unsupported type: {:?}", other), //FIXME
-            }
+    /// Gets the string from this column, replacing any invalid UTF-8 sequences with U+FFFD,
+    /// or errors if you ask for the wrong type
+    pub fn get_string_lossy(&self) -> Result<Cow<'a, str>> { Ok(String::from_utf8_lossy(self.get_string_bytes()?)) }
+
+    /// Borrows the raw bytes of the string in this column, which may not be valid UTF-8, or
+    /// errors if you ask for the wrong type
+    pub fn get_string_bytes(&self) -> Result<&'a [u8]> {
+        match self.get_type().inner() {
+            CASS_VALUE_TYPE_ASCII |
+            CASS_VALUE_TYPE_TEXT |
+            CASS_VALUE_TYPE_VARCHAR => Value::build(self.0).get_string_bytes(),
+            _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
         }
     }
 
-    /// Gets the blog from this column or errors if type if wrong
-    pub fn get_blob(&self) -> Result<Vec<u8>> {
-        unsafe {
-            match cass_value_type(self.0) {
-                CASS_VALUE_TYPE_BLOB => {
-                    let mut message = mem::zeroed();
-                    let mut message_length = mem::zeroed();
-                    match cass_value_get_string(self.0, &mut message, &mut message_length) {
-                        CASS_OK => Ok(Vec::from(slice::from_raw_parts(message as *const u8, message_length as usize))),
-                        err => Err(err.to_result("").unwrap().into()),
-                    }
-                }
-                _ => panic!("Value type is not BLOB"),
-            }
+    /// Gets the blob from this column or errors if you ask for the wrong type
+    pub fn get_blob(&self) -> Result<Vec<u8>> { self.get_bytes().map(Vec::from) }
+
+    /// Borrows the blob from this column without copying it, or errors if you ask for the
+    /// wrong type
+    pub fn get_bytes(&self) -> Result<&'a [u8]> {
+        match self.get_type().inner() {
+            CASS_VALUE_TYPE_BLOB => Value::build(self.0).get_bytes(),
+            _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
         }
     }
 
@@ -298,7 +308,7 @@ impl Column {
     pub fn get_i32(&self) -> Result<i32> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_int32(self.0, &mut output).to_result(output).chain_err(|| "")
+            cass_value_get_int32(self.0, &mut output).wrap(output)
         }
     }
 
@@ -306,7 +316,7 @@ impl Column {
     pub fn get_i64(&self) -> Result<i64> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_int64(self.0, &mut output).to_result(output).chain_err(|| "")
+            cass_value_get_int64(self.0, &mut output).wrap(output)
         }
     }
 
@@ -314,7 +324,7 @@ impl Column {
     pub fn get_float(&self) -> Result<f32> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_float(self.0, &mut output).to_result(output).chain_err(|| "")
+            cass_value_get_float(self.0, &mut output).wrap(output)
         }
     }
 
@@ -322,7 +332,7 @@ impl Column {
     pub fn get_double(&self) -> Result<f64> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_double(self.0, &mut output).to_result(output).chain_err(|| "")
+            cass_value_get_double(self.0, &mut output).wrap(output)
         }
     }
 
@@ -330,9 +340,7 @@ impl Column {
     pub fn get_bool(&self) -> Result<bool> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_bool(self.0, &mut output)
-                .to_result(if output == cass_true { true } else { false })
-                .chain_err(|| "")
+            cass_value_get_bool(self.0, &mut output).wrap(output == cass_true)
         }
     }
 
@@ -340,37 +348,40 @@ impl Column {
     pub fn get_uuid(&self) -> Result<Uuid> {
         unsafe {
             let mut output = mem::zeroed();
-            cass_value_get_uuid(self.0, &mut output).to_result(Uuid::build(output)).chain_err(|| "")
+            cass_value_get_uuid(self.0, &mut output).wrap(Uuid::build(output))
         }
     }
 
     /// Gets an iterator over the map in this column or errors if you ask for the wrong type
-    pub fn map_iter(&self) -> Result<MapIterator> {
+    pub fn map_iter(&self) -> Result<MapIterator<'a>> {
         unsafe {
             match self.get_type().inner() {
                 CASS_VALUE_TYPE_MAP => Ok(MapIterator::build(cass_iterator_from_map(self.0))),
-                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.to_result("").unwrap().into()),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
     /// Gets an iterator over the set in this column or errors if you ask for the wrong type
-    pub fn set_iter(&self) -> Result<SetIterator> {
+    pub fn set_iter(&self) -> Result<SetIterator<'a>> {
         unsafe {
             match self.get_type().inner() {
                 CASS_VALUE_TYPE_SET => Ok(SetIterator::build(cass_iterator_from_collection(self.0))),
-                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.to_result("").unwrap().into()),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
     /// Gets an iterator over the fields of the user type in this column or errors if you ask for the wrong type
-    pub fn use_type_iter(&self) -> Result<UserTypeIterator> {
-        unsafe {
-            match self.get_type().inner() {
-                CASS_VALUE_TYPE_UDT => Ok(UserTypeIterator::build(cass_iterator_fields_from_user_type(self.0))),
-                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.to_result("").unwrap().into()),
-            }
-        }
-    }
+    pub fn use_type_iter(&self) -> Result<UserTypeFieldIterator<'a>> { self.get_user_type() }
+
+    /// Gets an iterator over the list in this column or errors if you ask for the wrong type
+    pub fn get_list(&self) -> Result<SetIterator<'a>> { Value::build(self.0).get_list() }
+
+    /// Gets an iterator over the tuple in this column or errors if you ask for the wrong type
+    pub fn get_tuple(&self) -> Result<TupleIterator<'a>> { Value::build(self.0).get_tuple() }
+
+    /// Gets an iterator over the named fields of the user type in this column or errors if
+    /// you ask for the wrong type
+    pub fn get_user_type(&self) -> Result<UserTypeFieldIterator<'a>> { Value::build(self.0).get_user_type() }
 }
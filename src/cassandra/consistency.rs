@@ -8,7 +8,7 @@ use std::ffi::CStr;
 
 
 /// A Cassandra consistency level
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Consistency(_CassConsistency);
 
 impl ToString for Consistency {
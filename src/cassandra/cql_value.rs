@@ -0,0 +1,259 @@
+use cassandra::error::*;
+use cassandra::inet::Inet;
+use cassandra::iterator::LendingIterator;
+use cassandra::numeric::decode_varint;
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
+use cassandra::util::Protected;
+use cassandra::uuid::Uuid;
+use cassandra::value::{FromValue, Value};
+use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
+use cassandra_sys::CASS_OK;
+use cassandra_sys::CASS_VALUE_TYPE_ASCII;
+use cassandra_sys::CASS_VALUE_TYPE_BIGINT;
+use cassandra_sys::CASS_VALUE_TYPE_BLOB;
+use cassandra_sys::CASS_VALUE_TYPE_BOOLEAN;
+use cassandra_sys::CASS_VALUE_TYPE_COUNTER;
+use cassandra_sys::CASS_VALUE_TYPE_CUSTOM;
+use cassandra_sys::CASS_VALUE_TYPE_DATE;
+use cassandra_sys::CASS_VALUE_TYPE_DECIMAL;
+use cassandra_sys::CASS_VALUE_TYPE_DOUBLE;
+use cassandra_sys::CASS_VALUE_TYPE_FLOAT;
+use cassandra_sys::CASS_VALUE_TYPE_INET;
+use cassandra_sys::CASS_VALUE_TYPE_INT;
+use cassandra_sys::CASS_VALUE_TYPE_LIST;
+use cassandra_sys::CASS_VALUE_TYPE_MAP;
+use cassandra_sys::CASS_VALUE_TYPE_SET;
+use cassandra_sys::CASS_VALUE_TYPE_SMALL_INT;
+use cassandra_sys::CASS_VALUE_TYPE_TEXT;
+use cassandra_sys::CASS_VALUE_TYPE_TIME;
+use cassandra_sys::CASS_VALUE_TYPE_TIMESTAMP;
+use cassandra_sys::CASS_VALUE_TYPE_TIMEUUID;
+use cassandra_sys::CASS_VALUE_TYPE_TINY_INT;
+use cassandra_sys::CASS_VALUE_TYPE_TUPLE;
+use cassandra_sys::CASS_VALUE_TYPE_UDT;
+use cassandra_sys::CASS_VALUE_TYPE_UUID;
+use cassandra_sys::CASS_VALUE_TYPE_VARCHAR;
+use cassandra_sys::CASS_VALUE_TYPE_VARINT;
+use cassandra_sys::cass_data_type_class_name;
+use num::BigInt;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ptr;
+use std::slice;
+
+/// The class of the "custom" type that carries a "duration"
+const DURATION_CLASS: &str = "org.apache.cassandra.db.marshal.DurationType";
+
+/// An owned copy of any Cassandra value, including nested collections, tuples and user types.
+///
+/// Unlike `Value`, which borrows from its result, a `CqlValue` can be kept after the result is
+/// freed. Every CQL type has its own variant, so no information is lost in the conversion:
+/// a "decimal" keeps all of its digits and a "timeuuid" stays distinct from a "uuid".
+#[derive(Debug, Clone, PartialEq)]
+pub enum CqlValue {
+    /// A null value, including an unset tuple item or user type field
+    Null,
+    /// An "ascii"
+    Ascii(String),
+    /// A "text" or "varchar"
+    Text(String),
+    /// A "blob"
+    Blob(Vec<u8>),
+    /// A "custom" value, as the raw bytes of its serialized form
+    Custom(Vec<u8>),
+    /// A "boolean"
+    Boolean(bool),
+    /// A "tinyint"
+    TinyInt(i8),
+    /// A "smallint"
+    SmallInt(i16),
+    /// An "int"
+    Int(i32),
+    /// A "bigint"
+    BigInt(i64),
+    /// A "counter"
+    Counter(i64),
+    /// A "varint"
+    Varint(BigInt),
+    /// A "float"
+    Float(f32),
+    /// A "double"
+    Double(f64),
+    /// A "decimal", worth `unscaled * 10^-scale`
+    Decimal {
+        /// The digits of the decimal
+        unscaled: BigInt,
+        /// The number of digits after the decimal point
+        scale: i32,
+    },
+    /// A "date"
+    Date(CqlDate),
+    /// A "time"
+    Time(CqlTime),
+    /// A "timestamp"
+    Timestamp(CqlTimestamp),
+    /// A "duration"
+    Duration(CqlDuration),
+    /// A "uuid"
+    Uuid(Uuid),
+    /// A "timeuuid"
+    Timeuuid(Uuid),
+    /// An "inet"
+    Inet(Inet),
+    /// A "list"
+    List(Vec<CqlValue>),
+    /// A "set"
+    Set(Vec<CqlValue>),
+    /// A "map", as its entries in the order they were read
+    Map(Vec<(CqlValue, CqlValue)>),
+    /// A "tuple"
+    Tuple(Vec<CqlValue>),
+    /// A user defined type, as its fields in declaration order
+    UserType(Vec<(String, CqlValue)>),
+}
+
+impl FromValue for CqlValue {
+    fn from_value(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            return Ok(CqlValue::Null);
+        }
+        Ok(match value.get_type().inner() {
+            CASS_VALUE_TYPE_ASCII => CqlValue::Ascii(value.get_string()?.to_owned()),
+            CASS_VALUE_TYPE_TEXT | CASS_VALUE_TYPE_VARCHAR => CqlValue::Text(value.get_string()?.to_owned()),
+            CASS_VALUE_TYPE_BLOB => CqlValue::Blob(value.get_bytes()?.to_vec()),
+            CASS_VALUE_TYPE_CUSTOM if is_duration(value) => CqlValue::Duration(value.get_duration()?),
+            CASS_VALUE_TYPE_CUSTOM => CqlValue::Custom(value.get_bytes()?.to_vec()),
+            CASS_VALUE_TYPE_BOOLEAN => CqlValue::Boolean(value.get_bool()?),
+            CASS_VALUE_TYPE_TINY_INT => CqlValue::TinyInt(value.get_i8()?),
+            CASS_VALUE_TYPE_SMALL_INT => CqlValue::SmallInt(value.get_i16()?),
+            CASS_VALUE_TYPE_INT => CqlValue::Int(value.get_i32()?),
+            CASS_VALUE_TYPE_BIGINT => CqlValue::BigInt(value.get_i64()?),
+            CASS_VALUE_TYPE_COUNTER => CqlValue::Counter(value.get_i64()?),
+            CASS_VALUE_TYPE_VARINT => CqlValue::Varint(value.get_varint()?),
+            CASS_VALUE_TYPE_FLOAT => CqlValue::Float(value.get_flt()?),
+            CASS_VALUE_TYPE_DOUBLE => CqlValue::Double(value.get_dbl()?),
+            CASS_VALUE_TYPE_DECIMAL => {
+                let (varint, scale) = value.get_decimal_parts()?;
+                CqlValue::Decimal {
+                    unscaled: decode_varint(varint),
+                    scale,
+                }
+            }
+            CASS_VALUE_TYPE_DATE => CqlValue::Date(value.get_date()?),
+            CASS_VALUE_TYPE_TIME => CqlValue::Time(value.get_time()?),
+            CASS_VALUE_TYPE_TIMESTAMP => CqlValue::Timestamp(value.get_timestamp()?),
+            CASS_VALUE_TYPE_UUID => CqlValue::Uuid(value.get_uuid()?),
+            CASS_VALUE_TYPE_TIMEUUID => CqlValue::Timeuuid(value.get_uuid()?),
+            CASS_VALUE_TYPE_INET => CqlValue::Inet(value.get_inet()?),
+            CASS_VALUE_TYPE_LIST => {
+                CqlValue::List(value.get_list()?.map(|item| CqlValue::from_value(&item)).collect::<Result<_>>()?)
+            }
+            CASS_VALUE_TYPE_SET => {
+                CqlValue::Set(value.get_set()?.map(|item| CqlValue::from_value(&item)).collect::<Result<_>>()?)
+            }
+            CASS_VALUE_TYPE_MAP => {
+                CqlValue::Map(value.get_map()?
+                    .map(|(key, value)| Ok((CqlValue::from_value(&key)?, CqlValue::from_value(&value)?)))
+                    .collect::<Result<_>>()?)
+            }
+            CASS_VALUE_TYPE_TUPLE => {
+                CqlValue::Tuple(value.get_tuple()?.map(|item| CqlValue::from_value(&item)).collect::<Result<_>>()?)
+            }
+            CASS_VALUE_TYPE_UDT => {
+                CqlValue::UserType(value.get_user_type()?
+                    .map(|field| Ok((field.name, CqlValue::from_value(&field.value)?)))
+                    .collect::<Result<_>>()?)
+            }
+            other => {
+                return Err(CassError::with_message(CASS_ERROR_LIB_INVALID_VALUE_TYPE,
+                                                   format!("cannot read a value of type {:?}", other))
+                    .into())
+            }
+        })
+    }
+}
+
+/// The driver reads a "duration" as a "custom" value of the duration class
+fn is_duration(value: &Value) -> bool {
+    unsafe {
+        let mut class_name = ptr::null();
+        let mut class_name_length = 0;
+        match cass_data_type_class_name(value.data_type().0, &mut class_name, &mut class_name_length) {
+            CASS_OK => slice::from_raw_parts(class_name as *const u8, class_name_length) == DURATION_CLASS.as_bytes(),
+            _ => false,
+        }
+    }
+}
+
+/// Writes the value as a CQL literal, e.g. `{'a': [1, 2]}`
+impl Display for CqlValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            CqlValue::Null => write!(f, "null"),
+            CqlValue::Ascii(ref text) |
+            CqlValue::Text(ref text) => write!(f, "'{}'", text.replace('\'', "''")),
+            CqlValue::Blob(ref bytes) |
+            CqlValue::Custom(ref bytes) => {
+                write!(f, "0x")?;
+                for byte in bytes {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+            CqlValue::Boolean(value) => write!(f, "{}", value),
+            CqlValue::TinyInt(value) => write!(f, "{}", value),
+            CqlValue::SmallInt(value) => write!(f, "{}", value),
+            CqlValue::Int(value) => write!(f, "{}", value),
+            CqlValue::BigInt(value) |
+            CqlValue::Counter(value) => write!(f, "{}", value),
+            CqlValue::Varint(ref value) => write!(f, "{}", value),
+            CqlValue::Float(value) => write!(f, "{}", value),
+            CqlValue::Double(value) => write!(f, "{}", value),
+            CqlValue::Decimal { ref unscaled, scale } => write!(f, "{}E{}", unscaled, -(scale as i64)),
+            CqlValue::Date(value) => write!(f, "'{}'", value),
+            CqlValue::Time(value) => write!(f, "'{}'", value),
+            CqlValue::Timestamp(value) => write!(f, "'{}'", value),
+            CqlValue::Duration(value) => write!(f, "{}", value),
+            CqlValue::Uuid(value) |
+            CqlValue::Timeuuid(value) => write!(f, "{}", value),
+            CqlValue::Inet(value) => write!(f, "'{}'", value),
+            CqlValue::List(ref items) => write_items(f, "[", items.iter(), "]"),
+            CqlValue::Set(ref items) => write_items(f, "{", items.iter(), "}"),
+            CqlValue::Tuple(ref items) => write_items(f, "(", items.iter(), ")"),
+            CqlValue::Map(ref entries) => {
+                write_items(f, "{", entries.iter().map(|(key, value)| format!("{}: {}", key, value)), "}")
+            }
+            CqlValue::UserType(ref fields) => {
+                write_items(f, "{", fields.iter().map(|(name, value)| format!("{}: {}", name, value)), "}")
+            }
+        }
+    }
+}
+
+fn write_items<I>(f: &mut Formatter, open: &str, items: I, close: &str) -> fmt::Result
+    where I: Iterator,
+          I::Item: Display {
+    write!(f, "{}", open)?;
+    for (index, item) in items.enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, "{}", close)
+}
+
+#[test]
+fn test_display_as_cql_literal() {
+    let value = CqlValue::Map(vec![(CqlValue::Text("it's".to_owned()),
+                                    CqlValue::List(vec![CqlValue::Int(1), CqlValue::Null]))]);
+    assert_eq!(value.to_string(), "{'it''s': [1, null]}");
+    let value = CqlValue::UserType(vec![("blob".to_owned(), CqlValue::Blob(vec![0x0a, 0xff])),
+                                        ("decimal".to_owned(),
+                                         CqlValue::Decimal {
+                                             unscaled: BigInt::from(-1250),
+                                             scale: 2,
+                                         })]);
+    assert_eq!(value.to_string(), "{blob: 0x0aff, decimal: -1250E-2}");
+    assert_eq!(CqlValue::Tuple(vec![CqlValue::Boolean(true)]).to_string(), "(true)");
+}
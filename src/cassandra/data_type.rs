@@ -3,15 +3,15 @@
 use cassandra::error::CassError;
 
 use cassandra::user_type::UserType;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected, to_owned_string};
 use cassandra::value::ValueType;
 
 use cassandra_sys::CassDataType as _CassDataType;
 use cassandra_sys::cass_data_sub_type_count;
 use cassandra_sys::cass_data_type_add_sub_type;
-use cassandra_sys::cass_data_type_add_sub_type_by_name;
+use cassandra_sys::cass_data_type_add_sub_type_by_name_n;
 use cassandra_sys::cass_data_type_add_sub_value_type;
-use cassandra_sys::cass_data_type_add_sub_value_type_by_name;
+use cassandra_sys::cass_data_type_add_sub_value_type_by_name_n;
 use cassandra_sys::cass_data_type_class_name;
 use cassandra_sys::cass_data_type_free;
 use cassandra_sys::cass_data_type_keyspace;
@@ -19,18 +19,19 @@ use cassandra_sys::cass_data_type_new;
 use cassandra_sys::cass_data_type_new_from_existing;
 use cassandra_sys::cass_data_type_new_tuple;
 use cassandra_sys::cass_data_type_new_udt;
-use cassandra_sys::cass_data_type_set_class_name;
-use cassandra_sys::cass_data_type_set_keyspace;
-use cassandra_sys::cass_data_type_set_type_name;
+use cassandra_sys::cass_data_type_set_class_name_n;
+use cassandra_sys::cass_data_type_set_keyspace_n;
+use cassandra_sys::cass_data_type_set_type_name_n;
 use cassandra_sys::cass_data_type_sub_data_type;
-use cassandra_sys::cass_data_type_sub_data_type_by_name;
+use cassandra_sys::cass_data_type_sub_data_type_by_name_n;
 use cassandra_sys::cass_data_type_sub_type_name;
 use cassandra_sys::cass_data_type_type;
 use cassandra_sys::cass_data_type_type_name;
 use cassandra_sys::cass_user_type_new_from_data_type;
-use errors::*;
+use cassandra::error::*;
 
-use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
 
 
 /// Any cassandra datatype
@@ -72,14 +73,12 @@ impl DataType {
     pub fn get_type(data_type: DataType) -> ValueType { unsafe { ValueType::build(cass_data_type_type(data_type.0)) } }
 
     /// Gets the type name of a UDT data type.
-    pub fn type_name<S>(data_type: DataType, type_name: S) -> Result<()>
-        where S: Into<String> {
+    pub fn type_name(&self) -> Result<String> {
         unsafe {
-            let type_name2 = CString::new(type_name.into()).expect("must be valid utf8");
-            let err = cass_data_type_type_name(data_type.0,
-                                               &mut type_name2.as_ptr(),
-                                               &mut (type_name2.as_bytes().len()));
-            err.to_result(()).chain_err(|| "")
+            let mut name = ptr::null();
+            let mut name_length = 0;
+            cass_data_type_type_name(self.0, &mut name, &mut name_length).wrap(())?;
+            to_owned_string(name, name_length)
         }
     }
 
@@ -88,28 +87,22 @@ impl DataType {
     /// <b>Note:</b> Only valid for UDT data types.
     pub fn set_type_name<S>(data_type: DataType, type_name: S) -> Result<()>
         where S: Into<String> {
+        let type_name = type_name.into();
         unsafe {
-            cass_data_type_set_type_name(data_type.0,
-                                         CString::new(type_name.into())
-                                             .expect("must be utf8")
-                                             .as_ptr())
-                .to_result(())
-                .chain_err(|| "")
+            cass_data_type_set_type_name_n(data_type.0, type_name.as_ptr() as *const c_char, type_name.len())
+                .wrap(())
         }
     }
 
-    /// Gets the type name of a UDT data type.
+    /// Gets the keyspace of a UDT data type.
     ///
     /// <b>Note:</b> Only valid for UDT data types.
-    pub fn keyspace<S>(data_type: DataType, keyspace: S) -> Result<()>
-        where S: Into<String> {
+    pub fn keyspace(&self) -> Result<String> {
         unsafe {
-            let keyspace2 = CString::new(keyspace.into()).expect("must be utf8");
-            cass_data_type_keyspace(data_type.0,
-                                    &mut (keyspace2.as_ptr()),
-                                    &mut (keyspace2.as_bytes().len()))
-                .to_result(())
-                .chain_err(|| "")
+            let mut keyspace = ptr::null();
+            let mut keyspace_length = 0;
+            cass_data_type_keyspace(self.0, &mut keyspace, &mut keyspace_length).wrap(())?;
+            to_owned_string(keyspace, keyspace_length)
         }
     }
 
@@ -118,26 +111,22 @@ impl DataType {
     /// <b>Note:</b> Only valid for UDT data types.
     pub fn set_keyspace<S>(data_type: DataType, keyspace: S) -> Result<()>
         where S: Into<String> {
+        let keyspace = keyspace.into();
         unsafe {
-            cass_data_type_set_keyspace(data_type.0,
-                                        CString::new(keyspace.into()).expect("must be utf8").as_ptr())
-                .to_result(())
-                .chain_err(|| "")
+            cass_data_type_set_keyspace_n(data_type.0, keyspace.as_ptr() as *const c_char, keyspace.len())
+                .wrap(())
         }
     }
 
     /// Gets the class name of a custom data type.
     ///
     /// <b>Note:</b> Only valid for custom data types.
-    pub fn class_name<S>(data_type: DataType, class_name: S) -> Result<()>
-        where S: Into<String> {
+    pub fn class_name(&self) -> Result<String> {
         unsafe {
-            let class_name2 = CString::new(class_name.into()).expect("must be valid utf8");
-            cass_data_type_class_name(data_type.0,
-                                      &mut class_name2.as_ptr(),
-                                      &mut (class_name2.as_bytes().len()))
-                .to_result(())
-                .chain_err(|| "")
+            let mut class_name = ptr::null();
+            let mut class_name_length = 0;
+            cass_data_type_class_name(self.0, &mut class_name, &mut class_name_length).wrap(())?;
+            to_owned_string(class_name, class_name_length)
         }
     }
 
@@ -146,13 +135,10 @@ impl DataType {
     /// <b>Note:</b> Only valid for custom data types.
     pub fn set_class_name<S>(&self, class_name: S) -> Result<()>
         where S: Into<String> {
+        let class_name = class_name.into();
         unsafe {
-            cass_data_type_set_class_name(self.0,
-                                          CString::new(class_name.into())
-                                              .expect("must be valid utf8")
-                                              .as_ptr())
-                .to_result(())
-                .chain_err(|| "")
+            cass_data_type_set_class_name_n(self.0, class_name.as_ptr() as *const c_char, class_name.len())
+                .wrap(())
         }
     }
 
@@ -175,28 +161,23 @@ impl DataType {
     /// <b>Note:</b> Only valid for UDT data types.
     pub fn sub_data_type_by_name<S>(data_type: DataType, name: S) -> ConstDataType
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            ConstDataType(cass_data_type_sub_data_type_by_name(data_type.0,
-                                                               CString::new(name.into())
-                                                                   .expect("must be utf8")
-                                                                   .as_ptr()))
+            ConstDataType(cass_data_type_sub_data_type_by_name_n(data_type.0,
+                                                                 name.as_ptr() as *const c_char,
+                                                                 name.len()))
         }
     }
 
     /// Gets the sub-type name of a UDT (user defined type) at the specified index.
     ///
     /// <b>Note:</b> Only valid for UDT data types.
-    pub fn sub_type_name<S>(data_type: DataType, index: usize, name: S) -> Result<()>
-        where S: Into<String> {
+    pub fn sub_type_name(&self, index: usize) -> Result<String> {
         unsafe {
-            let name2 = CString::new(name.into()).expect("must be utf8");
-            cass_data_type_sub_type_name(data_type.0,
-                                         index,
-                                         &mut name2.as_ptr(),
-                                         &mut (name2.as_bytes().len()))
-                .to_result(())
-                .chain_err(|| "")
-
+            let mut name = ptr::null();
+            let mut name_length = 0;
+            cass_data_type_sub_type_name(self.0, index, &mut name, &mut name_length).wrap(())?;
+            to_owned_string(name, name_length)
         }
     }
 
@@ -206,8 +187,7 @@ impl DataType {
     pub fn add_sub_type(&self, sub_data_type: DataType) -> Result<()> {
         unsafe {
             cass_data_type_add_sub_type(self.0, sub_data_type.0)
-                .to_result(())
-                .chain_err(|| "")
+                .wrap(())
         }
     }
 
@@ -216,14 +196,10 @@ impl DataType {
     /// <b>Note:</b> Only valid for UDT data types.
     pub fn add_sub_type_by_name<S>(&mut self, name: S, sub_data_type: DataType) -> Result<()>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_data_type_add_sub_type_by_name(self.0,
-                                                CString::new(name.into())
-                                                    .expect("must be utf8")
-                                                    .as_ptr(),
-                                                sub_data_type.0)
-                .to_result(())
-                .chain_err(|| "")
+            cass_data_type_add_sub_type_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), sub_data_type.0)
+                .wrap(())
         }
     }
 
@@ -234,8 +210,7 @@ impl DataType {
         where S: Into<String> {
         unsafe {
             cass_data_type_add_sub_value_type(self.0, sub_value_type.inner())
-                .to_result(())
-                .chain_err(|| "")
+                .wrap(())
         }
     }
 
@@ -245,13 +220,11 @@ impl DataType {
     pub fn add_sub_value_type_by_name<S>(&self, name: &str, typ: ValueType) -> Result<()>
         where S: Into<String> {
         unsafe {
-            cass_data_type_add_sub_value_type_by_name(self.0,
-                                                      CString::new(name)
-                                                          .expect("must be utf8")
-                                                          .as_ptr(),
-                                                      typ.inner())
-                .to_result(())
-                .chain_err(|| "")
+            cass_data_type_add_sub_value_type_by_name_n(self.0,
+                                                        name.as_ptr() as *const c_char,
+                                                        name.len(),
+                                                        typ.inner())
+                .wrap(())
         }
 
     }
@@ -1,10 +1,10 @@
-#![macro_use]
-// use cassandra_sys;
-
 use cassandra::consistency::Consistency;
-use cassandra::util::Protected;
+use cassandra::util::{Protected, to_owned_string};
 use cassandra::write_type::WriteType;
 
+use cassandra_sys::{CASS_ERROR_SERVER_ALREADY_EXISTS, CASS_ERROR_SERVER_FUNCTION_FAILURE,
+                    CASS_ERROR_SERVER_READ_FAILURE, CASS_ERROR_SERVER_READ_TIMEOUT, CASS_ERROR_SERVER_UNAVAILABLE,
+                    CASS_ERROR_SERVER_WRITE_FAILURE, CASS_ERROR_SERVER_WRITE_TIMEOUT};
 use cassandra_sys::CASS_OK;
 use cassandra_sys::CassError as _CassError;
 
@@ -23,140 +23,142 @@ use cassandra_sys::cass_error_result_responses_received;
 use cassandra_sys::cass_error_result_responses_required;
 use cassandra_sys::cass_error_result_table;
 use cassandra_sys::cass_error_result_write_type;
-use errors::*;
-use errors::*;
-use std::{fmt, mem, slice, str};
+use cassandra_sys::cass_true;
+use std::{fmt, ptr, result, str};
 use std::error::Error as IError;
-// use std::error::Error;
-use std::ffi::{CStr, CString};
-use std::fmt::{Debug, Display, Formatter};
-use std::os::raw::c_char;
-
-// Simple and robust error handling with error-chain!
-// Use this as a template for new projects.
-
-// #[macro_export]
-// macro_rules! err_wrap {
-//    ( $s:expr,$x:expr ) => {match $x {
-//                CASS_OK => Ok($s),
-//                err => Err(Error::from(err.into()))
-//            }
-//    }
-// }
-
-error_chain! {
-       // Automatic conversions between this error chain and other
-    // error types not defined by the `error_chain!`. These will be
-    // wrapped in a new error with, in this case, the
-    // `ErrorKind::Temp` variant. The description and cause will
-    // forward to the description and cause of the original error.
-    //
-    // Optionally, some attributes can be added to a variant.
-    //
-    // This section can be empty.
-    foreign_links {
-        Fmt(::std::fmt::Error);
-        Io(::std::io::Error) #[cfg(unix)];
-        Null(::std::ffi::NulError);
-        Cass(_CassError);
-        Utf8(::std::str::Utf8Error);
-    }
+use std::ffi::{CStr, NulError};
+use std::fmt::{Display, Formatter};
+use std::net::AddrParseError;
+use std::str::Utf8Error;
+
+/// The result of a driver operation
+pub type Result<T> = result::Result<T, Error>;
 
-    // Define additional `ErrorKind` variants. The syntax here is
-    // the same as `quick_error!`, but the `from()` and `cause()`
-    // syntax is not supported.
-    errors {
-        CassError(t: String) {
-            //description("invalid toolchain name")
-            //display("invalid toolchain name: '{}'", t)
+/// An error returned by the driver, split by where it originated
+#[derive(Debug)]
+pub enum Error {
+    /// An error generated by the C++ driver
+    Lib(CassError),
+    /// An error signaled by the server and sent to the client over CQL transport,
+    /// with the details from the server's error response
+    Server(CassError, ServerError),
+    /// An error signaled by the client-linked SSL library
+    Ssl(CassError),
+    /// Tried to pass a string with a nul in the middle to the driver
+    NulInString(NulError),
+    /// The driver returned text that was not valid utf8
+    InvalidUtf8(Utf8Error),
+    /// Tried to parse an invalid ip address
+    BadAddress(AddrParseError),
+}
+
+impl Error {
+    /// Gets the driver error code, if the error came from the driver
+    pub fn code(&self) -> Option<_CassError> { self.cass_error().map(CassError::code) }
+
+    fn cass_error(&self) -> Option<&CassError> {
+        match *self {
+            Error::Lib(ref err) |
+            Error::Server(ref err, _) |
+            Error::Ssl(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Error::Lib(ref err) => write!(f, "driver error: {}", err),
+            Error::Server(ref err, ref details) => write!(f, "server error: {} ({})", err, details),
+            Error::Ssl(ref err) => write!(f, "ssl error: {}", err),
+            Error::NulInString(ref err) => write!(f, "string contains a nul: {}", err),
+            Error::InvalidUtf8(ref err) => write!(f, "invalid utf8: {}", err),
+            Error::BadAddress(ref err) => write!(f, "bad address: {}", err),
+        }
+    }
+}
 
+impl IError for Error {
+    fn source(&self) -> Option<&(dyn IError + 'static)> {
+        match *self {
+            Error::Lib(ref err) |
+            Error::Server(ref err, _) |
+            Error::Ssl(ref err) => Some(err),
+            Error::NulInString(ref err) => Some(err),
+            Error::InvalidUtf8(ref err) => Some(err),
+            Error::BadAddress(ref err) => Some(err),
+        }
+    }
+}
 
-/// An error returned by Cassandra or the driver
-pub struct CassError(_CassError);
+impl From<CassError> for Error {
+    fn from(err: CassError) -> Error {
+        match err.code as u32 >> 24 {
+            2 => Error::Server(err.clone(), ServerError::Other(err.code)),
+            3 => Error::Ssl(err),
+            _ => Error::Lib(err),
+        }
+    }
+}
 
-// ///An error generated by the C++ driver
-// pub struct CassLibError {
-//    err: _CassError,
-//    msg: String,
-// }
+impl From<_CassError> for Error {
+    fn from(code: _CassError) -> Error { CassError::new(code).into() }
+}
 
-// impl From<_CassError> for CassError {
-//    fn from(err:_CassError) -> CassError {
-//        CassError
-//    }
-// }
+impl From<NulError> for Error {
+    fn from(err: NulError) -> Error { Error::NulInString(err) }
+}
 
-// ///An error signaled by the server and sent to the client over CQL transport
-// pub struct CassServerError(_CassError);
-// ///An error signaled by the client-linked SSL library
-// pub struct CassSslError(_CassError);
+impl From<Utf8Error> for Error {
+    fn from(err: Utf8Error) -> Error { Error::InvalidUtf8(err) }
+}
 
-impl ::std::error::Error for CassError {
-    fn description(&self) -> &str {
-        unimplemented!()
-        // self.desc()
-        // let c_buf: *const i8 = self.desc();
-        // let buf: &[u8] = unsafe { CStr::from_ptr(c_buf).to_bytes() };
-        // from_utf8(buf).unwrap()
-    }
+impl From<AddrParseError> for Error {
+    fn from(err: AddrParseError) -> Error { Error::BadAddress(err) }
 }
 
-// impl From<AddrParseError> for CassError {
-//    fn from(err: AddrParseError) -> CassError {
-//        CassError::Rust(CassRustError::BadAddress(err))
-//    }
-// }
+/// An error code reported by the C++ driver, along with its message
+#[derive(Debug, Clone)]
+pub struct CassError {
+    code: _CassError,
+    message: String,
+}
 
-// impl From<NulError> for CassError {
-//    fn from(err: NulError) -> CassError {
-//        CassError::Rust(CassRustError::NulInString(err))
-//    }
-// }
+impl CassError {
+    /// Wraps an error code, using the driver's description of it as the message
+    pub fn new(code: _CassError) -> Self { CassError::with_message(code, desc(code)) }
 
-impl Display for CassError {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.description())
-        //        let c_buf: *const i8 = self.desc();
-        //        let buf: &[u8] = unsafe { CStr::from_ptr(c_buf).to_bytes() };
-        //        match str::from_utf8(buf) {
-        //            Ok(str_slice) => write!(f, "{}", str_slice),
-        //            Err(err) => panic!("unreachable? {:?}", err),
-        //        }
+    /// Wraps an error code with a more specific message, such as the one reported by a future
+    pub fn with_message<S: Into<String>>(code: _CassError, message: S) -> Self {
+        CassError {
+            code,
+            message: message.into(),
+        }
     }
+
+    /// Gets the error code
+    pub fn code(&self) -> _CassError { self.code }
+
+    /// Gets the message reported with this error
+    pub fn message(&self) -> &str { &self.message }
+
+    /// Gets the textual description of the error code
+    pub fn desc(&self) -> &'static str { desc(self.code) }
 }
-//
-impl Debug for CassError {
+
+impl Display for CassError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.description())
-        //        let c_buf: *const i8 = self.desc();
-        //        let buf: &[u8] = unsafe { CStr::from_ptr(c_buf).to_bytes() };
-        //        match str::from_utf8(buf) {
-        //            Ok(str_slice) => write!(f, "{:?}", str_slice),
-        //            Err(err) => panic!("unreachable? {:?}", err),
-        //        }
+        if self.message == self.desc() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.desc(), self.message)
+        }
     }
 }
 
-// impl From<NulError> for CassError {
-//    fn from(err: NulError) -> CassError {
-//        CliError::Io(err)
-//    }
-// }
-
-// impl CassError {
-//   /// Takes an upstream error and wraps it into the appropriate CassError
-//    pub fn wrap<T>(self, wrappee: T) -> Result<T> {
-//        match self.0 {
-//            CASS_OK => Ok(wrappee),
-//            err => Err(Error::from(err.into())),
-//        }
-//    }
-//    //
-// }
+impl IError for CassError {}
+
 /// An error result of a request
 #[derive(Debug)]
 pub struct CassErrorResult(*const _CassErrorResult);
@@ -166,182 +168,342 @@ impl Protected<*const _CassErrorResult> for CassErrorResult {
     fn build(inner: *const _CassErrorResult) -> Self { CassErrorResult(inner) }
 }
 
-// impl CassErrorResult {
-//    /// Gets error code for the error result. This error code will always
-//    /// have an server error source.
-//    pub fn result_code(&self) -> u32 { unsafe { cass_error_result_code(self.0) as u32 } }
-//
-//    /// Gets consistency that triggered the error result of the
-//    /// following types:
-//    ///
-//    /// <ul>
-//    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
-//    ///  <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
-//    ///  <li>CASS_ERROR_SERVER_READ_FAILURE</li>
-//    ///  <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
-//    ///  <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
-//    /// </ul>
-//    pub fn result_consistency(&self) -> Consistency {
-//        unsafe { Consistency::build(cass_error_result_consistency(self.0)) }
-//    }
-//
-//    /// Gets the actual number of received responses, received acknowledgments
-//    /// or alive nodes for following error result types, respectively:
-//    ///
-//    /// <ul>
-//    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
-//    ///   <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
-//    /// </ul>
-//    pub fn responses_received(&self) -> i32 { unsafe { cass_error_result_responses_received(self.0) } }
-//
-//    /// Gets required responses, required acknowledgments or required alive nodes
-//    /// needed to successfully complete the request for following error result types,
-//    /// respectively:
-//    ///
-//    /// <ul>
-//    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
-//    ///   <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
-//    /// </ul>
-//    pub fn responses_required(&self) -> i32 { unsafe { cass_error_result_responses_required(self.0) } }
-//
-//    /// Gets the number of nodes that experienced failures for the following error types:
-//    ///
-//    /// <ul>
-//    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
-//    /// </ul>
-//    pub fn num_failures(&self) -> i32 { unsafe { cass_error_result_num_failures(self.0) } }
-//
-//    /// Determines whether the actual data was present in the responses from the
-//    /// replicas for the following error result types:
-//    ///
-//    /// <ul>
-//    ///   <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
-//    /// </ul>
-//    pub fn data_present(&self) -> bool { unsafe { cass_error_result_data_present(self.0) as i32 > 0 } }
-//
-//
-//    /// Gets the write type of a request for the following error result types:
-//    ///
-//    /// <ul>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
-//    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
-//    /// </ul>
-//    pub fn write_type(&self) -> WriteType { unsafe { WriteType(cass_error_result_write_type(self.0)) } }
-//
-//    /// Gets the affected keyspace for the following error result types:
-//    ///
-//    /// <ul>
-//    ///   <li>CASS_ERROR_SERVER_ALREADY_EXISTS</li>
-//    ///   <li>CASS_ERROR_SERVER_FUNCTION_FAILURE</li>
-//    /// </ul>
-//    #[allow(cast_possible_truncation)]
-//    pub fn keyspace(&self) -> String {
-//        unsafe {
-//            let mut name = mem::zeroed();
-//            let mut length = mem::zeroed();
-//            match cass_error_result_keyspace(self.0, &mut name, &mut length) {
-//                CASS_OK => {
-//                    let slice = slice::from_raw_parts(name as *const u8, length as usize);
-//                    str::from_utf8(slice).expect("must be utf8").to_owned()
-//                }
-//                err => panic!("impossible: {:?}", err),
-//            }
-//        }
-//    }
-//
-//    /// Gets the affected table for the already exists error
-//    /// (CASS_ERROR_SERVER_ALREADY_EXISTS) result type.
-//    #[allow(cast_possible_truncation)]
-//    pub fn table(&self) -> String {
-//        unsafe {
-//            let mut name = mem::zeroed();
-//            let mut length = mem::zeroed();
-//            match cass_error_result_table(self.0, &mut name, &mut length) {
-//                CASS_OK => {
-//                    let slice = slice::from_raw_parts(name as *const u8, length as usize);
-//                    str::from_utf8(slice).expect("must be utf8").to_owned()
-//                }
-//                err => panic!("impossible: {:?}", err),
-//            }
-//        }
-//    }
-//
-//    /// Gets the affected function for the function failure error
-//    /// (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
-//    #[allow(cast_possible_truncation)]
-//    pub fn function(&self) -> String {
-//        unsafe {
-//            let mut name = mem::zeroed();
-//            let mut length = mem::zeroed();
-//            match cass_error_result_function(self.0, &mut name, &mut length) {
-//                CASS_OK => {
-//                    let slice = slice::from_raw_parts(name as *const u8, length as usize);
-//                    str::from_utf8(slice).expect("must be utf8").to_owned()
-//                }
-//                err => panic!("impossible: {:?}", err),
-//            }
-//        }
-//    }
-//
-//    /// Gets the number of argument types for the function failure error
-//    /// (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
-//    pub fn num_arg_types(error_result: CassErrorResult) -> usize { unsafe { cass_error_num_arg_types(error_result.0) } }
-//
-//    /// Gets the argument type at the specified index for the function failure
-//    /// error (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
-//    pub fn arg_type(&self, index: usize, arg_type: &str) -> u32 {
-//        unsafe {
-//            let cstr = CString::new(arg_type).expect("must be utf8");
-//            cass_error_result_arg_type(self.0,
-//                                       index,
-//                                       &mut cstr.as_ptr(),
-//                                       &mut (cstr.to_bytes().len())) as u32
-//        }
-//    }
-// }
-// impl Drop for CassErrorResult {
-//    fn drop(&mut self) { unsafe { cass_error_result_free(self.0) } }
-// }
-//
-// impl CassError {
-//    pub fn new(err:_CassError) -> Self {
-//        CassError(err)
-//    }
-//    fn result_from<T>(self, t:T) -> Result<T> {
-//          match self.0 {
-//                CASS_OK => Ok(t),
-//                err => Err(Error::from(err.into()))
-//            }
-//    }
-
-fn pointer_to_string<'a>(c_buf: *const c_char) -> &'a str {
-    let buf: &[u8] = unsafe { CStr::from_ptr(c_buf).to_bytes() };
-    str::from_utf8(buf).unwrap()
+impl CassErrorResult {
+    /// Gets error code for the error result. This error code will always
+    /// have an server error source.
+    pub fn result_code(&self) -> _CassError { unsafe { cass_error_result_code(self.0) } }
+
+    /// Gets consistency that triggered the error result of the
+    /// following types:
+    ///
+    /// <ul>
+    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
+    ///  <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
+    ///  <li>CASS_ERROR_SERVER_READ_FAILURE</li>
+    ///  <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
+    ///  <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
+    /// </ul>
+    pub fn result_consistency(&self) -> Consistency {
+        unsafe { Consistency::build(cass_error_result_consistency(self.0)) }
+    }
+
+    /// Gets the actual number of received responses, received acknowledgments
+    /// or alive nodes for following error result types, respectively:
+    ///
+    /// <ul>
+    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
+    ///   <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
+    /// </ul>
+    pub fn responses_received(&self) -> i32 { unsafe { cass_error_result_responses_received(self.0) } }
+
+    /// Gets required responses, required acknowledgments or required alive nodes
+    /// needed to successfully complete the request for following error result types,
+    /// respectively:
+    ///
+    /// <ul>
+    ///  <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
+    ///   <li>CASS_ERROR_SERVER_UNAVAILABLE</li>
+    /// </ul>
+    pub fn responses_required(&self) -> i32 { unsafe { cass_error_result_responses_required(self.0) } }
+
+    /// Gets the number of nodes that experienced failures for the following error types:
+    ///
+    /// <ul>
+    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
+    /// </ul>
+    pub fn num_failures(&self) -> i32 { unsafe { cass_error_result_num_failures(self.0) } }
+
+    /// Determines whether the actual data was present in the responses from the
+    /// replicas for the following error result types:
+    ///
+    /// <ul>
+    ///   <li>CASS_ERROR_SERVER_READ_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_READ_FAILURE</li>
+    /// </ul>
+    pub fn data_present(&self) -> bool { unsafe { cass_error_result_data_present(self.0) == cass_true } }
+
+
+    /// Gets the write type of a request for the following error result types:
+    ///
+    /// <ul>
+    ///   <li>CASS_ERROR_SERVER_WRITE_TIMEOUT</li>
+    ///   <li>CASS_ERROR_SERVER_WRITE_FAILURE</li>
+    /// </ul>
+    pub fn write_type(&self) -> WriteType { unsafe { WriteType(cass_error_result_write_type(self.0)) } }
+
+    /// Gets the affected keyspace for the following error result types:
+    ///
+    /// <ul>
+    ///   <li>CASS_ERROR_SERVER_ALREADY_EXISTS</li>
+    ///   <li>CASS_ERROR_SERVER_FUNCTION_FAILURE</li>
+    /// </ul>
+    pub fn keyspace(&self) -> Result<String> {
+        unsafe {
+            let mut name = ptr::null();
+            let mut length = 0;
+            match cass_error_result_keyspace(self.0, &mut name, &mut length) {
+                CASS_OK => to_owned_string(name, length),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    /// Gets the affected table for the already exists error
+    /// (CASS_ERROR_SERVER_ALREADY_EXISTS) result type.
+    pub fn table(&self) -> Result<String> {
+        unsafe {
+            let mut name = ptr::null();
+            let mut length = 0;
+            match cass_error_result_table(self.0, &mut name, &mut length) {
+                CASS_OK => to_owned_string(name, length),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    /// Gets the affected function for the function failure error
+    /// (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
+    pub fn function(&self) -> Result<String> {
+        unsafe {
+            let mut name = ptr::null();
+            let mut length = 0;
+            match cass_error_result_function(self.0, &mut name, &mut length) {
+                CASS_OK => to_owned_string(name, length),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    /// Gets the number of argument types for the function failure error
+    /// (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
+    pub fn num_arg_types(&self) -> usize { unsafe { cass_error_num_arg_types(self.0) } }
+
+    /// Gets the argument type at the specified index for the function failure
+    /// error (CASS_ERROR_SERVER_FUNCTION_FAILURE) result type.
+    pub fn arg_type(&self, index: usize) -> Result<String> {
+        unsafe {
+            let mut arg_type = ptr::null();
+            let mut length = 0;
+            match cass_error_result_arg_type(self.0, index, &mut arg_type, &mut length) {
+                CASS_OK => to_owned_string(arg_type, length),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    /// Collects the details of this error result into a `ServerError`
+    pub fn server_error(&self) -> ServerError {
+        let code = self.result_code();
+        match code {
+            CASS_ERROR_SERVER_READ_TIMEOUT => {
+                ServerError::ReadTimeout {
+                    consistency: self.result_consistency(),
+                    received: self.responses_received(),
+                    required: self.responses_required(),
+                    data_present: self.data_present(),
+                }
+            }
+            CASS_ERROR_SERVER_WRITE_TIMEOUT => {
+                ServerError::WriteTimeout {
+                    consistency: self.result_consistency(),
+                    received: self.responses_received(),
+                    required: self.responses_required(),
+                    write_type: self.write_type(),
+                }
+            }
+            CASS_ERROR_SERVER_READ_FAILURE => {
+                ServerError::ReadFailure {
+                    consistency: self.result_consistency(),
+                    received: self.responses_received(),
+                    required: self.responses_required(),
+                    num_failures: self.num_failures(),
+                    data_present: self.data_present(),
+                }
+            }
+            CASS_ERROR_SERVER_WRITE_FAILURE => {
+                ServerError::WriteFailure {
+                    consistency: self.result_consistency(),
+                    received: self.responses_received(),
+                    required: self.responses_required(),
+                    num_failures: self.num_failures(),
+                    write_type: self.write_type(),
+                }
+            }
+            CASS_ERROR_SERVER_UNAVAILABLE => {
+                ServerError::Unavailable {
+                    consistency: self.result_consistency(),
+                    alive: self.responses_received(),
+                    required: self.responses_required(),
+                }
+            }
+            CASS_ERROR_SERVER_FUNCTION_FAILURE => {
+                ServerError::FunctionFailure {
+                    keyspace: self.keyspace().unwrap_or_default(),
+                    function: self.function().unwrap_or_default(),
+                    arg_types: (0..self.num_arg_types())
+                        .map(|index| self.arg_type(index).unwrap_or_default())
+                        .collect(),
+                }
+            }
+            CASS_ERROR_SERVER_ALREADY_EXISTS => {
+                ServerError::AlreadyExists {
+                    keyspace: self.keyspace().unwrap_or_default(),
+                    table: self.table().unwrap_or_default(),
+                }
+            }
+            code => ServerError::Other(code),
+        }
+    }
+}
+
+impl Drop for CassErrorResult {
+    fn drop(&mut self) { unsafe { cass_error_result_free(self.0) } }
+}
+
+/// The details of an error signaled by the server, as reported in its error response
+#[derive(Debug, Clone)]
+pub enum ServerError {
+    /// The coordinator timed out waiting for replicas to answer a read
+    ReadTimeout {
+        /// The consistency level of the request
+        consistency: Consistency,
+        /// The number of replicas that answered
+        received: i32,
+        /// The number of replicas needed to meet the consistency level
+        required: i32,
+        /// Whether the replica asked for the data answered
+        data_present: bool,
+    },
+    /// The coordinator timed out waiting for replicas to acknowledge a write
+    WriteTimeout {
+        /// The consistency level of the request
+        consistency: Consistency,
+        /// The number of replicas that acknowledged the write
+        received: i32,
+        /// The number of acknowledgements needed to meet the consistency level
+        required: i32,
+        /// The kind of write that timed out
+        write_type: WriteType,
+    },
+    /// One or more replicas failed to serve a read
+    ReadFailure {
+        /// The consistency level of the request
+        consistency: Consistency,
+        /// The number of replicas that answered
+        received: i32,
+        /// The number of replicas needed to meet the consistency level
+        required: i32,
+        /// The number of replicas that failed
+        num_failures: i32,
+        /// Whether the replica asked for the data answered
+        data_present: bool,
+    },
+    /// One or more replicas failed to apply a write
+    WriteFailure {
+        /// The consistency level of the request
+        consistency: Consistency,
+        /// The number of replicas that acknowledged the write
+        received: i32,
+        /// The number of acknowledgements needed to meet the consistency level
+        required: i32,
+        /// The number of replicas that failed
+        num_failures: i32,
+        /// The kind of write that failed
+        write_type: WriteType,
+    },
+    /// Too few replicas were alive to attempt the request at the requested consistency
+    Unavailable {
+        /// The consistency level of the request
+        consistency: Consistency,
+        /// The number of replicas known to be alive
+        alive: i32,
+        /// The number of replicas needed to meet the consistency level
+        required: i32,
+    },
+    /// A user defined function failed during execution
+    FunctionFailure {
+        /// The keyspace of the function
+        keyspace: String,
+        /// The name of the function
+        function: String,
+        /// The CQL types of the function's arguments
+        arg_types: Vec<String>,
+    },
+    /// A keyspace or table being created already exists
+    AlreadyExists {
+        /// The keyspace that already exists, or that holds the table
+        keyspace: String,
+        /// The table that already exists, empty if it was the keyspace
+        table: String,
+    },
+    /// Any other server error, which carries no further details
+    Other(_CassError),
+}
+
+impl Display for ServerError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            ServerError::ReadTimeout { ref consistency, received, required, data_present } => {
+                write!(f,
+                       "read timeout at {}: {} of {} responses received (data present: {})",
+                       consistency.to_string(),
+                       received,
+                       required,
+                       data_present)
+            }
+            ServerError::WriteTimeout { ref consistency, received, required, ref write_type } => {
+                write!(f,
+                       "{:?} write timeout at {}: {} of {} acknowledgements received",
+                       write_type,
+                       consistency.to_string(),
+                       received,
+                       required)
+            }
+            ServerError::ReadFailure { ref consistency, received, required, num_failures, .. } => {
+                write!(f,
+                       "read failure at {}: {} of {} responses received, {} failed",
+                       consistency.to_string(),
+                       received,
+                       required,
+                       num_failures)
+            }
+            ServerError::WriteFailure { ref consistency, received, required, num_failures, ref write_type } => {
+                write!(f,
+                       "{:?} write failure at {}: {} of {} acknowledgements received, {} failed",
+                       write_type,
+                       consistency.to_string(),
+                       received,
+                       required,
+                       num_failures)
+            }
+            ServerError::Unavailable { ref consistency, alive, required } => {
+                write!(f,
+                       "unavailable at {}: {} of {} replicas alive",
+                       consistency.to_string(),
+                       alive,
+                       required)
+            }
+            ServerError::FunctionFailure { ref keyspace, ref function, ref arg_types } => {
+                write!(f, "function {}.{}({}) failed", keyspace, function, arg_types.join(", "))
+            }
+            ServerError::AlreadyExists { ref keyspace, ref table } if table.is_empty() => {
+                write!(f, "keyspace {} already exists", keyspace)
+            }
+            ServerError::AlreadyExists { ref keyspace, ref table } => {
+                write!(f, "table {}.{} already exists", keyspace, table)
+            }
+            ServerError::Other(code) => write!(f, "{}", desc(code)),
+        }
+    }
+}
+
+fn desc(code: _CassError) -> &'static str {
+    unsafe { CStr::from_ptr(cass_error_desc(code)).to_str().unwrap_or("unknown error") }
 }
-//    /// Gets the textual description for this error
-//    pub fn desc(&self) -> &str {
-//        {
-//            CassError::pointer_to_string(unsafe { cass_error_desc(self.0) })
-//            //            match *self {
-//            //                CassError::Lib(ref err) => CassError::pointer_to_string(cass_error_desc(err.err)),
-//            //                CassError::Ssl(ref err) => CassError::pointer_to_string(cass_error_desc(err.0)),
-//            //                CassError::Server(ref err) => CassError::pointer_to_string(cass_error_desc(err.0)),
-//            //                CassError::Rust(ref err) => {
-//            //                    match err {
-//            //          &CassRustError::NulInString(_) => "Tried to create a CString with a nul in the middle",
-//            //                        /// /FIXME how do i return a slice created from self?
-//            //                        &CassRustError::BadAddress(_) => "Tried to parse an invalid ip address",
-//            //                    }
-//            //                }
-//            //            }
-//        }
-//    }
-// }
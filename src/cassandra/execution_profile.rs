@@ -0,0 +1,234 @@
+//! Named bundles of request settings.
+//!
+//! The C++ driver has no execution profiles of its own, so a profile is kept on the Rust side:
+//! it is registered on `Cluster` under a name, handed to the sessions the cluster connects, and
+//! its settings are copied onto a `Statement` or `Batch` when the profile is selected for it.
+//! Settings made on the statement or batch afterwards take precedence.
+//!
+//! A profile's load balancing policy is a `LoadBalancingPolicy` written in Rust, by which
+//! `Session::execute_balanced` routes the statements the profile is selected for. The driver
+//! routes batches itself, so the policy is not applied to them.
+
+use cassandra::consistency::Consistency;
+use cassandra::error::*;
+use cassandra::policy::load_balancing::LoadBalancingPolicy;
+use cassandra::policy::retry::RetryPolicy;
+use cassandra_sys::CASS_ERROR_LIB_BAD_PARAMS;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use time::Duration;
+
+/// The consistency, serial consistency, request timeout, retry policy and load balancing
+/// policy to execute statements with. Settings left unset keep the statement's own, or the
+/// cluster's.
+///
+/// ```ignore
+/// let mut analytics = ExecutionProfile::default();
+/// analytics.set_consistency(Consistency::ALL).set_request_timeout(Duration::seconds(60));
+/// cluster.set_execution_profile("analytics", analytics);
+/// let session = cluster.connect()?;
+///
+/// statement.set_execution_profile_name(&session, "analytics")?;
+/// ```
+#[derive(Debug, Default)]
+pub struct ExecutionProfile {
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Consistency>,
+    request_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
+}
+
+impl ExecutionProfile {
+    /// Sets the consistency level
+    pub fn set_consistency(&mut self, consistency: Consistency) -> &mut Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    /// Sets the serial consistency level
+    pub fn set_serial_consistency(&mut self, serial_consistency: Consistency) -> &mut Self {
+        self.serial_consistency = Some(serial_consistency);
+        self
+    }
+
+    /// Sets the timeout for waiting for a response from a node. A zero timeout disables it, and
+    /// a negative one is rejected when the profile is applied.
+    pub fn set_request_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the retry policy. A policy written in Rust is refused: pass it to
+    /// `Session::execute_with_retry` instead.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> Result<&mut Self> {
+        retry_policy.check_driver_policy()?;
+        self.retry_policy = Some(retry_policy);
+        Ok(self)
+    }
+
+    /// Sets the load balancing policy, by which `Session::execute_balanced` routes statements
+    pub fn set_load_balancing_policy<P: LoadBalancingPolicy + 'static>(&mut self, policy: P) -> &mut Self {
+        self.load_balancing_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// The consistency level, if set
+    pub fn consistency(&self) -> Option<Consistency> { self.consistency }
+
+    /// The serial consistency level, if set
+    pub fn serial_consistency(&self) -> Option<Consistency> { self.serial_consistency }
+
+    /// The request timeout, if set
+    pub fn request_timeout(&self) -> Option<Duration> { self.request_timeout }
+
+    /// The retry policy, if set
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> { self.retry_policy.as_ref() }
+
+    /// The load balancing policy, if set
+    pub fn load_balancing_policy(&self) -> Option<&Arc<dyn LoadBalancingPolicy>> { self.load_balancing_policy.as_ref() }
+
+    /// Applies the settings that are set to `request`, leaving its other settings alone
+    pub(crate) fn apply_to<R: ProfileTarget>(&self, request: &mut R) -> Result<()> {
+        if let Some(consistency) = self.consistency {
+            request.apply_consistency(consistency)?;
+        }
+        if let Some(serial_consistency) = self.serial_consistency {
+            request.apply_serial_consistency(serial_consistency)?;
+        }
+        if let Some(timeout) = self.request_timeout {
+            request.apply_request_timeout(timeout)?;
+        }
+        if let Some(ref retry_policy) = self.retry_policy {
+            request.apply_retry_policy(retry_policy)?;
+        }
+        if let Some(ref policy) = self.load_balancing_policy {
+            request.apply_load_balancing_policy(policy);
+        }
+        Ok(())
+    }
+}
+
+/// A statement or batch that the settings of an execution profile can be applied to
+pub(crate) trait ProfileTarget {
+    fn apply_consistency(&mut self, consistency: Consistency) -> Result<()>;
+
+    fn apply_serial_consistency(&mut self, serial_consistency: Consistency) -> Result<()>;
+
+    fn apply_request_timeout(&mut self, timeout: Duration) -> Result<()>;
+
+    fn apply_retry_policy(&mut self, retry_policy: &RetryPolicy) -> Result<()>;
+
+    fn apply_load_balancing_policy(&mut self, policy: &Arc<dyn LoadBalancingPolicy>);
+}
+
+/// The execution profiles registered on a cluster, by name
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ExecutionProfiles(HashMap<String, Arc<ExecutionProfile>>);
+
+impl ExecutionProfiles {
+    /// Registers `profile` under `name`, replacing any profile of that name
+    pub fn insert(&mut self, name: String, profile: ExecutionProfile) { self.0.insert(name, Arc::new(profile)); }
+
+    /// The load balancing policies of the profiles
+    pub fn load_balancing_policies(&self) -> impl Iterator<Item = &Arc<dyn LoadBalancingPolicy>> {
+        self.0.values().filter_map(|profile| profile.load_balancing_policy())
+    }
+
+    /// Gets the profile registered under `name`
+    pub fn get(&self, name: &str) -> Result<&ExecutionProfile> {
+        self.0.get(name).map(|profile| &**profile).ok_or_else(|| {
+            CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                                    format!("no execution profile named {:?}", name))
+                .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra::policy::load_balancing::HostDistance;
+    use cassandra::cluster::ContactPoint;
+
+    /// A request recording the settings applied to it
+    #[derive(Default)]
+    pub struct Request(pub Vec<String>);
+
+    impl ProfileTarget for Request {
+        fn apply_consistency(&mut self, consistency: Consistency) -> Result<()> {
+            self.0.push(format!("consistency {:?}", consistency));
+            Ok(())
+        }
+
+        fn apply_serial_consistency(&mut self, serial_consistency: Consistency) -> Result<()> {
+            self.0.push(format!("serial consistency {:?}", serial_consistency));
+            Ok(())
+        }
+
+        fn apply_request_timeout(&mut self, timeout: Duration) -> Result<()> {
+            self.0.push(format!("request timeout {}", timeout));
+            Ok(())
+        }
+
+        fn apply_retry_policy(&mut self, retry_policy: &RetryPolicy) -> Result<()> {
+            retry_policy.check_driver_policy()?;
+            self.0.push("retry policy".to_owned());
+            Ok(())
+        }
+
+        fn apply_load_balancing_policy(&mut self, policy: &Arc<dyn LoadBalancingPolicy>) {
+            self.0.push(format!("load balancing policy {:?}", policy));
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Local;
+
+    impl LoadBalancingPolicy for Local {
+        fn distance(&self, _host: &ContactPoint) -> HostDistance { HostDistance::Local }
+    }
+}
+
+#[test]
+fn test_execution_profiles() {
+    use self::fake::*;
+    use cassandra::util::Protected;
+    use cassandra_sys::CassConsistency_::{CASS_CONSISTENCY_ALL, CASS_CONSISTENCY_SERIAL};
+
+    let mut oltp = ExecutionProfile::default();
+    oltp.set_request_timeout(Duration::milliseconds(200));
+    let mut analytics = ExecutionProfile::default();
+    analytics.set_consistency(Consistency::build(CASS_CONSISTENCY_ALL))
+        .set_serial_consistency(Consistency::build(CASS_CONSISTENCY_SERIAL))
+        .set_request_timeout(Duration::seconds(60))
+        .set_load_balancing_policy(Local);
+    // the retry policy wraps no driver policy, so the profiles are forgotten rather than freed
+    analytics.set_retry_policy(RetryPolicy::build(::std::ptr::null_mut())).unwrap();
+    let mut profiles = ExecutionProfiles::default();
+    profiles.insert("oltp".to_owned(), oltp);
+    profiles.insert("analytics".to_owned(), analytics);
+    profiles.insert("default".to_owned(), ExecutionProfile::default());
+
+    let mut request = Request::default();
+    profiles.get("oltp").unwrap().apply_to(&mut request).unwrap();
+    assert_eq!(request.0, vec!["request timeout PT0.200S"]);
+
+    let mut request = Request::default();
+    profiles.get("analytics").unwrap().apply_to(&mut request).unwrap();
+    assert_eq!(request.0,
+               vec!["consistency Consistency(CASS_CONSISTENCY_ALL)",
+                    "serial consistency Consistency(CASS_CONSISTENCY_SERIAL)",
+                    "request timeout PT60S",
+                    "retry policy",
+                    "load balancing policy Local"]);
+
+    let mut request = Request::default();
+    profiles.get("default").unwrap().apply_to(&mut request).unwrap();
+    assert!(request.0.is_empty());
+
+    assert_eq!(profiles.load_balancing_policies().count(), 1);
+    assert!(profiles.get("batch").is_err());
+    ::std::mem::forget(profiles);
+}
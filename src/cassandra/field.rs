@@ -4,14 +4,16 @@ use cassandra::error::CassError;
 use cassandra::inet::Inet;
 use cassandra::iterator::MapIterator;
 use cassandra::iterator::SetIterator;
-// use decimal::d128;
-use cassandra::util::Protected;
+use cassandra::iterator::{LendingIterator, TupleIterator, UserTypeFieldIterator};
+use decimal::d128;
+use num::BigInt;
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
+use cassandra::util::{CassErrorExt, Protected};
 
 use cassandra::uuid::Uuid;
 use cassandra::value::{Value, ValueType};
 use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
 
-use cassandra_sys::CASS_OK;
 use cassandra_sys::CASS_VALUE_TYPE_ASCII;
 use cassandra_sys::CASS_VALUE_TYPE_BIGINT;
 use cassandra_sys::CASS_VALUE_TYPE_BLOB;
@@ -55,17 +57,16 @@ use cassandra_sys::cass_value_get_int16;
 use cassandra_sys::cass_value_get_int32;
 use cassandra_sys::cass_value_get_int64;
 use cassandra_sys::cass_value_get_int8;
-use cassandra_sys::cass_value_get_string;
 use cassandra_sys::cass_value_get_uint32;
 use cassandra_sys::cass_value_get_uuid;
 use cassandra_sys::cass_value_type;
-use errors::*;
+use cassandra::error::*;
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::mem;
-use std::slice;
 use std::str;
 
 
@@ -96,14 +97,14 @@ use std::str;
 // }
 
 /// A field's metadata
-pub struct Field {
+pub struct Field<'a> {
     /// The field's name
     pub name: String,
     /// The field's value
-    pub value: Value,
+    pub value: Value<'a>,
 }
 
-impl Debug for Field {
+impl<'a> Debug for Field<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self.get_type().inner() {
             CASS_VALUE_TYPE_UNKNOWN => write!(f, "UNKNOWN Cassandra type"),
@@ -129,19 +130,22 @@ impl Debug for Field {
             CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID Cassandra type"),
             CASS_VALUE_TYPE_INET => write!(f, "INET Cassandra type"),
             CASS_VALUE_TYPE_LIST => {
-                for item in self.set_iter().expect("a list must be able to return a set iterator") {
+                let mut items = self.get_list().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {}", item)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_MAP => {
-                for item in self.map_iter().expect("a map must be able to return a map iterator") {
+                let mut items = self.map_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {}-{}", item.0, item.1)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_SET => {
-                for item in self.set_iter().expect("a set must be able to return a set iterator") {
+                let mut items = self.set_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "SET {}", item)?
                 }
                 Ok(())
@@ -153,7 +157,7 @@ impl Debug for Field {
     }
 }
 
-impl Display for Field {
+impl<'a> Display for Field<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self.get_type().inner() {
             CASS_VALUE_TYPE_UNKNOWN => write!(f, "UNKNOWN Cassandra type"),
@@ -174,24 +178,27 @@ impl Display for Field {
             CASS_VALUE_TYPE_TEXT => write!(f, "TEXT Cassandra type"),
             CASS_VALUE_TYPE_TIMESTAMP => write!(f, "TIMESTAMP Cassandra type"),
             CASS_VALUE_TYPE_UUID => write!(f, "UUID Cassandra type"),
-            CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string().unwrap()),
+            CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string_lossy().map_err(|_| fmt::Error)?),
             CASS_VALUE_TYPE_VARINT => Ok(()),
             CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID Cassandra type"),
             CASS_VALUE_TYPE_INET => write!(f, "INET Cassandra type"),
             CASS_VALUE_TYPE_LIST => {
-                for item in self.set_iter().expect("a list must be able to return a set iterator") {
+                let mut items = self.get_list().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "LIST {}", item)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_MAP => {
-                for item in self.map_iter().expect("a map must be able to return a map iterator") {
+                let mut items = self.map_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "MAP {}-{}", item.0, item.1)?
                 }
                 Ok(())
             }
             CASS_VALUE_TYPE_SET => {
-                for item in self.set_iter().expect("a set must be able to return a set iterator") {
+                let mut items = self.set_iter().map_err(|_| fmt::Error)?;
+                while let Some(item) = items.next() {
                     write!(f, "SET {}", item)?
                 }
                 Ok(())
@@ -214,7 +221,7 @@ impl Display for Field {
 // }
 //
 
-impl Field {
+impl<'a> Field<'a> {
     /// Gets the name of this field
     pub fn get_name(&self) -> String { self.name.clone() }
 
@@ -226,8 +233,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_inet(self.value.inner(), &mut output)
-                .to_result(Inet::build(output))
-                .chain_err(|| "")
+                .wrap(Inet::build(output))
         }
     }
 
@@ -235,8 +241,7 @@ impl Field {
     pub fn get_u32(&self, mut output: u32) -> Result<u32> {
         unsafe {
             cass_value_get_uint32(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -244,8 +249,7 @@ impl Field {
     pub fn get_int8(&self, mut output: i8) -> Result<i8> {
         unsafe {
             cass_value_get_int8(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -253,39 +257,46 @@ impl Field {
     pub fn get_int16(&self, mut output: i16) -> Result<i16> {
         unsafe {
             cass_value_get_int16(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
-    //    //    pub fn get_decimal(&self, mut output: d128) -> Result<d128, CassError> {
-    //    //        let _ = output;
-    //    //        unimplemented!()
-    //    //        // unsafe { CassError::build(cass_value_get_decimal(self.0, &mut output)).wrap(output) }
-    //    //    }
-    //
+
+    /// Gets the value of a decimal field
+    pub fn get_decimal(&self) -> Result<d128> { self.value.get_decimal() }
+
+    /// Gets the value of a varint field
+    pub fn get_varint(&self) -> Result<BigInt> { self.value.get_varint() }
+
+    /// Gets the value of a date field
+    pub fn get_date(&self) -> Result<CqlDate> { self.value.get_date() }
+
+    /// Gets the value of a time field
+    pub fn get_time(&self) -> Result<CqlTime> { self.value.get_time() }
+
+    /// Gets the value of a timestamp field
+    pub fn get_timestamp(&self) -> Result<CqlTimestamp> { self.value.get_timestamp() }
+
+    /// Gets the value of a duration field
+    pub fn get_duration(&self) -> Result<CqlDuration> { self.value.get_duration() }
 
     /// Gets the value of an ASCII, Text, or Varchar field
-    #[allow(cast_possible_truncation)]
-    pub fn get_string(&self) -> Result<String> {
-        unsafe {
-            match cass_value_type(self.value.inner()) {
-                CASS_VALUE_TYPE_ASCII |
-                CASS_VALUE_TYPE_TEXT |
-                CASS_VALUE_TYPE_VARCHAR => {
-                    let mut message = mem::zeroed();
-                    let mut message_length = mem::zeroed();
-                    match cass_value_get_string(self.value.inner(), &mut message, &mut message_length) {
-                        CASS_OK => {
-                            let slice = slice::from_raw_parts(message as *const u8, message_length as usize);
-                            Ok(str::from_utf8(slice).expect("must be utf8").to_owned())
-                        }
-                        err => Err(err.to_result("").unwrap().into()),
-                    }
+    pub fn get_string(&self) -> Result<String> { self.get_str().map(str::to_owned) }
 
+    /// Borrows the value of an ASCII, Text, or Varchar field without copying it. Fails if the
+    /// text is not valid UTF-8.
+    pub fn get_str(&self) -> Result<&'a str> { Ok(str::from_utf8(self.get_string_bytes()?)?) }
 
-                }
-                other => panic!("unsupported type: {:?}", other), //FIXME
-            }
+    /// Gets the value of an ASCII, Text, or Varchar field, replacing any invalid UTF-8
+    /// sequences with U+FFFD
+    pub fn get_string_lossy(&self) -> Result<Cow<'a, str>> { Ok(String::from_utf8_lossy(self.get_string_bytes()?)) }
+
+    /// Borrows the raw bytes of an ASCII, Text, or Varchar field, which may not be valid UTF-8
+    pub fn get_string_bytes(&self) -> Result<&'a [u8]> {
+        match self.get_type().inner() {
+            CASS_VALUE_TYPE_ASCII |
+            CASS_VALUE_TYPE_TEXT |
+            CASS_VALUE_TYPE_VARCHAR => self.value.get_string_bytes(),
+            _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
         }
     }
 
@@ -294,8 +305,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int32(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -304,8 +314,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int64(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -314,8 +323,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_float(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -324,8 +332,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_double(self.value.inner(), &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -334,8 +341,7 @@ impl Field {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_bool(self.value.inner(), &mut output)
-                .to_result(output == cass_true)
-                .chain_err(|| "")
+                .wrap(output == cass_true)
         }
     }
 
@@ -343,36 +349,36 @@ impl Field {
     pub fn get_uuid(&self) -> Result<Uuid> {
         unsafe {
             let mut uuid = mem::zeroed();
-            cass_value_get_uuid(self.value.inner(), &mut uuid).to_result(Uuid::build(uuid)).chain_err(|| "")
+            cass_value_get_uuid(self.value.inner(), &mut uuid).wrap(Uuid::build(uuid))
         }
     }
 
     /// Gets the value of a map field as an iterator
-    pub fn map_iter(&self) -> Result<MapIterator> {
+    pub fn map_iter(&self) -> Result<MapIterator<'a>> {
         unsafe {
             match self.get_type().inner() {
                 CASS_VALUE_TYPE_MAP => Ok(MapIterator::build(cass_iterator_from_map(self.value.inner()))),
-                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE).chain_err(|| ""),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
     /// Gets the value of a set field as an iterator
-    pub fn set_iter(&self) -> Result<SetIterator> {
+    pub fn set_iter(&self) -> Result<SetIterator<'a>> {
         unsafe {
             match self.get_type().inner() {
                 CASS_VALUE_TYPE_SET => Ok(SetIterator::build(cass_iterator_from_collection(self.value.inner()))),
-                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE).chain_err(|| ""),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
-    //        pub fn use_type_iter(&self) -> Result<UserTypeIterator, CassError> {
-    //            unsafe {
-    //                match self.get_type() {
-    //                    ValueType::UDT => Ok(UserTypeIterator(cass_iterator_from_user_type(self.0))),
-    //                    _ => Err(CassError::build(1)),
-    //                }
-    //            }
-    //        }
+    /// Gets the value of a list field as an iterator
+    pub fn get_list(&self) -> Result<SetIterator<'a>> { self.value.get_list() }
+
+    /// Gets the value of a tuple field as an iterator
+    pub fn get_tuple(&self) -> Result<TupleIterator<'a>> { self.value.get_tuple() }
+
+    /// Gets the value of a user defined type field as an iterator over its named fields
+    pub fn get_user_type(&self) -> Result<UserTypeFieldIterator<'a>> { self.value.get_user_type() }
 }
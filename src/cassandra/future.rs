@@ -1,11 +1,10 @@
-use Session;
-
 use cassandra::error::{CassError, CassErrorResult};
 use cassandra::prepared::PreparedStatement;
 use cassandra::result::CassResult;
-use cassandra::util::Protected;
-use cassandra_sys::CASS_OK;
+use cassandra::util::{CassErrorExt, Protected, to_owned_string};
+use cassandra_sys::{CASS_ERROR_LIB_NULL_VALUE, CASS_OK};
 
+use cassandra_sys::CassError as _CassError;
 use cassandra_sys::CassFuture as _Future;
 use cassandra_sys::CassFutureCallback as _CassFutureCallback;
 use cassandra_sys::cass_future_custom_payload_item;
@@ -23,11 +22,17 @@ use cassandra_sys::cass_future_wait;
 use cassandra_sys::cass_future_wait_timed;
 
 use cassandra_sys::cass_true;
-use errors::*;
-use std::mem;
+use cassandra::error::*;
+use std::fmt;
+use std::future;
 use std::os::raw;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::ptr;
 use std::slice;
-use std::str;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
 
 /// A CQL Future representing the status of any asynchronous calls to Cassandra
 #[derive(Debug)]
@@ -45,7 +50,7 @@ impl Drop for Future {
 impl Future {
     /// Sets a callback that is called when a future is set
     pub unsafe fn set_callback(&mut self, callback: FutureCallback, data: *mut raw::c_void) -> Result<&mut Self> {
-        cass_future_set_callback(self.0, callback.0, data).to_result(self).chain_err(|| "")
+        cass_future_set_callback(self.0, callback.0, data).wrap(self)
     }
 
     /// Gets the set status of the future.
@@ -73,26 +78,16 @@ impl Future {
 
     /// Gets the error result from a future that failed as a result of a server error. If the
     /// future is not ready this method will wait for the future to be set.
-    pub fn get_error_result(&self) -> CassErrorResult {
-        unsafe { CassErrorResult::build(cass_future_get_error_result(self.0)) }
-    }
+    /// A None response indicates that the future did not fail with a server error
+    pub fn get_error_result(&self) -> Option<CassErrorResult> { unsafe { error_result(self.0) } }
 
     /// Gets the error code from future. If the future is not ready this method will
     // wait for the future to be set.
-    fn error_code(self) -> Result<()> { unsafe { cass_future_error_code(self.0).to_result(()).chain_err(|| "") } }
+    fn error_code(self) -> Result<()> { unsafe { error_code(self.0) } }
 
     /// Gets the error message from future. If the future is not ready this method will
     /// wait for the future to be set.
-    pub fn error_message(&mut self) -> String {
-        unsafe {
-            let message = mem::zeroed();
-            let message_length = mem::zeroed();
-            cass_future_error_message(self.0, message, message_length);
-
-            let slice: &[u8] = slice::from_raw_parts(message as *const u8, message_length as usize);
-            str::from_utf8(slice).expect("must be utf8").to_owned()
-        }
-    }
+    pub fn error_message(&mut self) -> String { unsafe { error_message(self.0) } }
 
 
     /// Gets a the number of custom payload items from a response future. If the future is not
@@ -103,22 +98,196 @@ impl Future {
     /// ready this method will wait for the future to be set.
     pub fn payload_item(&self, index: usize) -> Result<(String, String)> {
         unsafe {
-            let name = mem::zeroed();
-            let name_length = mem::zeroed();
-            let value = mem::zeroed();
-            let value_length = mem::zeroed();
-            match cass_future_custom_payload_item(self.0, index, name, name_length, value, value_length) {
-                CASS_OK => {
-                    Ok((str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                        .expect("must be utf8")
-                        .to_owned(),
-                        str::from_utf8(slice::from_raw_parts(value as *const u8, value_length as usize))
-                        .expect("must be utf8")
-                        .to_owned()))
+            let mut name = ptr::null();
+            let mut name_length = 0;
+            let mut value = ptr::null();
+            let mut value_length = 0;
+            cass_future_custom_payload_item(self.0,
+                                            index,
+                                            &mut name,
+                                            &mut name_length,
+                                            &mut value,
+                                            &mut value_length)
+                .wrap(())?;
+            Ok((to_owned_string(name, name_length)?, to_owned_string(value as *const raw::c_char, value_length)?))
+        }
+    }
+}
+
+/// The driver calls a future is polled and given callbacks through, kept apart so the bookkeeping
+/// around them can be tested without the driver
+trait RawFuture: Copy {
+    /// Whether the future is set
+    fn ready(self) -> bool;
+
+    /// Registers the future's only callback; the driver runs it at once if the future is set
+    fn set_callback(self, callback: _CassFutureCallback, data: *mut raw::c_void) -> _CassError;
+}
+
+impl RawFuture for *mut _Future {
+    fn ready(self) -> bool { unsafe { cass_future_ready(self) == cass_true } }
+
+    fn set_callback(self, callback: _CassFutureCallback, data: *mut raw::c_void) -> _CassError {
+        unsafe { cass_future_set_callback(self, callback, data) }
+    }
+}
+
+/// What to do once a future is set: wake the task that last polled it, or run the closure given
+/// to `on_complete` after it was polled
+enum Wakeup {
+    Task(Waker),
+    Closure(Callback),
+}
+
+/// The wakeup of a polled future, shared with the driver callback registered by the first poll.
+/// The driver takes a single callback per future, so everything that waits on a polled future
+/// goes through it.
+#[derive(Default)]
+struct WakerSlot(Mutex<Option<Wakeup>>);
+
+impl fmt::Debug for WakerSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("WakerSlot") }
+}
+
+impl WakerSlot {
+    fn set(&self, wakeup: Wakeup) { *self.0.lock().unwrap_or_else(|err| err.into_inner()) = Some(wakeup) }
+
+    fn take(&self) -> Option<Wakeup> { self.0.lock().unwrap_or_else(|err| err.into_inner()).take() }
+
+    /// Wakes the polling task or runs the closure, whichever is stored
+    fn fire(&self) {
+        match self.take() {
+            Some(Wakeup::Task(waker)) => waker.wake(),
+            Some(Wakeup::Closure(callback)) => run(callback),
+            None => {}
+        }
+    }
+}
+
+/// Invoked by the driver once the future is set. Reclaims the slot handed over in `poll_set`
+/// and wakes whichever task last polled the future.
+unsafe extern "C" fn wake_task(_future: *mut _Future, data: *mut raw::c_void) {
+    Arc::from_raw(data as *const WakerSlot).fire()
+}
+
+/// Polls a driver future for completion without blocking. The first time the future is found
+/// pending a callback is registered with the driver to wake the polling task; later polls only
+/// replace the stored waker.
+fn poll_set<F: RawFuture>(future: F, slot: &mut Option<Arc<WakerSlot>>, cx: &mut Context) -> Poll<Result<()>> {
+    if future.ready() {
+        return Poll::Ready(Ok(()));
+    }
+    match *slot {
+        Some(ref slot) => slot.set(Wakeup::Task(cx.waker().clone())),
+        None => {
+            let new_slot = Arc::new(WakerSlot::default());
+            new_slot.set(Wakeup::Task(cx.waker().clone()));
+            let data = Arc::into_raw(new_slot.clone()) as *mut raw::c_void;
+            match future.set_callback(Some(wake_task), data) {
+                CASS_OK => *slot = Some(new_slot),
+                err => {
+                    drop(unsafe { Arc::from_raw(data as *const WakerSlot) });
+                    return Poll::Ready(err.wrap(()));
                 }
-                err => Err(err.to_result("").unwrap().into()),
             }
+        }
+    }
+    // the future may have been set before the waker was stored
+    if future.ready() { Poll::Ready(Ok(())) } else { Poll::Pending }
+}
 
+/// Gets the error a future failed with, keeping the driver's message and, for server errors,
+/// the details of the server's response.
+unsafe fn error_code(future: *mut _Future) -> Result<()> {
+    match cass_future_error_code(future) {
+        CASS_OK => Ok(()),
+        code => {
+            let err = CassError::with_message(code, error_message(future));
+            match error_result(future) {
+                Some(details) => Err(Error::Server(err, details.server_error())),
+                None => Err(err.into()),
+            }
+        }
+    }
+}
+
+unsafe fn error_message(future: *mut _Future) -> String {
+    let mut message = ptr::null();
+    let mut message_length = 0;
+    cass_future_error_message(future, &mut message, &mut message_length);
+    String::from_utf8_lossy(slice::from_raw_parts(message as *const u8, message_length)).into_owned()
+}
+
+unsafe fn error_result(future: *mut _Future) -> Option<CassErrorResult> {
+    let error_result = cass_future_get_error_result(future);
+    if error_result.is_null() {
+        None
+    } else {
+        Some(CassErrorResult::build(error_result))
+    }
+}
+
+/// A closure to run once a future is set
+type Callback = Box<dyn FnOnce() + Send>;
+
+/// Runs a closure on a driver thread, stopping any panic from unwinding into the driver
+fn run(callback: Callback) {
+    if panic::catch_unwind(AssertUnwindSafe(callback)).is_err() {
+        error!("panic in future callback");
+    }
+}
+
+/// Invoked by the driver once a future is set. Takes back ownership of the boxed closure handed
+/// over in `set_closure`, so it is run and freed exactly once.
+unsafe extern "C" fn run_callback(_future: *mut _Future, data: *mut raw::c_void) {
+    run(*Box::from_raw(data as *mut Callback))
+}
+
+/// Registers a closure to be run on a driver thread once the future is set. If the driver refuses
+/// the callback the closure is dropped without being run.
+fn set_closure<F: RawFuture>(future: F, callback: Callback) -> Result<()> {
+    let data = Box::into_raw(Box::new(callback)) as *mut raw::c_void;
+    match future.set_callback(Some(run_callback), data) {
+        CASS_OK => Ok(()),
+        err => {
+            drop(unsafe { Box::from_raw(data as *mut Callback) });
+            err.wrap(())
+        }
+    }
+}
+
+/// Runs a closure once the future is set. A future that was already polled has its one driver
+/// callback taken by `poll_set`, so the closure replaces the polling task's waker instead; it is
+/// run right away if the future was set in the meantime.
+fn run_when_set<F: RawFuture>(future: F, slot: Option<Arc<WakerSlot>>, callback: Callback) -> Result<()> {
+    match slot {
+        None => set_closure(future, callback),
+        Some(slot) => {
+            slot.set(Wakeup::Closure(callback));
+            if future.ready() {
+                slot.fire();
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Wakes a thread blocked in `block_on`
+struct Unpark(thread::Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) { self.0.unpark() }
+}
+
+/// Blocks the calling thread until a future made of driver futures is done, for the `wait` of
+/// futures that are not themselves driver futures
+pub(crate) fn block_on<F: future::Future + Unpin>(mut future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
         }
     }
 }
@@ -128,8 +297,12 @@ impl Future {
 /// It can represent a result if the operation completed successfully or an
 /// error if the operation failed. It can be waited on, polled or a callback
 /// can be attached.
+///
+/// It also implements `std::future::Future`, so it can be `.await`ed from an async
+/// executor without blocking a thread.
 #[derive(Debug)]
-pub struct ResultFuture(*mut _Future);
+pub struct ResultFuture(*mut _Future, Option<Arc<WakerSlot>>);
+unsafe impl Send for ResultFuture {}
 
 impl Drop for ResultFuture {
     fn drop(&mut self) { unsafe { cass_future_free(self.0) } }
@@ -144,32 +317,39 @@ impl ResultFuture {
         }
     }
 
+    /// Calls `callback` with the outcome once the future is set, without blocking.
+    ///
+    /// Important: The callback runs on a driver IO thread. Do not wait on other
+    /// futures from within it.
+    ///
+    /// The future may have been polled before. If it is already set, the callback may run
+    /// right away on the calling thread.
+    pub fn on_complete<F>(self, callback: F) -> Result<()>
+        where F: FnOnce(Result<CassResult>) + Send + 'static {
+        let (future, slot) = (self.0, self.1.clone());
+        run_when_set(future,
+                     slot,
+                     Box::new(move || {
+                         let mut this = self;
+                         callback(this.error_code())
+                     }))
+    }
+
     /// Gets the error code from future. If the future is not ready this method will
     /// wait for the future to be set.
     pub fn error_code(&mut self) -> Result<CassResult> {
-        unsafe {
-            let x = self.get();
-            let error_code = cass_future_error_code(self.0);
-            match (x, error_code) {
-                (Some(x), _) => Ok(x),
-                (None, CASS_OK) => unimplemented!(),
-                (None, err) => Err(err.to_result("").unwrap().into()),
-            }
-        }
+        unsafe { error_code(self.0)? };
+        self.get().ok_or_else(|| CASS_ERROR_LIB_NULL_VALUE.into())
     }
 
+    /// Gets the error result from a future that failed as a result of a server error. If the
+    /// future is not ready this method will wait for the future to be set.
+    /// A None response indicates that the future did not fail with a server error
+    pub fn get_error_result(&self) -> Option<CassErrorResult> { unsafe { error_result(self.0) } }
+
     /// Gets the error message from future. If the future is not ready this method will
     /// wait for the future to be set.
-    pub fn error_message(&mut self) -> String {
-        unsafe {
-            let message = mem::zeroed();
-            let message_length = mem::zeroed();
-            cass_future_error_message(self.0, message, message_length);
-
-            let slice = slice::from_raw_parts(message as *const u8, message_length as usize);
-            str::from_utf8(slice).expect("must be utf8").to_owned()
-        }
-    }
+    pub fn error_message(&mut self) -> String { unsafe { error_message(self.0) } }
 
 
 
@@ -193,8 +373,12 @@ impl ResultFuture {
 /// It can represent a result if the operation completed successfully or an
 /// error if the operation failed. It can be waited on, polled or a callback
 /// can be attached.
+///
+/// It also implements `std::future::Future`, so it can be `.await`ed from an async
+/// executor without blocking a thread.
 #[derive(Debug)]
-pub struct PreparedFuture(*mut _Future);
+pub struct PreparedFuture(*mut _Future, Option<Arc<WakerSlot>>);
+unsafe impl Send for PreparedFuture {}
 
 impl Drop for PreparedFuture {
     fn drop(&mut self) { unsafe { cass_future_free(self.0) } }
@@ -212,10 +396,29 @@ impl PreparedFuture {
         }
     }
 
+    /// Calls `callback` with the prepared statement once the future is set, without blocking.
+    ///
+    /// Important: The callback runs on a driver IO thread. Do not wait on other
+    /// futures from within it.
+    ///
+    /// The future may have been polled before. If it is already set, the callback may run
+    /// right away on the calling thread.
+    pub fn on_complete<F>(self, callback: F) -> Result<()>
+        where F: FnOnce(Result<PreparedStatement>) + Send + 'static {
+        let (future, slot) = (self.0, self.1.clone());
+        run_when_set(future,
+                     slot,
+                     Box::new(move || {
+                         let mut this = self;
+                         callback(this.error_code())
+                     }))
+    }
+
     /// Gets the error code from future. If the future is not ready this method will
     /// wait for the future to be set.
     pub fn error_code(&mut self) -> Result<PreparedStatement> {
-        unsafe { cass_future_error_code(self.0).to_result(self.get()).chain_err(|| "") }
+        unsafe { error_code(self.0)? };
+        Ok(self.get())
     }
 
     /// Gets the error message from future. If the future is not ready this method will
@@ -234,11 +437,20 @@ impl Protected<*mut _Future> for ConnectFuture {
 impl Drop for ConnectFuture {
     fn drop(&mut self) { unsafe { cass_future_free(self.0) } }
 }
+
+impl ConnectFuture {
+    /// Blocks until the session connects or errors out
+    pub fn wait(&self) -> Result<()> { unsafe { error_code(self.0) } }
+}
 /// The future result of an attempt to create a new Cassandra session
 /// It can be waited on, polled or a callback
 /// can be attached.
+///
+/// It also implements `std::future::Future`, so it can be `.await`ed from an async
+/// executor without blocking a thread.
 #[derive(Debug)]
-pub struct SessionFuture(*mut _Future);
+pub struct SessionFuture(*mut _Future, Option<Arc<WakerSlot>>);
+unsafe impl Send for SessionFuture {}
 
 impl SessionFuture {
     /// blocks until the session connects or errors out
@@ -249,9 +461,22 @@ impl SessionFuture {
         }
     }
 
+    /// Calls `callback` once the session connects or errors out, without blocking.
+    ///
+    /// Important: The callback runs on a driver IO thread. Do not wait on other
+    /// futures from within it.
+    ///
+    /// The future may have been polled before. If it is already set, the callback may run
+    /// right away on the calling thread.
+    pub fn on_complete<F>(self, callback: F) -> Result<()>
+        where F: FnOnce(Result<()>) + Send + 'static {
+        let (future, slot) = (self.0, self.1.clone());
+        run_when_set(future, slot, Box::new(move || callback(self.error_code())))
+    }
+
     /// Gets the error code from future. If the future is not ready this method will
     /// wait for the future to be set.
-    pub fn error_code(&self) -> Result<()> { unsafe { cass_future_error_code(self.0).to_result(()).chain_err(|| "") } }
+    pub fn error_code(&self) -> Result<()> { unsafe { error_code(self.0) } }
 
     /// Gets the result of a successful future. If the future is not ready this method will
     /// wait for the future to be set.
@@ -277,8 +502,12 @@ impl Drop for SessionFuture {
 /// It can represent a result if the operation completed successfully or an
 /// error if the operation failed. It can be waited on, polled or a callback
 /// can be attached.
+///
+/// It also implements `std::future::Future`, so it can be `.await`ed from an async
+/// executor without blocking a thread.
 #[derive(Debug)]
-pub struct CloseFuture(*mut _Future);
+pub struct CloseFuture(*mut _Future, Option<Arc<WakerSlot>>);
+unsafe impl Send for CloseFuture {}
 
 impl Protected<*mut _Future> for Future {
     fn inner(&self) -> *mut _Future { self.0 }
@@ -287,22 +516,22 @@ impl Protected<*mut _Future> for Future {
 
 impl Protected<*mut _Future> for PreparedFuture {
     fn inner(&self) -> *mut _Future { self.0 }
-    fn build(inner: *mut _Future) -> Self { PreparedFuture(inner) }
+    fn build(inner: *mut _Future) -> Self { PreparedFuture(inner, None) }
 }
 
 impl Protected<*mut _Future> for ResultFuture {
     fn inner(&self) -> *mut _Future { self.0 }
-    fn build(inner: *mut _Future) -> Self { ResultFuture(inner) }
+    fn build(inner: *mut _Future) -> Self { ResultFuture(inner, None) }
 }
 
 impl Protected<*mut _Future> for SessionFuture {
     fn inner(&self) -> *mut _Future { self.0 }
-    fn build(inner: *mut _Future) -> Self { SessionFuture(inner) }
+    fn build(inner: *mut _Future) -> Self { SessionFuture(inner, None) }
 }
 
 impl Protected<*mut _Future> for CloseFuture {
     fn inner(&self) -> *mut _Future { self.0 }
-    fn build(inner: *mut _Future) -> Self { CloseFuture(inner) }
+    fn build(inner: *mut _Future) -> Self { CloseFuture(inner, None) }
 }
 
 
@@ -322,11 +551,207 @@ impl CloseFuture {
         }
     }
 
+    /// Calls `callback` once the session has been closed, without blocking.
+    ///
+    /// Important: The callback runs on a driver IO thread. Do not wait on other
+    /// futures from within it.
+    ///
+    /// The future may have been polled before. If it is already set, the callback may run
+    /// right away on the calling thread.
+    pub fn on_complete<F>(self, callback: F) -> Result<()>
+        where F: FnOnce(Result<()>) + Send + 'static {
+        let (future, slot) = (self.0, self.1.clone());
+        run_when_set(future, slot, Box::new(move || callback(self.error_code())))
+    }
+
     /// Gets the error code from future. If the future is not ready this method will
     /// wait for the future to be set.
-    pub fn error_code(&self) -> Result<()> { unsafe { cass_future_error_code(self.0).to_result(()).chain_err(|| "") } }
+    pub fn error_code(&self) -> Result<()> { unsafe { error_code(self.0) } }
 
     /// Gets the error message from future. If the future is not ready this method will
     /// wait for the future to be set.
     pub fn get(&self) -> PreparedStatement { unsafe { PreparedStatement::build(cass_future_get_prepared(self.0)) } }
 }
+
+impl future::Future for ResultFuture {
+    type Output = Result<CassResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let future = self.get_mut();
+        match poll_set(future.0, &mut future.1, cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(future.error_code()),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl future::Future for PreparedFuture {
+    type Output = Result<PreparedStatement>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let future = self.get_mut();
+        match poll_set(future.0, &mut future.1, cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(future.error_code()),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl future::Future for SessionFuture {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let future = self.get_mut();
+        match poll_set(future.0, &mut future.1, cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(future.error_code()),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl future::Future for CloseFuture {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let future = self.get_mut();
+        match poll_set(future.0, &mut future.1, cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(future.error_code()),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A driver future set by hand, taking a single callback like the driver does
+#[cfg(test)]
+#[derive(Default)]
+struct FakeFuture {
+    ready: ::std::cell::Cell<bool>,
+    registered: ::std::cell::Cell<bool>,
+    callback: ::std::cell::Cell<Option<(_CassFutureCallback, *mut raw::c_void)>>,
+}
+
+#[cfg(test)]
+impl FakeFuture {
+    fn set(&self) {
+        self.ready.set(true);
+        if let Some((Some(callback), data)) = self.callback.take() {
+            unsafe { callback(ptr::null_mut(), data) }
+        }
+    }
+}
+
+#[cfg(test)]
+impl RawFuture for &FakeFuture {
+    fn ready(self) -> bool { self.ready.get() }
+
+    fn set_callback(self, callback: _CassFutureCallback, data: *mut raw::c_void) -> _CassError {
+        assert!(!self.registered.replace(true), "the driver takes a single callback per future");
+        self.callback.set(Some((callback, data)));
+        if self.ready.get() {
+            self.set();
+        }
+        CASS_OK
+    }
+}
+
+/// A waker counting how often it was woken
+#[cfg(test)]
+#[derive(Default)]
+struct CountingWaker(::std::sync::atomic::AtomicUsize);
+
+#[cfg(test)]
+impl ::std::task::Wake for CountingWaker {
+    fn wake(self: Arc<Self>) { self.0.fetch_add(1, ::std::sync::atomic::Ordering::SeqCst); }
+}
+
+#[cfg(test)]
+fn poll_fake(future: &FakeFuture, slot: &mut Option<Arc<WakerSlot>>, waker: &Arc<CountingWaker>) -> Poll<Result<()>> {
+    poll_set(future, slot, &mut Context::from_waker(&Waker::from(waker.clone())))
+}
+
+/// A closure counting its runs in `runs`
+#[cfg(test)]
+fn counting_closure(runs: &Arc<::std::sync::atomic::AtomicUsize>) -> Callback {
+    let runs = runs.clone();
+    Box::new(move || {
+        runs.fetch_add(1, ::std::sync::atomic::Ordering::SeqCst);
+    })
+}
+
+#[test]
+fn test_poll_set_hands_waker_to_driver() {
+    use std::sync::atomic::Ordering::SeqCst;
+
+    let future = FakeFuture::default();
+    let (first, second) = (Arc::new(CountingWaker::default()), Arc::new(CountingWaker::default()));
+    let mut slot = None;
+    assert!(poll_fake(&future, &mut slot, &first).is_pending());
+    // polling again only replaces the waker, the fake would fail on a second callback
+    assert!(poll_fake(&future, &mut slot, &second).is_pending());
+    future.set();
+    assert_eq!((first.0.load(SeqCst), second.0.load(SeqCst)), (0, 1));
+    // the driver callback gave back its reference to the slot
+    assert_eq!(Arc::strong_count(slot.as_ref().unwrap()), 1);
+    assert!(matches!(poll_fake(&future, &mut slot, &second), Poll::Ready(Ok(()))));
+
+    // a future already set is ready without a callback
+    let future = FakeFuture::default();
+    future.set();
+    let mut slot = None;
+    assert!(matches!(poll_fake(&future, &mut slot, &first), Poll::Ready(Ok(()))));
+    assert!(slot.is_none() && !future.registered.get());
+}
+
+#[test]
+fn test_set_closure_runs_once_and_contains_panics() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let future = FakeFuture::default();
+    let runs = Arc::new(AtomicUsize::new(0));
+    set_closure(&future, counting_closure(&runs)).unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+    future.set();
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    // the closure was freed after running
+    assert_eq!(Arc::strong_count(&runs), 1);
+
+    let future = FakeFuture::default();
+    let held = runs.clone();
+    set_closure(&future,
+                Box::new(move || {
+                    let _held = held;
+                    panic!("callback panicked")
+                }))
+        .unwrap();
+    future.set();
+    assert_eq!(Arc::strong_count(&runs), 1);
+}
+
+#[test]
+fn test_run_when_set_after_poll() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let task = Arc::new(CountingWaker::default());
+
+    // still pending: the closure takes the place of the task's waker
+    let future = FakeFuture::default();
+    let mut slot = None;
+    assert!(poll_fake(&future, &mut slot, &task).is_pending());
+    run_when_set(&future, slot.clone(), counting_closure(&runs)).unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+    future.set();
+    assert_eq!((runs.load(Ordering::SeqCst), task.0.load(Ordering::SeqCst)), (1, 0));
+
+    // set since the poll: the closure runs right away
+    let future = FakeFuture::default();
+    let mut slot = None;
+    assert!(poll_fake(&future, &mut slot, &task).is_pending());
+    future.set();
+    run_when_set(&future, slot.clone(), counting_closure(&runs)).unwrap();
+    assert_eq!((runs.load(Ordering::SeqCst), task.0.load(Ordering::SeqCst)), (2, 1));
+}
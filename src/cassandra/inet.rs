@@ -1,36 +1,53 @@
 
 
 use cassandra::error::CassError;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
+use cassandra_sys::CASS_ERROR_LIB_BAD_PARAMS;
 use cassandra_sys::CASS_OK;
 use cassandra_sys::CassInet as _Inet;
-use cassandra_sys::cass_inet_from_string;
+use cassandra_sys::cass_inet_from_string_n;
 use cassandra_sys::cass_inet_init_v4;
 use cassandra_sys::cass_inet_init_v6;
-use cassandra_sys::cass_inet_string;
-use errors::*;
+use cassandra::error::*;
 use std::default::Default;
 // use std::ffi::NulError;
-use std::ffi::CStr;
-use std::ffi::CString;
 use std::fmt;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::mem;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::net::SocketAddr;
+use std::os::raw::c_char;
 use std::str::FromStr;
-use std::string::ToString;
 // use cassandra::error::CassLibError;
 
 #[repr(C)]
 /// Cassandra's version of an IP address
-
+#[derive(Clone, Copy)]
 pub struct Inet(_Inet);
 
 impl Debug for Inet {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "can't format an inet") }
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { Display::fmt(self, f) }
+}
+
+impl Display for Inet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let address = self.0.address;
+        match self.0.address_length {
+            4 => write!(f, "{}", Ipv4Addr::new(address[0], address[1], address[2], address[3])),
+            _ => write!(f, "{}", Ipv6Addr::from(address)),
+        }
+    }
 }
 
+impl PartialEq for Inet {
+    fn eq(&self, other: &Inet) -> bool {
+        let length = self.0.address_length as usize;
+        length == other.0.address_length as usize && self.0.address[..length] == other.0.address[..length]
+    }
+}
+
+impl Eq for Inet {}
+
 impl Protected<_Inet> for Inet {
     fn inner(&self) -> _Inet { self.0 }
     fn build(inner: _Inet) -> Self { Inet(inner) }
@@ -78,58 +95,42 @@ impl FromStr for Inet {
         unsafe {
             let mut inet = mem::zeroed();
 
-            let str = CString::new(s).expect("must be utf8");
-            match cass_inet_from_string(str.as_ptr(), &mut inet) {
+            match cass_inet_from_string_n(s.as_ptr() as *const c_char, s.len(), &mut inet) {
                 CASS_OK => Ok(Inet(inet)),
-                err => err.to_result(Inet(inet)).chain_err(|| ""),
+                err => err.wrap(Inet(inet)),
             }
         }
     }
 }
 
-impl ToString for Inet {
-    fn to_string(&self) -> String {
-        unsafe {
-            let mut inet_str = mem::zeroed();
-            cass_inet_string(self.0, &mut inet_str);
-            CStr::from_ptr(&inet_str).to_string_lossy().into_owned()
-        }
-    }
+/// Converts from an Cassandra Inet address
+pub trait FromInet: Sized {
+    /// Converts from an Cassandra Inet address, failing if it is of the other IP version
+    fn from_cass_inet(inet: Inet) -> Result<Self>;
 }
 
-/// Converts from an Cassandra Inet address
-pub trait FromInet {
-    /// Converts from an Cassandra Inet address
-    fn from_cass_inet(inet: Inet) -> Self;
+/// The error returned when an address is not of the IP version asked for
+fn wrong_version(inet: Inet, expected: &str) -> Error {
+    CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                            format!("{} is not an {} address", inet, expected))
+        .into()
 }
 
 impl FromInet for Ipv4Addr {
-    fn from_cass_inet(inet: Inet) -> Self {
+    fn from_cass_inet(inet: Inet) -> Result<Self> {
         let raw_addr: [u8; 16] = inet.0.address;
         match inet.0.address_length {
-            4 => Ipv4Addr::new(raw_addr[0], raw_addr[1], raw_addr[2], raw_addr[3]),
-            16 => panic!(),
-            unsupported => panic!("impossible inet type: {:?}", unsupported),
+            4 => Ok(Ipv4Addr::new(raw_addr[0], raw_addr[1], raw_addr[2], raw_addr[3])),
+            _ => Err(wrong_version(inet, "IPv4")),
         }
     }
 }
 
 impl FromInet for Ipv6Addr {
-    fn from_cass_inet(inet: Inet) -> Self {
-        let raw_addr: [u8; 16] = inet.0.address;
+    fn from_cass_inet(inet: Inet) -> Result<Self> {
         match inet.0.address_length {
-            4 => panic!(),
-            16 => {
-                Ipv6Addr::new((raw_addr[1] as u16) << (8 + raw_addr[0] as u16),
-                              (raw_addr[3] as u16) << (8 + raw_addr[2] as u16),
-                              (raw_addr[5] as u16) << (8 + raw_addr[4] as u16),
-                              (raw_addr[7] as u16) << (8 + raw_addr[6] as u16),
-                              (raw_addr[9] as u16) << (8 + raw_addr[8] as u16),
-                              (raw_addr[11] as u16) << (8 + raw_addr[10] as u16),
-                              (raw_addr[13] as u16) << (8 + raw_addr[12] as u16),
-                              (raw_addr[15] as u16) << (8 + raw_addr[14] as u16))
-            }
-            unsupported => panic!("impossible inet type: {}", unsupported),
+            16 => Ok(Ipv6Addr::from(inet.0.address)),
+            _ => Err(wrong_version(inet, "IPv6")),
         }
     }
 }
@@ -145,3 +146,20 @@ impl Inet {
         unsafe { Inet(cass_inet_init_v6(address.segments().as_ptr() as *const u8)) }
     }
 }
+
+#[test]
+fn test_from_cass_inet() {
+    let mut v4 = Inet::default();
+    v4.0.address[..4].copy_from_slice(&[10, 0, 0, 1]);
+    v4.0.address_length = 4;
+    let v6 = Inet(_Inet {
+        address: "2001:db8::1".parse::<Ipv6Addr>().unwrap().octets(),
+        address_length: 16,
+    });
+
+    assert_eq!(Ipv4Addr::from_cass_inet(v4).unwrap(), Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(Ipv6Addr::from_cass_inet(v6).unwrap(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+    assert!(Ipv4Addr::from_cass_inet(v6).is_err());
+    assert!(Ipv6Addr::from_cass_inet(v4).is_err());
+    assert!(Ipv4Addr::from_cass_inet(Inet::default()).is_err());
+}
@@ -8,7 +8,6 @@ use cassandra::schema::table_meta::TableMeta;
 use cassandra::util::Protected;
 use cassandra::value::Value;
 
-use cassandra_sys::CASS_OK;
 
 // use cassandra_sys::CassIteratorType as _CassIteratorType;
 use cassandra_sys::CassIterator as _CassIterator;
@@ -25,10 +24,72 @@ use cassandra_sys::cass_iterator_get_meta_field_name;
 use cassandra_sys::cass_iterator_get_meta_field_value;
 use cassandra_sys::cass_iterator_get_table_meta;
 use cassandra_sys::cass_iterator_get_user_type;
+use cassandra_sys::cass_iterator_get_user_type_field_name;
+use cassandra_sys::cass_iterator_get_user_type_field_value;
 use cassandra_sys::cass_iterator_get_value;
 use cassandra_sys::cass_iterator_next;
 use cassandra_sys::cass_true;
-use std::{mem, slice, str};
+use std::{ptr, slice};
+use std::marker::PhantomData;
+
+/// An iterator whose items borrow the iterator itself.
+///
+/// The driver decodes each row of a result, and each item of a collection, tuple or user
+/// type, in place inside the iterator, so advancing it overwrites the previous item. Items
+/// therefore have to be dropped before the next one is fetched, and are read in a
+/// `while let` loop rather than a `for` loop:
+///
+/// ```ignore
+/// let mut rows = result.iter();
+/// while let Some(row) = rows.next() {
+///     let id: i32 = row.get(0)?;
+/// }
+/// ```
+///
+/// `map` turns the items into values that own their data, which can then be collected:
+///
+/// ```ignore
+/// let ids: Vec<i32> = result.iter().map(|row| row.get(0)).collect::<Result<_>>()?;
+/// ```
+pub trait LendingIterator: for<'i> LendingItem<'i> {
+    /// Advances the iterator and returns the next item, or `None` once it is exhausted
+    fn next(&mut self) -> Option<<Self as LendingItem<'_>>::Item>;
+
+    /// Converts each item with `f`, yielding the results as a standard `Iterator`
+    fn map<F, T>(self, f: F) -> LendingMap<Self, F>
+        where Self: Sized,
+              F: for<'i> FnMut(<Self as LendingItem<'i>>::Item) -> T {
+        LendingMap {
+            iter: self,
+            f,
+        }
+    }
+}
+
+/// The type of the items a `LendingIterator` lends out for `'i`.
+///
+/// The `&'i Self` parameter is never set; it only restricts `'i` to lifetimes the iterator
+/// outlives.
+pub trait LendingItem<'i, Bound = &'i Self> {
+    /// The type of the items, borrowed from the iterator for `'i`
+    type Item;
+}
+
+/// A standard `Iterator` over the items of a `LendingIterator`, each converted by a closure.
+/// Created by `LendingIterator::map`.
+#[derive(Debug)]
+pub struct LendingMap<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F, T> Iterator for LendingMap<I, F>
+    where I: LendingIterator,
+          F: for<'i> FnMut(<I as LendingItem<'i>>::Item) -> T {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.iter.next().map(&mut self.f) }
+}
 
 /// Iterates over the  aggregate metadata entries(??)
 #[derive(Debug)]
@@ -73,11 +134,50 @@ impl Iterator for UserTypeIterator {
     }
 }
 
-impl UserTypeIterator {
-    //    pub fn get_field_name(&mut self)-> Value {unsafe{
-    //
-    //        Value::new(cass_iterator_get_user_type_field_name(self.0))
-    //    }}
+/// An iterator over the fields of a user defined type value, yielding each field's
+/// name along with its value
+#[derive(Debug)]
+pub struct UserTypeFieldIterator<'a>(*mut _CassIterator, PhantomData<Value<'a>>);
+
+impl<'a> Drop for UserTypeFieldIterator<'a> {
+    fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
+}
+
+impl<'i, 'a> LendingItem<'i> for UserTypeFieldIterator<'a> {
+    type Item = Field<'i>;
+}
+
+impl<'a> LendingIterator for UserTypeFieldIterator<'a> {
+    fn next(&mut self) -> Option<Field<'_>> {
+        unsafe {
+            match cass_iterator_next(self.0) {
+                cass_false => None,
+                cass_true => {
+                    Some(Field {
+                        name: self.get_field_name(),
+                        value: self.get_field_value(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl<'a> UserTypeFieldIterator<'a> {
+    /// Gets the name of the current field
+    pub fn get_field_name(&mut self) -> String {
+        unsafe {
+            let mut name = ptr::null();
+            let mut name_length = 0;
+            cass_iterator_get_user_type_field_name(self.0, &mut name, &mut name_length);
+            String::from_utf8_lossy(slice::from_raw_parts(name as *const u8, name_length)).into_owned()
+        }
+    }
+
+    /// Gets the value of the current field
+    pub fn get_field_value(&mut self) -> Value<'_> {
+        unsafe { Value::build(cass_iterator_get_user_type_field_value(self.0)) }
+    }
 }
 
 
@@ -148,28 +248,23 @@ impl Iterator for ColumnIterator {
 
 /// Iterater over the field's metadata entries(??)
 #[derive(Debug)]
-pub struct FieldIterator(*mut _CassIterator);
+pub struct FieldIterator<'a>(*mut _CassIterator, PhantomData<Value<'a>>);
 
-impl Iterator for FieldIterator {
-    type Item = Field;
-    #[allow(cast_possible_truncation)]
+impl<'a> Iterator for FieldIterator<'a> {
+    type Item = Field<'a>;
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
         unsafe {
             match cass_iterator_next(self.0) {
                 cass_false => None,
                 cass_true => {
-                    let mut name = mem::zeroed();
-                    let mut name_length = mem::zeroed();
-                    match cass_iterator_get_meta_field_name(self.0, &mut name, &mut name_length) {
-                        CASS_OK => {
-                            let slice = slice::from_raw_parts(name as *const u8, name_length as usize);
-                            Some(Field {
-                                name: str::from_utf8(slice).expect("must be utf8").to_owned(),
-                                value: Value::build(cass_iterator_get_meta_field_value(self.0)),
-                            })
-                        }
-                        err => panic!("FIXME: no error handling. Err {:?}", err),
-                    }
+                    let mut name = ptr::null();
+                    let mut name_length = 0;
+                    cass_iterator_get_meta_field_name(self.0, &mut name, &mut name_length);
+                    Some(Field {
+                        name: String::from_utf8_lossy(slice::from_raw_parts(name as *const u8, name_length))
+                            .into_owned(),
+                        value: Value::build(cass_iterator_get_meta_field_value(self.0)),
+                    })
                 }
             }
         }
@@ -197,6 +292,16 @@ impl Protected<*mut _CassIterator> for UserTypeIterator {
     fn build(inner: *mut _CassIterator) -> Self { UserTypeIterator(inner) }
 }
 
+impl<'a> Protected<*mut _CassIterator> for UserTypeFieldIterator<'a> {
+    fn inner(&self) -> *mut _CassIterator { self.0 }
+    fn build(inner: *mut _CassIterator) -> Self { UserTypeFieldIterator(inner, PhantomData) }
+}
+
+impl<'a> Protected<*mut _CassIterator> for TupleIterator<'a> {
+    fn inner(&self) -> *mut _CassIterator { self.0 }
+    fn build(inner: *mut _CassIterator) -> Self { TupleIterator(inner, PhantomData) }
+}
+
 impl Protected<*mut _CassIterator> for AggregateIterator {
     fn inner(&self) -> *mut _CassIterator { self.0 }
     fn build(inner: *mut _CassIterator) -> Self { AggregateIterator(inner) }
@@ -212,9 +317,9 @@ impl Protected<*mut _CassIterator> for KeyspaceIterator {
     fn build(inner: *mut _CassIterator) -> Self { KeyspaceIterator(inner) }
 }
 
-impl Protected<*mut _CassIterator> for FieldIterator {
+impl<'a> Protected<*mut _CassIterator> for FieldIterator<'a> {
     fn inner(&self) -> *mut _CassIterator { self.0 }
-    fn build(inner: *mut _CassIterator) -> Self { FieldIterator(inner) }
+    fn build(inner: *mut _CassIterator) -> Self { FieldIterator(inner, PhantomData) }
 }
 
 impl Protected<*mut _CassIterator> for ColumnIterator {
@@ -227,20 +332,20 @@ impl Protected<*mut _CassIterator> for TableIterator {
     fn build(inner: *mut _CassIterator) -> Self { TableIterator(inner) }
 }
 
-impl Protected<*mut _CassIterator> for MapIterator {
+impl<'a> Protected<*mut _CassIterator> for MapIterator<'a> {
     fn inner(&self) -> *mut _CassIterator { self.0 }
-    fn build(inner: *mut _CassIterator) -> Self { MapIterator(inner) }
+    fn build(inner: *mut _CassIterator) -> Self { MapIterator(inner, PhantomData) }
 }
 
-impl Protected<*mut _CassIterator> for SetIterator {
+impl<'a> Protected<*mut _CassIterator> for SetIterator<'a> {
     fn inner(&self) -> *mut _CassIterator { self.0 }
-    fn build(inner: *mut _CassIterator) -> Self { SetIterator(inner) }
+    fn build(inner: *mut _CassIterator) -> Self { SetIterator(inner, PhantomData) }
 }
 
 
 /// Iterater over the set's metadata entries(??)
 #[derive(Debug)]
-pub struct SetIterator(*mut _CassIterator);
+pub struct SetIterator<'a>(*mut _CassIterator, PhantomData<Value<'a>>);
 
 // impl<'a> Display for &'a SetIterator {
 //    fn fmt(&self, f:&mut Formatter) -> fmt::Result {
@@ -251,15 +356,17 @@ pub struct SetIterator(*mut _CassIterator);
 //    }
 // }
 
-impl Drop for SetIterator {
+impl<'a> Drop for SetIterator<'a> {
     fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
 }
 
 
-impl Iterator for SetIterator {
-    type Item = Value;
+impl<'i, 'a> LendingItem<'i> for SetIterator<'a> {
+    type Item = Value<'i>;
+}
 
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+impl<'a> LendingIterator for SetIterator<'a> {
+    fn next(&mut self) -> Option<Value<'_>> {
         unsafe {
             match cass_iterator_next(self.0) {
                 cass_false => None,
@@ -269,33 +376,36 @@ impl Iterator for SetIterator {
     }
 }
 
-impl SetIterator {
-    fn get_value(&mut self) -> Value { unsafe { Value::build(cass_iterator_get_value(self.0)) } }
+impl<'a> SetIterator<'a> {
+    fn get_value(&mut self) -> Value<'_> { unsafe { Value::build(cass_iterator_get_value(self.0)) } }
 }
 
 /// An iterator over the k/v pair in the map
 #[derive(Debug)]
-pub struct MapIterator(*mut _CassIterator);
+pub struct MapIterator<'a>(*mut _CassIterator, PhantomData<Value<'a>>);
 
-impl MapIterator {
-    fn get_key(&mut self) -> Value { unsafe { Value::build(cass_iterator_get_map_key(self.0)) } }
-    fn get_value(&mut self) -> Value { unsafe { Value::build(cass_iterator_get_map_value(self.0)) } }
+impl<'a> MapIterator<'a> {
+    fn get_key(&self) -> Value<'_> { unsafe { Value::build(cass_iterator_get_map_key(self.0)) } }
+    fn get_value(&self) -> Value<'_> { unsafe { Value::build(cass_iterator_get_map_value(self.0)) } }
 
     /// Gets the next k/v pair in the map
-    pub fn get_pair(&mut self) -> (Value, Value) { (self.get_key(), self.get_value()) }
+    pub fn get_pair(&mut self) -> (Value<'_>, Value<'_>) { (self.get_key(), self.get_value()) }
 }
 
 /// An iterator over the elements of a Cassandra tuple
 #[derive(Debug)]
-pub struct TupleIterator(pub *mut _CassIterator);
+pub struct TupleIterator<'a>(pub *mut _CassIterator, PhantomData<Value<'a>>);
 
-impl Drop for TupleIterator {
+impl<'a> Drop for TupleIterator<'a> {
     fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
 }
 
-impl Iterator for TupleIterator {
-    type Item = Value;
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+impl<'i, 'a> LendingItem<'i> for TupleIterator<'a> {
+    type Item = Value<'i>;
+}
+
+impl<'a> LendingIterator for TupleIterator<'a> {
+    fn next(&mut self) -> Option<Value<'_>> {
         unsafe {
             match cass_iterator_next(self.0) {
                 cass_false => None,
@@ -305,19 +415,22 @@ impl Iterator for TupleIterator {
     }
 }
 
-impl TupleIterator {
-    fn get_value(&mut self) -> Value { unsafe { Value::build(cass_iterator_get_value(self.0)) } }
+impl<'a> TupleIterator<'a> {
+    fn get_value(&mut self) -> Value<'_> { unsafe { Value::build(cass_iterator_get_value(self.0)) } }
 }
 
 
 
-impl Drop for MapIterator {
+impl<'a> Drop for MapIterator<'a> {
     fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
 }
 
-impl Iterator for MapIterator {
-    type Item = (Value, Value);
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+impl<'i, 'a> LendingItem<'i> for MapIterator<'a> {
+    type Item = (Value<'i>, Value<'i>);
+}
+
+impl<'a> LendingIterator for MapIterator<'a> {
+    fn next(&mut self) -> Option<(Value<'_>, Value<'_>)> {
         unsafe {
             match cass_iterator_next(self.0) {
                 cass_false => None,
@@ -1,12 +1,228 @@
-use cassandra::util::Protected;
 use cassandra_sys::CassMetrics as _CassMetrics;
 
-/// A view into server metrics FIXME not meaingfully implemented
-#[derive(Debug)]
-pub struct SessionMetrics(*const _CassMetrics);
+use std::fmt::Write;
 
+/// A snapshot of a session's performance/diagnostic metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SessionMetrics {
+    /// Latency and throughput of the session's requests
+    pub requests: RequestMetrics,
+    /// The state of the session's connections
+    pub stats: ConnectionStats,
+    /// Timeouts seen by the session
+    pub errors: TimeoutMetrics,
+    /// Speculative executions made by `Session::execute_speculative`
+    pub speculative_executions: SpeculativeExecutionMetrics,
+}
+
+/// The request latency histogram, in microseconds, and request rates, in requests per second
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestMetrics {
+    /// Minimum in microseconds
+    pub min: u64,
+    /// Maximum in microseconds
+    pub max: u64,
+    /// Mean in microseconds
+    pub mean: u64,
+    /// Standard deviation in microseconds
+    pub stddev: u64,
+    /// Median in microseconds
+    pub median: u64,
+    /// 75th percentile in microseconds
+    pub percentile_75th: u64,
+    /// 95th percentile in microseconds
+    pub percentile_95th: u64,
+    /// 98th percentile in microseconds
+    pub percentile_98th: u64,
+    /// 99th percentile in microseconds
+    pub percentile_99th: u64,
+    /// 99.9th percentile in microseconds
+    pub percentile_999th: u64,
+    /// Mean rate in requests per second
+    pub mean_rate: f64,
+    /// 1 minute rate in requests per second
+    pub one_minute_rate: f64,
+    /// 5 minute rate in requests per second
+    pub five_minute_rate: f64,
+    /// 15 minute rate in requests per second
+    pub fifteen_minute_rate: f64,
+}
+
+/// Connection statistics of a session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConnectionStats {
+    /// The total number of connections
+    pub total_connections: u64,
+    /// The number of connections available to take requests
+    pub available_connections: u64,
+    /// Occurrences when requests exceeded a pool's water mark
+    pub exceeded_pending_requests_water_mark: u64,
+    /// Occurrences when number of bytes exceeded a connection's water mark
+    pub exceeded_write_bytes_water_mark: u64,
+}
+
+/// Timeout counts of a session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeoutMetrics {
+    /// Occurrences of a connection timeout
+    pub connection_timeouts: u64,
+    /// Occurrences of requests that timed out waiting for a connection
+    pub pending_request_timeouts: u64,
+    /// Occurrences of requests that timed out waiting for a request to finish
+    pub request_timeouts: u64,
+}
+
+/// Speculative execution counts of a session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpeculativeExecutionMetrics {
+    /// Executions started after the first execution of a statement
+    pub fired: u64,
+    /// Statements answered by an execution other than the first one
+    pub won: u64,
+}
+
+impl From<_CassMetrics> for SessionMetrics {
+    fn from(metrics: _CassMetrics) -> Self {
+        let requests = metrics.requests;
+        let stats = metrics.stats;
+        let errors = metrics.errors;
+        SessionMetrics {
+            requests: RequestMetrics {
+                min: requests.min,
+                max: requests.max,
+                mean: requests.mean,
+                stddev: requests.stddev,
+                median: requests.median,
+                percentile_75th: requests.percentile_75th,
+                percentile_95th: requests.percentile_95th,
+                percentile_98th: requests.percentile_98th,
+                percentile_99th: requests.percentile_99th,
+                percentile_999th: requests.percentile_999th,
+                mean_rate: requests.mean_rate,
+                one_minute_rate: requests.one_minute_rate,
+                five_minute_rate: requests.five_minute_rate,
+                fifteen_minute_rate: requests.fifteen_minute_rate,
+            },
+            stats: ConnectionStats {
+                total_connections: stats.total_connections,
+                available_connections: stats.available_connections,
+                exceeded_pending_requests_water_mark: stats.exceeded_pending_requests_water_mark,
+                exceeded_write_bytes_water_mark: stats.exceeded_write_bytes_water_mark,
+            },
+            errors: TimeoutMetrics {
+                connection_timeouts: errors.connection_timeouts,
+                pending_request_timeouts: errors.pending_request_timeouts,
+                request_timeouts: errors.request_timeouts,
+            },
+            speculative_executions: SpeculativeExecutionMetrics::default(),
+        }
+    }
+}
+
+impl SessionMetrics {
+    /// Renders the metrics in the Prometheus text exposition format, with every metric
+    /// name prefixed by `cassandra_`
+    pub fn to_prometheus(&self) -> String {
+        let requests = &self.requests;
+        let mut out = String::new();
+
+        // the driver reports no latency sum or count, which a summary may leave out
+        header(&mut out,
+               "request_latency_microseconds",
+               "summary",
+               "Request latency percentiles in microseconds.");
+        for &(quantile, value) in &[("0.5", requests.median),
+                                    ("0.75", requests.percentile_75th),
+                                    ("0.95", requests.percentile_95th),
+                                    ("0.98", requests.percentile_98th),
+                                    ("0.99", requests.percentile_99th),
+                                    ("0.999", requests.percentile_999th)] {
+            let _ = writeln!(out,
+                             "cassandra_request_latency_microseconds{{quantile=\"{}\"}} {}",
+                             quantile,
+                             value);
+        }
+        gauge(&mut out, "request_latency_min_microseconds", "Minimum request latency in microseconds.", requests.min);
+        gauge(&mut out, "request_latency_max_microseconds", "Maximum request latency in microseconds.", requests.max);
+        gauge(&mut out, "request_latency_mean_microseconds", "Mean request latency in microseconds.", requests.mean);
+        gauge(&mut out,
+              "request_latency_stddev_microseconds",
+              "Standard deviation of request latency in microseconds.",
+              requests.stddev);
+
+        header(&mut out, "requests_per_second", "gauge", "Request rate in requests per second.");
+        for &(window, value) in &[("mean", requests.mean_rate),
+                                  ("1m", requests.one_minute_rate),
+                                  ("5m", requests.five_minute_rate),
+                                  ("15m", requests.fifteen_minute_rate)] {
+            let _ = writeln!(out, "cassandra_requests_per_second{{window=\"{}\"}} {}", window, value);
+        }
+
+        gauge(&mut out, "connections", "Total number of connections.", self.stats.total_connections);
+        gauge(&mut out,
+              "available_connections",
+              "Number of connections available to take requests.",
+              self.stats.available_connections);
+        counter(&mut out,
+                "exceeded_pending_requests_water_mark_total",
+                "Occurrences when requests exceeded a pool's water mark.",
+                self.stats.exceeded_pending_requests_water_mark);
+        counter(&mut out,
+                "exceeded_write_bytes_water_mark_total",
+                "Occurrences when number of bytes exceeded a connection's water mark.",
+                self.stats.exceeded_write_bytes_water_mark);
+
+        counter(&mut out,
+                "connection_timeouts_total",
+                "Occurrences of a connection timeout.",
+                self.errors.connection_timeouts);
+        counter(&mut out,
+                "pending_request_timeouts_total",
+                "Occurrences of requests that timed out waiting for a connection.",
+                self.errors.pending_request_timeouts);
+        counter(&mut out,
+                "request_timeouts_total",
+                "Occurrences of requests that timed out waiting for a request to finish.",
+                self.errors.request_timeouts);
+
+        counter(&mut out,
+                "speculative_executions_fired_total",
+                "Executions started after the first execution of a statement.",
+                self.speculative_executions.fired);
+        counter(&mut out,
+                "speculative_executions_won_total",
+                "Statements answered by an execution other than the first one.",
+                self.speculative_executions.won);
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP cassandra_{} {}", name, help);
+    let _ = writeln!(out, "# TYPE cassandra_{} {}", name, kind);
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: u64) {
+    header(out, name, "gauge", help);
+    let _ = writeln!(out, "cassandra_{} {}", name, value);
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    header(out, name, "counter", help);
+    let _ = writeln!(out, "cassandra_{} {}", name, value);
+}
 
-impl Protected<*const _CassMetrics> for SessionMetrics {
-    fn inner(&self) -> *const _CassMetrics { self.0 }
-    fn build(inner: *const _CassMetrics) -> Self { SessionMetrics(inner) }
+#[test]
+fn test_metrics_to_prometheus() {
+    let mut metrics = SessionMetrics::default();
+    metrics.requests.percentile_99th = 1500;
+    metrics.requests.one_minute_rate = 2.5;
+    metrics.errors.request_timeouts = 3;
+    metrics.speculative_executions.won = 4;
+    let text = metrics.to_prometheus();
+    assert!(text.contains("# TYPE cassandra_request_latency_microseconds summary\n"));
+    assert!(text.contains("cassandra_request_latency_microseconds{quantile=\"0.99\"} 1500\n"));
+    assert!(text.contains("cassandra_requests_per_second{window=\"1m\"} 2.5\n"));
+    assert!(text.contains("# TYPE cassandra_request_timeouts_total counter\ncassandra_request_timeouts_total 3\n"));
+    assert!(text.contains("cassandra_speculative_executions_won_total 4\n"));
 }
@@ -0,0 +1,168 @@
+use cassandra::error::*;
+use cassandra_sys::CASS_ERROR_LIB_INVALID_DATA;
+use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
+
+use decimal::d128;
+use num::{BigInt, Signed};
+use std::str::FromStr;
+
+/// The number of significant digits a `d128` holds without rounding
+const D128_DIGITS: usize = 34;
+
+/// Splits a decimal into the unscaled varint and the scale of its CQL encoding,
+/// where the value is `unscaled * 10^-scale`.
+pub fn encode_decimal(value: d128) -> Result<(Vec<u8>, i32)> {
+    if !value.is_finite() {
+        return Err(CassError::with_message(CASS_ERROR_LIB_INVALID_VALUE_TYPE,
+                                           format!("cannot bind the non-finite decimal {}", value))
+            .into());
+    }
+    let (unscaled, scale) = split_decimal(&value.to_string())?;
+    Ok((encode_varint(&unscaled), scale))
+}
+
+/// Fails with the given message about the decimal `text`
+fn invalid_decimal<T>(message: &str, text: &str) -> Result<T> {
+    Err(CassError::with_message(CASS_ERROR_LIB_INVALID_DATA, format!("{} in decimal {}", message, text)).into())
+}
+
+/// Splits the plain or scientific rendering of a finite decimal, e.g. "-12.50" or "1.25E+7",
+/// into its unscaled digits and scale
+fn split_decimal(text: &str) -> Result<(BigInt, i32)> {
+    let (mantissa, exponent) = match text.find(['E', 'e']) {
+        Some(pos) => {
+            match i64::from_str(&text[pos + 1..]) {
+                Ok(exponent) => (&text[..pos], exponent),
+                Err(_) => return invalid_decimal("bad exponent", text),
+            }
+        }
+        None => (text, 0),
+    };
+    let (digits, fraction_len) = match mantissa.find('.') {
+        Some(pos) => (format!("{}{}", &mantissa[..pos], &mantissa[pos + 1..]), mantissa.len() - pos - 1),
+        None => (mantissa.to_owned(), 0),
+    };
+    match BigInt::from_str(&digits) {
+        Ok(unscaled) => Ok((unscaled, (fraction_len as i64 - exponent) as i32)),
+        Err(_) => invalid_decimal("bad digits", text),
+    }
+}
+
+/// Rebuilds a decimal from the unscaled varint and scale of its CQL encoding, failing if
+/// the value cannot be held by a `d128` exactly.
+pub fn decode_decimal(varint: &[u8], scale: i32) -> Result<d128> {
+    let digits = BigInt::from_signed_bytes_be(varint).to_string();
+    if digits.trim_start_matches('-').len() > D128_DIGITS {
+        return Err(CassError::with_message(CASS_ERROR_LIB_INVALID_DATA,
+                                           format!("decimal {}E{} exceeds the precision of d128",
+                                                   digits,
+                                                   -(scale as i64)))
+            .into());
+    }
+    let text = format!("{}E{}", digits, -(scale as i64));
+    let value = match d128::from_str(&text) {
+        Ok(value) => value,
+        Err(()) => return invalid_decimal("bad digits", &text),
+    };
+    if !value.is_finite() {
+        return Err(CassError::with_message(CASS_ERROR_LIB_INVALID_DATA,
+                                           format!("decimal {}E{} exceeds the range of d128",
+                                                   digits,
+                                                   -(scale as i64)))
+            .into());
+    }
+    Ok(value)
+}
+
+/// Writes a decimal the way Cassandra's `BigDecimal` does: in plain notation, e.g. "12.50",
+/// unless the scale is negative or the value is smaller than 10^-6, e.g. "1.25E+7".
+#[cfg(feature = "serde")]
+pub fn format_decimal(unscaled: &BigInt, scale: i32) -> String {
+    let digits = unscaled.abs().to_string();
+    let sign = if unscaled.is_negative() { "-" } else { "" };
+    let adjusted = digits.len() as i64 - 1 - scale as i64;
+    if scale >= 0 && adjusted >= -6 {
+        let scale = scale as usize;
+        if scale == 0 {
+            format!("{}{}", sign, digits)
+        } else if digits.len() > scale {
+            let (whole, fraction) = digits.split_at(digits.len() - scale);
+            format!("{}{}.{}", sign, whole, fraction)
+        } else {
+            format!("{}0.{}{}", sign, "0".repeat(scale - digits.len()), digits)
+        }
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        format!("{}{}{}{}E{}{}", sign, first, point, rest, if adjusted >= 0 { "+" } else { "" }, adjusted)
+    }
+}
+
+/// Decodes the two's complement, big-endian bytes of a "varint"
+pub fn decode_varint(varint: &[u8]) -> BigInt { BigInt::from_signed_bytes_be(varint) }
+
+/// Encodes a "varint" as two's complement, big-endian bytes
+pub fn encode_varint(value: &BigInt) -> Vec<u8> {
+    let mut bytes = value.to_signed_bytes_be();
+    // num-bigint 0.1 leaves out the sign byte of positive values like 128 or 32768
+    if !value.is_negative() && bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+        bytes.insert(0, 0);
+    }
+    bytes
+}
+
+#[test]
+fn test_split_decimal() {
+    assert_eq!(split_decimal("0").unwrap(), (BigInt::from(0), 0));
+    assert_eq!(split_decimal("-12.50").unwrap(), (BigInt::from(-1250), 2));
+    assert_eq!(split_decimal("1.25E+7").unwrap(), (BigInt::from(125), -5));
+    assert_eq!(split_decimal("0.000001").unwrap(), (BigInt::from(1), 6));
+    assert_eq!(split_decimal("-5E-10").unwrap(), (BigInt::from(-5), 10));
+    assert!(split_decimal("1.5E+x").is_err());
+    assert!(split_decimal("1.x").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_format_decimal() {
+    assert_eq!(format_decimal(&BigInt::from(0), 0), "0");
+    assert_eq!(format_decimal(&BigInt::from(-1250), 2), "-12.50");
+    assert_eq!(format_decimal(&BigInt::from(5), 3), "0.005");
+    assert_eq!(format_decimal(&BigInt::from(1), 6), "0.000001");
+    assert_eq!(format_decimal(&BigInt::from(-5), 10), "-5E-10");
+    assert_eq!(format_decimal(&BigInt::from(125), -5), "1.25E+7");
+    for &(unscaled, scale) in &[(-1250i64, 2), (1, 6), (-5, 10), (125, -5), (7, 0)] {
+        let unscaled = BigInt::from(unscaled);
+        assert_eq!(split_decimal(&format_decimal(&unscaled, scale)).unwrap(), (unscaled, scale));
+    }
+}
+
+#[test]
+fn test_varint_round_trip() {
+    for value in &[0i64, 1, -1, 127, 128, -128, -129, i64::MAX, i64::MIN] {
+        let value = BigInt::from(*value);
+        assert_eq!(decode_varint(&encode_varint(&value)), value);
+    }
+    assert_eq!(encode_varint(&BigInt::from(128)), vec![0x00, 0x80]);
+    assert_eq!(encode_varint(&BigInt::from(-128)), vec![0x80]);
+}
+
+#[test]
+fn test_decode_decimal_precision() { assert!(decode_decimal(&[0x7f; 16], 0).is_err()); }
+
+#[test]
+fn test_decimal_round_trip() {
+    for &(unscaled, scale, text) in &[(0i64, 0, "0"),
+                                      (15, 1, "1.5"),
+                                      (-1250, 2, "-12.50"),
+                                      (125, -5, "1.25E+7"),
+                                      (-5, 10, "-5E-10"),
+                                      (i64::MAX, 3, "9223372036854775.807")] {
+        let value = decode_decimal(&encode_varint(&BigInt::from(unscaled)), scale).unwrap();
+        assert_eq!(value.to_string(), text);
+        let (varint, encoded_scale) = encode_decimal(value).unwrap();
+        assert_eq!((decode_varint(&varint), encoded_scale), (BigInt::from(unscaled), scale));
+    }
+    assert!(encode_decimal(d128::from_str("NaN").unwrap()).is_err());
+    assert!(decode_decimal(&encode_varint(&BigInt::from(1)), -7000).is_err());
+}
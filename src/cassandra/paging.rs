@@ -0,0 +1,290 @@
+use cassandra::error::*;
+use cassandra::future::{ResultFuture, block_on};
+use cassandra::iterator::LendingIterator;
+use cassandra::result::CassResult;
+use cassandra::row::FromRow;
+use cassandra::session::Session;
+use cassandra::statement::Statement;
+use cassandra::util::{CassErrorExt, Protected};
+
+use cassandra_sys::cass_statement_set_paging_state;
+use futures_core::Stream;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::vec;
+
+/// The result pages of a statement, fetched one at a time as they are consumed.
+///
+/// Returned by `Session::execute_paged`, which takes the statement over so its paging state
+/// can be advanced along the way. Each page is requested only once the previous one has been
+/// handed out. Pages can be pulled with a blocking `for` loop, or as a `Stream` from an async
+/// executor; `rows` flattens them into the rows they hold.
+#[derive(Debug)]
+pub struct ResultPages<'a> {
+    session: &'a Session,
+    statement: Statement,
+    pager: Pager<ResultFuture>,
+}
+
+impl<'a> ResultPages<'a> {
+    pub(crate) fn new(session: &'a Session, statement: Statement) -> Self {
+        ResultPages {
+            session,
+            statement,
+            pager: Pager::new(),
+        }
+    }
+
+    /// Iterates over the rows of every page, each mapped into `T`.
+    ///
+    /// ```ignore
+    /// for user in session.execute_paged(statement).rows::<User>() {
+    ///     println!("{:?}", user?);
+    /// }
+    /// ```
+    pub fn rows<T: FromRow>(self) -> PagedRows<'a, T> {
+        PagedRows {
+            pages: self,
+            rows: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for ResultPages<'a> {
+    type Item = Result<CassResult>;
+
+    fn next(&mut self) -> Option<Self::Item> { block_on(future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))) }
+}
+
+impl<'a> Stream for ResultPages<'a> {
+    type Item = Result<CassResult>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let (session, statement) = (this.session, &this.statement);
+        this.pager.poll_page(cx,
+                             || session.execute(statement),
+                             |result| {
+            if !result.has_more_pages() {
+                return Ok(false);
+            }
+            unsafe { cass_statement_set_paging_state(statement.inner(), result.inner()).wrap(true) }
+        })
+    }
+}
+
+/// The paging of a statement, which requests each page once the previous one was handed out
+#[derive(Debug)]
+pub(crate) struct Pager<E> {
+    pending: Option<E>,
+    has_more_pages: bool,
+}
+
+impl<E> Pager<E> {
+    pub(crate) fn new() -> Self {
+        Pager {
+            pending: None,
+            has_more_pages: true,
+        }
+    }
+
+    /// Polls the page being fetched, requesting it with `request` if none is. Once it arrives,
+    /// `advance` points the statement at the next page, telling whether there is one. Any error
+    /// ends the paging, so a failed page is not requested again.
+    pub(crate) fn poll_page<T, R, A>(&mut self, cx: &mut Context, request: R, advance: A) -> Poll<Option<Result<T>>>
+        where E: Future<Output = Result<T>> + Unpin,
+              R: FnOnce() -> E,
+              A: FnOnce(&T) -> Result<bool> {
+        if !self.has_more_pages {
+            return Poll::Ready(None);
+        }
+        let result = match Pin::new(self.pending.get_or_insert_with(request)).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.pending = None;
+        self.has_more_pages = false;
+        let page = result.and_then(|page| {
+            self.has_more_pages = advance(&page)?;
+            Ok(page)
+        });
+        Poll::Ready(Some(page))
+    }
+}
+
+/// The rows of a statement across all of its result pages, each mapped into `T`.
+///
+/// Created by `ResultPages::rows`. A page is mapped as a whole once it arrives, and a row
+/// that fails to map is yielded as an error without ending the iteration.
+#[derive(Debug)]
+pub struct PagedRows<'a, T> {
+    pages: ResultPages<'a>,
+    rows: vec::IntoIter<Result<T>>,
+}
+
+// the rows are only ever moved out, never pinned
+impl<'a, T> Unpin for PagedRows<'a, T> {}
+
+impl<'a, T: FromRow> Iterator for PagedRows<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> { block_on(future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))) }
+}
+
+impl<'a, T: FromRow> Stream for PagedRows<'a, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let pages = &mut this.pages;
+        poll_row(&mut this.rows,
+                 |cx| Pin::new(&mut *pages).poll_next(cx),
+                 |page| page.iter().map(|row| T::from_row(&row)).collect(),
+                 cx)
+    }
+}
+
+/// Polls the next row, taking it from the rows left of the last page, or else from the next
+/// page `poll_page` gives once `load` has mapped its rows. A page that fails to arrive is
+/// yielded as an error, and the rows end with the pages.
+fn poll_row<P, T, F, L>(rows: &mut vec::IntoIter<Result<T>>,
+                        mut poll_page: F,
+                        load: L,
+                        cx: &mut Context)
+                        -> Poll<Option<Result<T>>>
+    where F: FnMut(&mut Context) -> Poll<Option<Result<P>>>,
+          L: Fn(&P) -> Vec<Result<T>> {
+    loop {
+        if let Some(row) = rows.next() {
+            return Poll::Ready(Some(row));
+        }
+        match poll_page(cx) {
+            Poll::Ready(Some(Ok(page))) => *rows = load(&page).into_iter(),
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra_sys::CASS_ERROR_LIB_REQUEST_TIMED_OUT;
+    use std::task::Waker;
+
+    /// A page that has arrived, or one that never does
+    pub struct Page(pub Option<Result<u32>>);
+
+    impl Future for Page {
+        type Output = Result<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<u32>> {
+            match self.0.take() {
+                Some(page) => Poll::Ready(page),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    pub fn timed_out() -> Error { CassError::with_message(CASS_ERROR_LIB_REQUEST_TIMED_OUT, "timed out").into() }
+
+    /// Pages through `pages`, each of which has a next one unless it is the last, or unless
+    /// `advance` fails on it. Returns what each poll resolved to, and how many pages were
+    /// requested when it did.
+    pub fn page(pages: Vec<Page>, advance: fn(u32) -> Result<()>) -> Vec<(Poll<Option<Result<u32>>>, usize)> {
+        let mut pager = Pager::new();
+        let last = pages.len() as u32;
+        let mut pages = pages.into_iter();
+        let mut requested = 0;
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut polls = Vec::new();
+        loop {
+            let poll = pager.poll_page(&mut cx,
+                                       || {
+                                           requested += 1;
+                                           pages.next().expect("no page left")
+                                       },
+                                       |&page| advance(page).map(|_| page < last));
+            let done = !matches!(poll, Poll::Ready(Some(_)));
+            polls.push((poll, requested));
+            if done {
+                return polls;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pager() {
+    use self::fake::*;
+
+    let pages = |count| (1..=count).map(|page| Page(Some(Ok(page)))).collect::<Vec<_>>();
+    let polls = page(pages(3), |_| Ok(()));
+    let pages_of = |polls: Vec<(Poll<Option<Result<u32>>>, usize)>| {
+        polls.into_iter().map(|(poll, requested)| (poll.map(|page| page.map(|page| page.ok())), requested))
+    };
+    assert_eq!(pages_of(polls).collect::<Vec<_>>(),
+               vec![(Poll::Ready(Some(Some(1))), 1),
+                    (Poll::Ready(Some(Some(2))), 2),
+                    (Poll::Ready(Some(Some(3))), 3),
+                    (Poll::Ready(None), 3)]);
+
+    // a page still on its way is polled again rather than requested again
+    let polls = page(vec![Page(Some(Ok(1))), Page(None)], |_| Ok(()));
+    assert!(matches!(polls[1], (Poll::Pending, 2)));
+    assert_eq!(polls.len(), 2);
+
+    // a failed page ends the paging
+    let polls = page(vec![Page(Some(Ok(1))), Page(Some(Err(timed_out()))), Page(Some(Ok(3)))], |_| Ok(()));
+    assert!(matches!(polls[1], (Poll::Ready(Some(Err(_))), 2)));
+    assert!(matches!(polls[2], (Poll::Ready(None), 2)));
+
+    // so does failing to move on to the next page
+    let polls = page(pages(3), |page| if page == 2 { Err(timed_out()) } else { Ok(()) });
+    assert!(matches!(polls[1], (Poll::Ready(Some(Err(_))), 2)));
+    assert!(matches!(polls[2], (Poll::Ready(None), 2)));
+}
+
+#[test]
+fn test_poll_row() {
+    use self::fake::*;
+    use std::task::Waker;
+
+    // a page of 2 rows, an empty one, one still on its way that then fails, and one of 3 rows
+    // whose second fails to map
+    let mut pages = vec![Poll::Ready(Some(Ok(2))),
+                         Poll::Ready(Some(Ok(0))),
+                         Poll::Pending,
+                         Poll::Ready(Some(Err(timed_out()))),
+                         Poll::Ready(Some(Ok(3))),
+                         Poll::Ready(None)]
+        .into_iter();
+    let load = |&count: &u32| {
+        (0..count).map(move |row| if count == 3 && row == 1 { Err(timed_out()) } else { Ok(row) })
+    };
+    let mut rows = Vec::new().into_iter();
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut polls = Vec::new();
+    loop {
+        let poll = poll_row(&mut rows,
+                            |_| pages.next().expect("no page left"),
+                            |page| load(page).collect(),
+                            &mut cx);
+        let done = matches!(poll, Poll::Ready(None));
+        polls.push(poll.map(|row| row.map(|row| row.ok())));
+        if done {
+            break;
+        }
+    }
+    assert_eq!(polls,
+               vec![Poll::Ready(Some(Some(0))),
+                    Poll::Ready(Some(Some(1))),
+                    Poll::Pending,
+                    Poll::Ready(Some(None)),
+                    Poll::Ready(Some(Some(0))),
+                    Poll::Ready(Some(None)),
+                    Poll::Ready(Some(Some(2))),
+                    Poll::Ready(None)]);
+}
@@ -0,0 +1,372 @@
+//! Load balancing policies written in Rust.
+//!
+//! The driver picks the coordinator of each request inside its own request handling, with the
+//! policies built into it, and has neither callbacks through which a policy written in Rust
+//! could be consulted nor a way to send a request to a given host. A `LoadBalancingPolicy` set
+//! on `Cluster`, or on an execution profile, is applied on the Rust side instead: next to its
+//! own, each session connects one session per contact point a policy does not ignore,
+//! whitelisted to that host alone, and `Session::execute_balanced` sends a statement through the
+//! sessions of its query plan in turn.
+
+use cassandra::cluster::{Cluster, ContactPoint};
+use cassandra::error::*;
+use cassandra::future::{ConnectFuture, ResultFuture, block_on};
+use cassandra::result::CassResult;
+use cassandra::session::Session;
+use cassandra::statement::Statement;
+use cassandra::util::Protected;
+use cassandra_sys::{CASS_ERROR_LIB_NO_HOSTS_AVAILABLE, CASS_ERROR_LIB_UNABLE_TO_CONNECT,
+                    CASS_ERROR_SERVER_IS_BOOTSTRAPPING, CASS_ERROR_SERVER_OVERLOADED};
+use cassandra_sys::cass_cluster_set_whitelist_filtering_n;
+use cassandra_sys::cass_session_connect;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::os::raw::c_char;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::vec;
+
+/// How far a host is from the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HostDistance {
+    /// A host tried before the remote ones, such as one in the local data center or rack
+    Local,
+    /// A host tried after the local ones
+    Remote,
+    /// A host that is neither connected to nor sent any statement
+    Ignored,
+}
+
+/// Chooses the hosts a statement is sent to, and the order they are tried in.
+///
+/// ```ignore
+/// /// Sends statements to the hosts of the local rack first
+/// #[derive(Debug)]
+/// struct RackAware(HashSet<ContactPoint>);
+///
+/// impl LoadBalancingPolicy for RackAware {
+///     fn distance(&self, host: &ContactPoint) -> HostDistance {
+///         if self.0.contains(host) { HostDistance::Local } else { HostDistance::Remote }
+///     }
+/// }
+///
+/// cluster.set_load_balancing_policy(RackAware(local_rack));
+/// let session = cluster.connect()?;
+/// let result = session.execute_balanced(&statement).wait()?;
+/// ```
+pub trait LoadBalancingPolicy: Debug + Send + Sync {
+    /// How far `host`, one of the cluster's contact points, is from the client
+    fn distance(&self, host: &ContactPoint) -> HostDistance;
+
+    /// The hosts to send `statement` to, in the order they are tried, taken from `hosts`: the
+    /// hosts that are not ignored, local ones first, which is the default plan.
+    fn query_plan<'a>(&self,
+                      _statement: &Statement,
+                      hosts: &'a [ContactPoint])
+                      -> Box<dyn Iterator<Item = &'a ContactPoint> + 'a> {
+        Box::new(hosts.iter())
+    }
+}
+
+/// The hosts of `contact_points` that `policy` does not ignore, local ones first
+fn by_distance(policy: &dyn LoadBalancingPolicy, contact_points: &[ContactPoint]) -> Vec<ContactPoint> {
+    let mut hosts: Vec<(HostDistance, ContactPoint)> = contact_points.iter()
+        .map(|host| (policy.distance(host), host.clone()))
+        .filter(|&(distance, _)| distance != HostDistance::Ignored)
+        .collect();
+    hosts.sort_by_key(|&(distance, _)| distance);
+    hosts.into_iter().map(|(_, host)| host).collect()
+}
+
+/// The sessions a load balancing policy routes statements through, one per host
+#[derive(Debug)]
+pub(crate) struct BalancedHosts {
+    /// The cluster's policy, for the statements without one of their own
+    policy: Option<Arc<dyn LoadBalancingPolicy>>,
+    hosts: Vec<ContactPoint>,
+    sessions: Vec<Session>,
+}
+
+impl BalancedHosts {
+    /// Connects a session to each host of `contact_points` that one of `policies` does not
+    /// ignore, each whitelisted to its host alone. The cluster is given back the `whitelist` it
+    /// had, and the connections are left to complete with the returned futures.
+    pub(crate) fn connect(policy: Option<Arc<dyn LoadBalancingPolicy>>,
+                          policies: &[Arc<dyn LoadBalancingPolicy>],
+                          cluster: &Cluster,
+                          contact_points: &[ContactPoint],
+                          whitelist: &[String])
+                          -> (Self, Vec<ConnectFuture>) {
+        let hosts: Vec<ContactPoint> = contact_points.iter()
+            .filter(|&host| policies.iter().any(|policy| policy.distance(host) != HostDistance::Ignored))
+            .cloned()
+            .collect();
+        let mut sessions = Vec::new();
+        let mut connecting = Vec::new();
+        for host in &hosts {
+            set_whitelist(cluster, &[]);
+            set_whitelist(cluster, &[host.host()]);
+            let session = Session::new();
+            connecting.push(unsafe { ConnectFuture::build(cass_session_connect(session.inner(), cluster.inner())) });
+            sessions.push(session);
+        }
+        set_whitelist(cluster, &[]);
+        set_whitelist(cluster, whitelist);
+        let balanced = BalancedHosts {
+            policy,
+            hosts,
+            sessions,
+        };
+        (balanced, connecting)
+    }
+
+    /// The sessions of the hosts in the query plan of `statement`, in order, by the policy of
+    /// its execution profile or else the cluster's. None if neither has one.
+    fn plan(&self, statement: &Statement) -> Option<Vec<&Session>> {
+        let policy = statement.load_balancing_policy().or(self.policy.as_ref())?;
+        Some(query_plan(&**policy, statement, &self.hosts).into_iter().map(|index| &self.sessions[index]).collect())
+    }
+}
+
+/// The query plan of `statement` as indexes into `hosts`, planned from the hosts that `policy`
+/// does not ignore
+fn query_plan(policy: &dyn LoadBalancingPolicy, statement: &Statement, hosts: &[ContactPoint]) -> Vec<usize> {
+    let planned = by_distance(policy, hosts);
+    policy.query_plan(statement, &planned).filter_map(|host| hosts.iter().position(|known| known == host)).collect()
+}
+
+/// Sets the whitelist of `cluster` to `hosts`, or clears it if there are none
+fn set_whitelist(cluster: &Cluster, hosts: &[String]) {
+    let hosts = hosts.join(",");
+    unsafe { cass_cluster_set_whitelist_filtering_n(cluster.inner(), hosts.as_ptr() as *const c_char, hosts.len()) }
+}
+
+/// Whether `err` means the host did not take the statement at all, which can then be sent to
+/// the next one without being executed twice
+fn not_taken(err: &Error) -> bool {
+    matches!(err.code(),
+             Some(CASS_ERROR_LIB_NO_HOSTS_AVAILABLE) |
+             Some(CASS_ERROR_LIB_UNABLE_TO_CONNECT) |
+             Some(CASS_ERROR_SERVER_OVERLOADED) |
+             Some(CASS_ERROR_SERVER_IS_BOOTSTRAPPING))
+}
+
+/// The executions of a statement on the hosts of its query plan, one after the other
+#[derive(Debug)]
+pub(crate) struct Plan<H, E> {
+    hosts: vec::IntoIter<H>,
+    pending: Option<E>,
+    /// The error of the last host that did not take the statement
+    last_error: Option<Error>,
+}
+
+impl<H, E> Plan<H, E> {
+    pub(crate) fn new(hosts: Vec<H>) -> Self {
+        Plan {
+            hosts: hosts.into_iter(),
+            pending: None,
+            last_error: None,
+        }
+    }
+
+    /// Polls the current execution, starting one on the next host with `launch` when none is
+    /// pending. Resolves to the first result, to the first error of a host that took the
+    /// statement, or to the last error once every host was tried.
+    pub(crate) fn poll_plan<T, L>(&mut self, cx: &mut Context, mut launch: L) -> Poll<Result<T>>
+        where E: Future<Output = Result<T>> + Unpin,
+              L: FnMut(H) -> E {
+        loop {
+            if self.pending.is_none() {
+                match self.hosts.next() {
+                    Some(host) => self.pending = Some(launch(host)),
+                    None => return Poll::Ready(Err(self.last_error.take().unwrap_or_else(no_hosts))),
+                }
+            }
+            let err = match self.pending.as_mut().map(|pending| Pin::new(pending).poll(cx)) {
+                Some(Poll::Ready(Ok(value))) => return Poll::Ready(Ok(value)),
+                Some(Poll::Ready(Err(err))) => err,
+                _ => return Poll::Pending,
+            };
+            self.pending = None;
+            if !not_taken(&err) {
+                return Poll::Ready(Err(err));
+            }
+            self.last_error = Some(err);
+        }
+    }
+}
+
+fn no_hosts() -> Error {
+    CassError::with_message(CASS_ERROR_LIB_NO_HOSTS_AVAILABLE, "the query plan has no hosts").into()
+}
+
+/// The result of a statement executed by `Session::execute_balanced`.
+///
+/// It implements `std::future::Future`, so it can be `.await`ed from an async executor, or
+/// waited on with `wait`. The statement is executed when the future is first polled.
+#[derive(Debug)]
+pub struct BalancedFuture<'a> {
+    statement: &'a Statement,
+    plan: Plan<&'a Session, ResultFuture>,
+}
+
+impl<'a> BalancedFuture<'a> {
+    /// Routes `statement` by its query plan among `balanced`, or through `session` alone if
+    /// neither its execution profile nor the cluster has a load balancing policy
+    pub(crate) fn new(session: &'a Session, balanced: Option<&'a BalancedHosts>, statement: &'a Statement) -> Self {
+        let hosts = balanced.and_then(|balanced| balanced.plan(statement)).unwrap_or_else(|| vec![session]);
+        BalancedFuture {
+            statement,
+            plan: Plan::new(hosts),
+        }
+    }
+
+    /// Blocks until a host answers, or every host of the plan failed to take the statement
+    pub fn wait(self) -> Result<CassResult> { block_on(self) }
+}
+
+impl<'a> Future for BalancedFuture<'a> {
+    type Output = Result<CassResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let statement = this.statement;
+        this.plan.poll_plan(cx, |session| session.execute(statement))
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra_sys::{CASS_ERROR_LIB_REQUEST_TIMED_OUT, CassError_};
+    use std::collections::HashMap;
+    use std::mem;
+    use std::ptr;
+    use std::str::FromStr;
+    use std::task::Waker;
+
+    /// Places hosts by the distance listed for them, ignoring the others, and sends statements
+    /// to the hosts of the plan given, if any
+    #[derive(Debug)]
+    pub struct Listed(pub HashMap<ContactPoint, HostDistance>, pub Option<Vec<ContactPoint>>);
+
+    impl LoadBalancingPolicy for Listed {
+        fn distance(&self, host: &ContactPoint) -> HostDistance {
+            self.0.get(host).cloned().unwrap_or(HostDistance::Ignored)
+        }
+
+        fn query_plan<'a>(&self,
+                          _statement: &Statement,
+                          hosts: &'a [ContactPoint])
+                          -> Box<dyn Iterator<Item = &'a ContactPoint> + 'a> {
+            match self.1 {
+                Some(ref plan) => {
+                    let plan = plan.clone();
+                    Box::new(plan.into_iter().filter_map(move |planned| hosts.iter().find(|&host| *host == planned)))
+                }
+                None => Box::new(hosts.iter()),
+            }
+        }
+    }
+
+    /// A statement that is never executed, nor freed
+    pub fn statement() -> mem::ManuallyDrop<Statement> { mem::ManuallyDrop::new(Statement::build(ptr::null_mut())) }
+
+    pub fn host(host: &str) -> ContactPoint { ContactPoint::from_str(host).unwrap() }
+
+    /// An execution that answers with the host it ran on, fails with the given error, or never
+    /// answers
+    pub struct Execution(pub Option<Result<u32>>);
+
+    impl Future for Execution {
+        type Output = Result<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<u32>> {
+            match self.0.take() {
+                Some(answer) => Poll::Ready(answer),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    pub fn failure(code: CassError_) -> Error { CassError::with_message(code, "failed").into() }
+
+    pub fn timed_out() -> Error { failure(CASS_ERROR_LIB_REQUEST_TIMED_OUT) }
+
+    /// Tries the hosts `0..answers.len()` in turn, each answering as `answers` says. Returns
+    /// what the plan resolved to on its first poll, and the hosts it ran the statement on.
+    pub fn plan(answers: Vec<Option<Result<u32>>>) -> (Poll<Result<u32>>, Vec<u32>) {
+        let hosts = (0..answers.len() as u32).collect();
+        let mut answers = answers.into_iter();
+        let mut plan = Plan::new(hosts);
+        let mut tried = Vec::new();
+        let mut cx = Context::from_waker(Waker::noop());
+        let poll = plan.poll_plan(&mut cx, |host| {
+            tried.push(host);
+            Execution(answers.next().unwrap().map(|answer| answer.map(|_| host)))
+        });
+        (poll, tried)
+    }
+}
+
+#[test]
+fn test_hosts_by_distance() {
+    use self::fake::*;
+
+    let distances = vec![(host("10.0.0.1"), HostDistance::Remote),
+                         (host("10.0.0.2"), HostDistance::Local),
+                         (host("db-3.example.com"), HostDistance::Remote),
+                         (host("10.0.0.4"), HostDistance::Local)];
+    let contact_points = ["10.0.0.1", "10.0.0.2", "db-3.example.com", "10.0.0.4", "10.0.0.5"]
+        .iter()
+        .map(|point| host(point))
+        .collect::<Vec<_>>();
+    let policy = Listed(distances.iter().cloned().collect(), None);
+    assert_eq!(by_distance(&policy, &contact_points),
+               vec![host("10.0.0.2"), host("10.0.0.4"), host("10.0.0.1"), host("db-3.example.com")]);
+    assert_eq!(query_plan(&policy, &statement(), &contact_points), vec![1, 3, 0, 2]);
+
+    // a plan of its own, which leaves out hosts and names one that the policy ignores
+    let policy = Listed(distances.into_iter().collect(),
+                        Some(vec![host("10.0.0.5"), host("10.0.0.1"), host("10.0.0.2")]));
+    assert_eq!(query_plan(&policy, &statement(), &contact_points), vec![0, 1]);
+}
+
+#[test]
+fn test_query_plan() {
+    use self::fake::*;
+
+    assert_eq!(plan(vec![Some(Ok(0)), Some(Ok(0))]).1, vec![0]);
+
+    // hosts that do not take the statement are passed over
+    let (poll, tried) = plan(vec![Some(Err(failure(CASS_ERROR_LIB_NO_HOSTS_AVAILABLE))),
+                                  Some(Err(failure(CASS_ERROR_SERVER_OVERLOADED))),
+                                  Some(Ok(0))]);
+    assert!(matches!(poll, Poll::Ready(Ok(2))));
+    assert_eq!(tried, vec![0, 1, 2]);
+
+    // but a host that took it and failed ends the plan, lest it be executed twice
+    let (poll, tried) = plan(vec![Some(Err(timed_out())), Some(Ok(0))]);
+    assert!(matches!(poll, Poll::Ready(Err(_))));
+    assert_eq!(tried, vec![0]);
+
+    // as does a pending host, which is waited for
+    let (poll, tried) = plan(vec![None, Some(Ok(0))]);
+    assert!(poll.is_pending());
+    assert_eq!(tried, vec![0]);
+
+    // with every host passed over, the last one's error is given
+    let (poll, _) = plan(vec![Some(Err(failure(CASS_ERROR_LIB_NO_HOSTS_AVAILABLE))),
+                              Some(Err(failure(CASS_ERROR_LIB_UNABLE_TO_CONNECT)))]);
+    match poll {
+        Poll::Ready(Err(err)) => assert!(matches!(err.code(), Some(CASS_ERROR_LIB_UNABLE_TO_CONNECT))),
+        _ => panic!("expected the plan to fail"),
+    }
+    let (poll, _) = plan(vec![]);
+    match poll {
+        Poll::Ready(Err(err)) => assert!(matches!(err.code(), Some(CASS_ERROR_LIB_NO_HOSTS_AVAILABLE))),
+        _ => panic!("expected the plan to fail"),
+    }
+}
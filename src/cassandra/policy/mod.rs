@@ -1 +1,10 @@
+//! Retry, speculative execution and load balancing policies.
+//!
+//! Load balancing is configured on `Cluster` with the policies built into the C++ driver:
+//! round robin, DC aware, token aware and latency aware routing, narrowed by host and data
+//! center white and black lists. A `LoadBalancingPolicy` written in Rust can be set as well,
+//! which `Session::execute_balanced` routes statements by.
+
+pub mod load_balancing;
 pub mod retry;
+pub mod speculative;
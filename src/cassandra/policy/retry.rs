@@ -1,42 +1,429 @@
-use cassandra::util::Protected;
+//! Retry policies.
+//!
+//! The driver decides whether to retry a timed out, unavailable or failed request inside its
+//! own request handling, and it only offers its built-in policies: it has no callbacks through
+//! which a retry policy written in Rust could be consulted. A `RetryDecision` is consulted on the
+//! Rust side instead, by `Session::execute_with_retry` and `Session::execute_batch_with_retry`:
+//! they hand the driver the fallthrough policy for the request, which returns every such error,
+//! and execute the request again for as long as the decision asks for it. A `RetryPolicy::custom`
+//! is only taken there; the cluster, statements, batches and execution profiles refuse it, since
+//! the driver would be left with the fallthrough policy and never retry their requests.
+
+use cassandra::batch::Batch;
+use cassandra::consistency::Consistency;
+use cassandra::error::*;
+use cassandra::future::{ResultFuture, block_on};
+use cassandra::result::CassResult;
+use cassandra::session::Session;
+use cassandra::statement::Statement;
+use cassandra::util::{CassErrorExt, Protected};
+use cassandra::write_type::WriteType;
+use cassandra_sys::CASS_ERROR_LIB_BAD_PARAMS;
 use cassandra_sys::CassRetryPolicy as _RetryPolicy;
+use cassandra_sys::cass_batch_set_retry_policy;
 use cassandra_sys::cass_retry_policy_default_new;
 use cassandra_sys::cass_retry_policy_downgrading_consistency_new;
 use cassandra_sys::cass_retry_policy_fallthrough_new;
 use cassandra_sys::cass_retry_policy_free;
 use cassandra_sys::cass_retry_policy_logging_new;
+use cassandra_sys::cass_statement_set_retry_policy;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
 /// The selected retry policy
 #[derive(Debug)]
-pub struct RetryPolicy(*mut _RetryPolicy);
+pub struct RetryPolicy {
+    inner: *mut _RetryPolicy,
+    decision: Option<Arc<dyn RetryDecision>>,
+}
+
+// The driver's policies are reference counted and never changed once created, and the driver
+// itself shares them between its IO threads, so they can be sent and shared across threads.
+unsafe impl Send for RetryPolicy {}
+unsafe impl Sync for RetryPolicy {}
 
 impl Protected<*mut _RetryPolicy> for RetryPolicy {
-    fn inner(&self) -> *mut _RetryPolicy { self.0 }
-    fn build(inner: *mut _RetryPolicy) -> Self { RetryPolicy(inner) }
+    fn inner(&self) -> *mut _RetryPolicy { self.inner }
+    fn build(inner: *mut _RetryPolicy) -> Self {
+        RetryPolicy {
+            inner,
+            decision: None,
+        }
+    }
 }
 
 impl RetryPolicy {
     /// The default retry policy
-    pub fn default_new() -> Self { unsafe { RetryPolicy(cass_retry_policy_default_new()) } }
+    pub fn default_new() -> Self { unsafe { RetryPolicy::build(cass_retry_policy_default_new()) } }
 
     /// An auto-CL-downgrading consistency level
     pub fn downgrading_consistency_new() -> Self {
-        unsafe { RetryPolicy(cass_retry_policy_downgrading_consistency_new()) }
+        unsafe { RetryPolicy::build(cass_retry_policy_downgrading_consistency_new()) }
     }
 
     /// a fallthrough retry policy
-    pub fn fallthrough_new() -> Self { unsafe { RetryPolicy(cass_retry_policy_fallthrough_new()) } }
+    pub fn fallthrough_new() -> Self { unsafe { RetryPolicy::build(cass_retry_policy_fallthrough_new()) } }
 
     /// The a logging retry policy
     pub fn logging_new(child_retry_policy: RetryPolicy) -> Self {
-        unsafe { RetryPolicy(cass_retry_policy_logging_new(child_retry_policy.0)) }
+        RetryPolicy {
+            inner: unsafe { cass_retry_policy_logging_new(child_retry_policy.inner) },
+            decision: child_retry_policy.decision.clone(),
+        }
+    }
+
+    /// A retry policy written in Rust, to pass to `Session::execute_with_retry` or
+    /// `Session::execute_batch_with_retry`. The driver itself is given the fallthrough policy for
+    /// the request, so it never retries on its own.
+    pub fn custom<D: RetryDecision + 'static>(decision: D) -> Self {
+        let mut policy = RetryPolicy::fallthrough_new();
+        policy.decision = Some(Arc::new(decision));
+        policy
+    }
+
+    /// Fails for a policy written in Rust, which the driver alone would never retry with
+    pub(crate) fn check_driver_policy(&self) -> Result<()> {
+        match self.decision {
+            Some(_) => {
+                Err(CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS,
+                                            "a custom retry policy is only taken by execute_with_retry and \
+                                             execute_batch_with_retry")
+                    .into())
+            }
+            None => Ok(()),
+        }
     }
 }
 
 impl Drop for RetryPolicy {
     fn drop(&mut self) {
         unsafe {
-            cass_retry_policy_free(self.0);
+            cass_retry_policy_free(self.inner);
+        }
+    }
+}
+
+/// What to do about a request that failed
+#[derive(Debug, Clone, Copy)]
+pub enum RetryAction {
+    /// Execute the request again, at the same consistency level
+    Retry,
+    /// Execute the request again, at another consistency level
+    RetryAt(Consistency),
+    /// Fail with the error
+    Rethrow,
+    /// Succeed without a result
+    Ignore,
+}
+
+/// Decides whether to retry a request the server failed. Each callback is given the number of
+/// times the request was retried already, and rethrows the error unless overridden.
+///
+/// ```ignore
+/// #[derive(Debug)]
+/// struct RetryReadsOnce;
+///
+/// impl RetryDecision for RetryReadsOnce {
+///     fn on_read_timeout(&self, consistency: Consistency, _received: i32, _required: i32,
+///                        _data_present: bool, retries: u32) -> RetryAction {
+///         if retries == 0 { RetryAction::RetryAt(consistency) } else { RetryAction::Rethrow }
+///     }
+/// }
+///
+/// let result = session.execute_with_retry(statement, RetryPolicy::custom(RetryReadsOnce)).wait()?;
+/// ```
+pub trait RetryDecision: Debug + Send + Sync {
+    /// The coordinator timed out waiting for replicas to answer a read
+    fn on_read_timeout(&self,
+                       _consistency: Consistency,
+                       _received: i32,
+                       _required: i32,
+                       _data_present: bool,
+                       _retries: u32)
+                       -> RetryAction {
+        RetryAction::Rethrow
+    }
+
+    /// The coordinator timed out waiting for replicas to acknowledge a write
+    fn on_write_timeout(&self,
+                        _consistency: Consistency,
+                        _received: i32,
+                        _required: i32,
+                        _write_type: WriteType,
+                        _retries: u32)
+                        -> RetryAction {
+        RetryAction::Rethrow
+    }
+
+    /// Too few replicas were alive to attempt the request
+    fn on_unavailable(&self, _consistency: Consistency, _alive: i32, _required: i32, _retries: u32) -> RetryAction {
+        RetryAction::Rethrow
+    }
+
+    /// The request failed with any other error
+    fn on_request_error(&self, _error: &Error, _retries: u32) -> RetryAction { RetryAction::Rethrow }
+}
+
+/// Asks `decision` what to do about `error`, the request having been retried `retries` times
+fn decide(decision: &dyn RetryDecision, error: &Error, retries: u32) -> RetryAction {
+    match *error {
+        Error::Server(_, ServerError::ReadTimeout { consistency, received, required, data_present }) => {
+            decision.on_read_timeout(consistency, received, required, data_present, retries)
+        }
+        Error::Server(_, ServerError::WriteTimeout { consistency, received, required, write_type }) => {
+            decision.on_write_timeout(consistency, received, required, write_type, retries)
+        }
+        Error::Server(_, ServerError::Unavailable { consistency, alive, required }) => {
+            decision.on_unavailable(consistency, alive, required, retries)
+        }
+        _ => decision.on_request_error(error, retries),
+    }
+}
+
+/// A request that can be executed again
+#[derive(Debug)]
+pub(crate) enum Request {
+    Statement(Statement),
+    Batch(Batch),
+}
+
+impl Request {
+    fn execute(&self, session: &Session) -> ResultFuture {
+        match *self {
+            Request::Statement(ref statement) => session.execute(statement),
+            Request::Batch(ref batch) => session.execute_batch_ref(batch),
+        }
+    }
+
+    fn set_consistency(&mut self, consistency: Consistency) -> Result<()> {
+        match *self {
+            Request::Statement(ref mut statement) => statement.set_consistency(consistency).map(|_| ()),
+            Request::Batch(ref mut batch) => batch.set_consistency(consistency.inner()).map(|_| ()),
+        }
+    }
+
+    /// Hands the driver `retry_policy` for the request, which may be written in Rust
+    fn set_retry_policy(&mut self, retry_policy: &RetryPolicy) -> Result<()> {
+        unsafe {
+            match *self {
+                Request::Statement(ref statement) => {
+                    cass_statement_set_retry_policy(statement.inner(), retry_policy.inner()).wrap(())
+                }
+                Request::Batch(ref batch) => cass_batch_set_retry_policy(batch.inner(), retry_policy.inner()).wrap(()),
+            }
+        }
+    }
+}
+
+/// The executions of a request, retried for as long as its retry decision asks
+#[derive(Debug)]
+pub(crate) struct Retries<E> {
+    decision: Option<Arc<dyn RetryDecision>>,
+    pending: Option<E>,
+    retries: u32,
+}
+
+impl<E> Retries<E> {
+    pub(crate) fn new(decision: Option<Arc<dyn RetryDecision>>) -> Self {
+        Retries {
+            decision,
+            pending: None,
+            retries: 0,
+        }
+    }
+
+    /// Polls the current execution, starting one with `launch` when none is pending. `launch`
+    /// is given the consistency level to retry at, if the decision changed it. Resolves to the
+    /// result, to no result if the error was ignored, or to the error that ended the retries.
+    pub(crate) fn poll_retries<T, L>(&mut self, cx: &mut Context, mut launch: L) -> Poll<Result<Option<T>>>
+        where E: Future<Output = Result<T>> + Unpin,
+              L: FnMut(Option<Consistency>) -> Result<E> {
+        loop {
+            if self.pending.is_none() {
+                self.pending = Some(launch(None)?);
+            }
+            let err = match self.pending.as_mut().map(|pending| Pin::new(pending).poll(cx)) {
+                Some(Poll::Ready(Ok(value))) => return Poll::Ready(Ok(Some(value))),
+                Some(Poll::Ready(Err(err))) => err,
+                _ => return Poll::Pending,
+            };
+            self.pending = None;
+            let action = match self.decision {
+                Some(ref decision) => decide(&**decision, &err, self.retries),
+                None => RetryAction::Rethrow,
+            };
+            match action {
+                RetryAction::Retry => {}
+                RetryAction::RetryAt(consistency) => self.pending = Some(launch(Some(consistency))?),
+                RetryAction::Rethrow => return Poll::Ready(Err(err)),
+                RetryAction::Ignore => return Poll::Ready(Ok(None)),
+            }
+            self.retries += 1;
+        }
+    }
+}
+
+/// The result of a request executed by `Session::execute_with_retry` or
+/// `Session::execute_batch_with_retry`, which is `None` if the retry decision ignored the error.
+///
+/// It implements `std::future::Future`, so it can be `.await`ed from an async executor, or
+/// waited on with `wait`. The request is executed when the future is first polled.
+#[derive(Debug)]
+pub struct RetryFuture<'a> {
+    session: &'a Session,
+    request: Request,
+    /// The policy to hand the driver before the request is first executed
+    retry_policy: Option<RetryPolicy>,
+    retries: Retries<ResultFuture>,
+}
+
+impl<'a> RetryFuture<'a> {
+    /// Executes the request with `retry_policy`, retrying it for as long as its decision asks
+    pub(crate) fn new(session: &'a Session, request: Request, retry_policy: RetryPolicy) -> Self {
+        RetryFuture {
+            session,
+            request,
+            retries: Retries::new(retry_policy.decision.clone()),
+            retry_policy: Some(retry_policy),
+        }
+    }
+
+    /// Blocks until the request succeeds, or the retry decision gives up on it
+    pub fn wait(self) -> Result<Option<CassResult>> { block_on(self) }
+}
+
+impl<'a> Future for RetryFuture<'a> {
+    type Output = Result<Option<CassResult>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (session, request, retry_policy) = (this.session, &mut this.request, &mut this.retry_policy);
+        this.retries.poll_retries(cx, |consistency| {
+            if let Some(retry_policy) = retry_policy.take() {
+                request.set_retry_policy(&retry_policy)?;
+            }
+            if let Some(consistency) = consistency {
+                request.set_consistency(consistency)?;
+            }
+            Ok(request.execute(session))
+        })
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra_sys::{CASS_ERROR_SERVER_READ_TIMEOUT, CASS_ERROR_SERVER_SYNTAX_ERROR, CASS_ERROR_SERVER_UNAVAILABLE};
+    use cassandra_sys::CassConsistency_::{CASS_CONSISTENCY_ONE, CASS_CONSISTENCY_QUORUM};
+    use std::future;
+
+    /// Retries read timeouts at ONE, twice at most, and ignores unavailable replicas
+    #[derive(Debug)]
+    pub struct Downgrading;
+
+    impl RetryDecision for Downgrading {
+        fn on_read_timeout(&self,
+                           _consistency: Consistency,
+                           _received: i32,
+                           _required: i32,
+                           _data_present: bool,
+                           retries: u32)
+                           -> RetryAction {
+            if retries < 2 { RetryAction::RetryAt(one()) } else { RetryAction::Rethrow }
+        }
+
+        fn on_unavailable(&self, _consistency: Consistency, _alive: i32, _required: i32, _retries: u32) -> RetryAction {
+            RetryAction::Ignore
         }
     }
+
+    pub fn one() -> Consistency { Consistency::build(CASS_CONSISTENCY_ONE) }
+
+    pub fn read_timeout() -> Error {
+        Error::Server(CassError::with_message(CASS_ERROR_SERVER_READ_TIMEOUT, "read timeout"),
+                      ServerError::ReadTimeout {
+                          consistency: Consistency::build(CASS_CONSISTENCY_QUORUM),
+                          received: 1,
+                          required: 2,
+                          data_present: false,
+                      })
+    }
+
+    pub fn unavailable() -> Error {
+        Error::Server(CassError::with_message(CASS_ERROR_SERVER_UNAVAILABLE, "unavailable"),
+                      ServerError::Unavailable {
+                          consistency: Consistency::build(CASS_CONSISTENCY_QUORUM),
+                          alive: 1,
+                          required: 2,
+                      })
+    }
+
+    pub fn syntax_error() -> Error {
+        CassError::with_message(CASS_ERROR_SERVER_SYNTAX_ERROR, "syntax error").into()
+    }
+
+    /// Executes a request whose executions answer with `outcomes` in turn, returning its result
+    /// and the consistency levels the retries were launched at
+    pub fn execute(decision: Option<Arc<dyn RetryDecision>>,
+                   outcomes: Vec<Result<u32>>)
+                   -> (Result<Option<u32>>, Vec<Option<Consistency>>) {
+        let mut retries = Retries::new(decision);
+        let mut outcomes = outcomes.into_iter();
+        let mut launched = Vec::new();
+        let mut cx = Context::from_waker(::std::task::Waker::noop());
+        let poll = retries.poll_retries(&mut cx, |consistency| {
+            launched.push(consistency);
+            Ok(future::ready(outcomes.next().expect("no execution left")))
+        });
+        match poll {
+            Poll::Ready(result) => (result, launched),
+            Poll::Pending => panic!("ready executions left the request pending"),
+        }
+    }
+}
+
+#[test]
+fn test_retry_decision() {
+    use self::fake::*;
+
+    let decision: Arc<dyn RetryDecision> = Arc::new(Downgrading);
+
+    let (result, launched) = execute(Some(decision.clone()), vec![Ok(1)]);
+    assert_eq!(result.unwrap(), Some(1));
+    assert_eq!(launched.len(), 1);
+
+    let (result, launched) = execute(Some(decision.clone()), vec![Err(read_timeout()), Ok(2)]);
+    assert_eq!(result.unwrap(), Some(2));
+    assert_eq!(launched.iter().map(|consistency| consistency.map(|c| c.inner() as u32)).collect::<Vec<_>>(),
+               vec![None, Some(one().inner() as u32)]);
+
+    let (result, launched) = execute(Some(decision.clone()),
+                                     vec![Err(read_timeout()), Err(read_timeout()), Err(read_timeout())]);
+    assert!(matches!(result, Err(Error::Server(_, ServerError::ReadTimeout { .. }))));
+    assert_eq!(launched.len(), 3);
+
+    let (result, _) = execute(Some(decision.clone()), vec![Err(unavailable())]);
+    assert_eq!(result.unwrap(), None);
+
+    let (result, launched) = execute(Some(decision), vec![Err(syntax_error())]);
+    assert!(result.is_err());
+    assert_eq!(launched.len(), 1);
+
+    let (result, launched) = execute(None, vec![Err(read_timeout())]);
+    assert!(result.is_err());
+    assert_eq!(launched.len(), 1);
+
+    // the policies wrap no driver policy, and are forgotten rather than freed
+    let builtin = RetryPolicy::build(::std::ptr::null_mut());
+    assert!(builtin.check_driver_policy().is_ok());
+    let custom = RetryPolicy {
+        inner: ::std::ptr::null_mut(),
+        decision: Some(Arc::new(Downgrading)),
+    };
+    assert!(custom.check_driver_policy().is_err());
+    ::std::mem::forget((builtin, custom));
 }
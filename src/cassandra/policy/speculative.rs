@@ -0,0 +1,437 @@
+//! Speculative execution of idempotent statements.
+//!
+//! The C++ driver this crate wraps has no speculative execution of its own, so it is done here:
+//! the statement is executed again each time `delay` passes without an answer, up to
+//! `max_executions` times in total, and the first execution to succeed wins.
+
+use cassandra::error::*;
+use cassandra::future::{ResultFuture, block_on};
+use cassandra::result::CassResult;
+use cassandra::session::Session;
+use cassandra::statement::Statement;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Executes a statement again after a constant delay without an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstantSpeculativeExecutionPolicy {
+    /// How long to wait for an answer before starting another execution
+    pub delay: Duration,
+    /// The largest number of executions of a statement, the first one included
+    pub max_executions: u32,
+}
+
+/// Counts of the speculative executions started by a session
+#[derive(Debug, Default)]
+pub struct SpeculativeExecutionCounters {
+    fired: AtomicU64,
+    won: AtomicU64,
+}
+
+impl SpeculativeExecutionCounters {
+    /// The number of executions started after the first one
+    pub fn fired(&self) -> u64 { self.fired.load(Ordering::Relaxed) }
+
+    /// The number of statements answered by an execution other than the first one
+    pub fn won(&self) -> u64 { self.won.load(Ordering::Relaxed) }
+}
+
+impl ConstantSpeculativeExecutionPolicy {
+    /// Starts a race of executions, timing the delays between them on `clock`
+    pub(crate) fn race<E, C: Clock>(&self, clock: C) -> Race<E, C> {
+        Race {
+            policy: *self,
+            clock,
+            executions: Vec::new(),
+            started: 0,
+            next: None,
+        }
+    }
+}
+
+/// Times the delays between the executions of a race
+pub(crate) trait Clock {
+    /// Resolves once its delay has passed
+    type Sleep: Future<Output = ()> + Unpin;
+
+    /// Starts a delay
+    fn sleep(&self, delay: Duration) -> Self::Sleep;
+}
+
+/// The system clock, which wakes the races from a timer thread shared by all of them once a
+/// delay has passed
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    type Sleep = Sleep;
+
+    fn sleep(&self, delay: Duration) -> Sleep {
+        Sleep {
+            deadline: Instant::now() + delay,
+            id: None,
+        }
+    }
+}
+
+/// A delay on the system clock
+#[derive(Debug)]
+pub(crate) struct Sleep {
+    deadline: Instant,
+    /// The id of the delay on the timer thread, once it has been polled
+    id: Option<u64>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            if let Some(id) = self.id.take() {
+                Timer::shared().cancel(id);
+            }
+            return Poll::Ready(());
+        }
+        let id = Timer::shared().schedule(self.deadline, self.id, cx.waker());
+        self.id = Some(id);
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    /// Stops waiting on the timer thread, the delay being no longer awaited
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            Timer::shared().cancel(id);
+        }
+    }
+}
+
+/// The delays of every race, waited on by a single thread
+#[derive(Debug, Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    next_id: u64,
+    /// The deadlines by id, earliest first. A cancelled delay stays here until its deadline.
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    /// The waker of each delay still awaited
+    wakers: HashMap<u64, Waker>,
+}
+
+impl Timer {
+    /// The timer, its thread started on first use
+    fn shared() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            thread::Builder::new()
+                .name("cassandra-timer".to_owned())
+                .spawn(|| Timer::shared().run())
+                .expect("failed to spawn the timer thread");
+            Timer::default()
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TimerState> { self.state.lock().unwrap_or_else(PoisonError::into_inner) }
+
+    /// Wakes `waker` once `deadline` has passed, returning the id of the delay. A delay already
+    /// scheduled under `id` only has its waker replaced.
+    fn schedule(&self, deadline: Instant, id: Option<u64>, waker: &Waker) -> u64 {
+        let mut state = self.lock();
+        let id = match id {
+            Some(id) => id,
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                let earliest = state.deadlines.peek().is_none_or(|&Reverse((next, _))| deadline < next);
+                state.deadlines.push(Reverse((deadline, id)));
+                if earliest {
+                    self.changed.notify_one();
+                }
+                id
+            }
+        };
+        state.wakers.insert(id, waker.clone());
+        id
+    }
+
+    fn cancel(&self, id: u64) { self.lock().wakers.remove(&id); }
+
+    /// Wakes the delays as their deadlines pass
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            while let Some(&Reverse((deadline, id))) = state.deadlines.peek() {
+                if deadline > now {
+                    break;
+                }
+                state.deadlines.pop();
+                due.extend(state.wakers.remove(&id));
+            }
+            if !due.is_empty() {
+                drop(state);
+                due.into_iter().for_each(Waker::wake);
+                state = self.lock();
+                continue;
+            }
+            let next = state.deadlines.peek().map(|&Reverse((deadline, _))| deadline);
+            state = match next {
+                Some(deadline) => {
+                    self.changed
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self.changed.wait(state).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+/// The executions of a statement racing for an answer
+#[derive(Debug)]
+pub(crate) struct Race<E, C: Clock> {
+    policy: ConstantSpeculativeExecutionPolicy,
+    clock: C,
+    /// The pending executions, by number
+    executions: Vec<(u32, E)>,
+    started: u32,
+    /// The delay before the next execution, if another one may be started
+    next: Option<C::Sleep>,
+}
+
+impl<E, C: Clock> Race<E, C> {
+    /// Polls the executions, starting one with `launch` whenever one is due. Resolves to the
+    /// first success, or to the last error once every execution started has failed.
+    pub(crate) fn poll_race<T, L>(&mut self,
+                                  cx: &mut Context,
+                                  counters: &SpeculativeExecutionCounters,
+                                  mut launch: L)
+                                  -> Poll<Result<T>>
+        where E: Future<Output = Result<T>> + Unpin,
+              L: FnMut() -> E {
+        if self.started == 0 {
+            self.start(&mut launch);
+        }
+        loop {
+            let mut index = 0;
+            while index < self.executions.len() {
+                match Pin::new(&mut self.executions[index].1).poll(cx) {
+                    Poll::Ready(Ok(value)) => {
+                        if self.executions[index].0 > 0 {
+                            counters.won.fetch_add(1, Ordering::Relaxed);
+                        }
+                        self.finish();
+                        return Poll::Ready(Ok(value));
+                    }
+                    Poll::Ready(Err(err)) => {
+                        self.executions.swap_remove(index);
+                        if self.executions.is_empty() {
+                            self.finish();
+                            return Poll::Ready(Err(err));
+                        }
+                    }
+                    Poll::Pending => index += 1,
+                }
+            }
+            let due = match self.next {
+                Some(ref mut next) => Pin::new(next).poll(cx).is_ready(),
+                None => false,
+            };
+            if !due {
+                return Poll::Pending;
+            }
+            self.start(&mut launch);
+            counters.fired.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn start<L: FnMut() -> E>(&mut self, launch: &mut L) {
+        self.executions.push((self.started, launch()));
+        self.started += 1;
+        self.next = if self.started < self.policy.max_executions {
+            Some(self.clock.sleep(self.policy.delay))
+        } else {
+            None
+        };
+    }
+
+    /// Drops the executions still pending, whose answers are no longer awaited
+    fn finish(&mut self) {
+        self.executions.clear();
+        self.next = None;
+    }
+}
+
+/// The result of a statement executed by `Session::execute_speculative`.
+///
+/// It implements `std::future::Future`, so it can be `.await`ed from an async executor, or
+/// waited on with `wait`. The statement is executed when the future is first polled.
+#[derive(Debug)]
+pub struct SpeculativeFuture<'a> {
+    session: &'a Session,
+    statement: &'a Statement,
+    counters: &'a SpeculativeExecutionCounters,
+    race: Race<ResultFuture, SystemClock>,
+}
+
+impl<'a> SpeculativeFuture<'a> {
+    pub(crate) fn new(session: &'a Session,
+                      statement: &'a Statement,
+                      policy: ConstantSpeculativeExecutionPolicy,
+                      counters: &'a SpeculativeExecutionCounters)
+                      -> Self {
+        SpeculativeFuture {
+            session,
+            statement,
+            counters,
+            race: policy.race(SystemClock),
+        }
+    }
+
+    /// Blocks until an execution succeeds, or every execution failed
+    pub fn wait(self) -> Result<CassResult> { block_on(self) }
+}
+
+impl<'a> Future for SpeculativeFuture<'a> {
+    type Output = Result<CassResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (session, statement) = (this.session, this.statement);
+        this.race.poll_race(cx, this.counters, || session.execute(statement))
+    }
+}
+
+#[cfg(test)]
+mod fake {
+    use super::*;
+    use cassandra_sys::CASS_ERROR_LIB_REQUEST_TIMED_OUT;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A clock that only moves when the test advances it, a millisecond at a time
+    #[derive(Clone, Default)]
+    pub struct FakeClock(Rc<Cell<u64>>);
+
+    impl FakeClock {
+        fn now(&self) -> u64 { self.0.get() }
+
+        fn at(&self, millis: u64) -> Alarm { Alarm(self.clone(), millis) }
+    }
+
+    /// Resolves once the fake clock reaches the given millisecond
+    pub struct Alarm(FakeClock, u64);
+
+    impl Future for Alarm {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<()> {
+            if self.0.now() >= self.1 { Poll::Ready(()) } else { Poll::Pending }
+        }
+    }
+
+    impl Clock for FakeClock {
+        type Sleep = Alarm;
+
+        fn sleep(&self, delay: Duration) -> Alarm { self.at(self.now() + delay.as_millis() as u64) }
+    }
+
+    /// An execution answering with its number, or failing, once its alarm goes off
+    pub struct Execution(Alarm, u32, bool);
+
+    impl Future for Execution {
+        type Output = Result<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<u32>> {
+            match Pin::new(&mut self.0).poll(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(()) if self.2 => Poll::Ready(Ok(self.1)),
+                Poll::Ready(()) => {
+                    Poll::Ready(Err(CassError::with_message(CASS_ERROR_LIB_REQUEST_TIMED_OUT, "timed out").into()))
+                }
+            }
+        }
+    }
+
+    /// Races executions that answer the given number of milliseconds after they start, with
+    /// success or failure. Executions past the end of `answers` never answer.
+    pub fn race(policy: &ConstantSpeculativeExecutionPolicy,
+                counters: &SpeculativeExecutionCounters,
+                answers: &[(u64, bool)])
+                -> Result<u32> {
+        let clock = FakeClock::default();
+        let mut race = policy.race(clock.clone());
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut started = 0;
+        loop {
+            let launch = || {
+                let (delay, succeeds) = answers.get(started as usize).cloned().unwrap_or((u64::MAX, false));
+                let execution = Execution(clock.at(clock.now().saturating_add(delay)), started, succeeds);
+                started += 1;
+                execution
+            };
+            if let Poll::Ready(result) = race.poll_race(&mut cx, counters, launch) {
+                return result;
+            }
+            assert!(clock.now() < 10_000, "the race never ended");
+            clock.0.set(clock.now() + 1);
+        }
+    }
+}
+
+#[test]
+fn test_speculative_race() {
+    use self::fake::race;
+
+    let policy = ConstantSpeculativeExecutionPolicy {
+        delay: Duration::from_millis(20),
+        max_executions: 3,
+    };
+    let counters = SpeculativeExecutionCounters::default();
+
+    assert_eq!(race(&policy, &counters, &[(0, true)]).unwrap(), 0);
+    assert_eq!((counters.fired(), counters.won()), (0, 0));
+
+    assert_eq!(race(&policy, &counters, &[(500, true), (0, true)]).unwrap(), 1);
+    assert_eq!((counters.fired(), counters.won()), (1, 1));
+
+    assert_eq!(race(&policy, &counters, &[(500, true), (500, true), (0, true)]).unwrap(), 2);
+    assert_eq!((counters.fired(), counters.won()), (3, 2));
+
+    assert_eq!(race(&policy, &counters, &[(30, false), (100, true), (500, true)]).unwrap(), 1);
+    assert!(race(&policy, &counters, &[(0, false)]).is_err());
+    assert!(race(&policy, &counters, &[(30, false), (30, false), (30, false)]).is_err());
+    assert_eq!((counters.fired(), counters.won()), (7, 3));
+}
+
+#[test]
+fn test_system_clock() {
+    let start = Instant::now();
+    let (later, sooner) = (SystemClock.sleep(Duration::from_millis(20)), SystemClock.sleep(Duration::from_millis(10)));
+    block_on(later);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    block_on(sooner);
+
+    let mut pending = SystemClock.sleep(Duration::from_secs(60));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(Pin::new(&mut pending).poll(&mut cx).is_pending());
+    let id = pending.id.unwrap();
+    assert!(Timer::shared().lock().wakers.contains_key(&id));
+    drop(pending);
+    assert!(!Timer::shared().lock().wakers.contains_key(&id));
+}
@@ -1,15 +1,16 @@
 use cassandra::data_type::ConstDataType;
+use cassandra::error::*;
 use cassandra::statement::Statement;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 
 use cassandra_sys::CassPrepared as _PreparedStatement;
 use cassandra_sys::cass_prepared_bind;
 use cassandra_sys::cass_prepared_free;
 use cassandra_sys::cass_prepared_parameter_data_type;
-use cassandra_sys::cass_prepared_parameter_data_type_by_name;
+use cassandra_sys::cass_prepared_parameter_data_type_by_name_n;
 use cassandra_sys::cass_prepared_parameter_name;
 use std::{mem, slice, str};
-use std::ffi::CString;
+use std::os::raw::c_char;
 
 /// A statement that has been prepared against at least one Cassandra node.
 /// Instances of this class should not be created directly, but through Session.prepare().
@@ -36,16 +37,24 @@ impl Protected<*const _PreparedStatement> for PreparedStatement {
 
 impl PreparedStatement {
     /// Creates a bound statement from a pre-prepared statement.
-    pub fn bind(&self) -> Statement { unsafe { Statement::build(cass_prepared_bind(self.0)) } }
+    pub fn bind(&self) -> Statement {
+        let parameter_types = (0..self.parameter_count()).map(|index| self.parameter_data_type(index)).collect();
+        unsafe { Statement::build(cass_prepared_bind(self.0)).with_parameter_types(parameter_types) }
+    }
+
+    /// Gets the number of parameters of the statement.
+    pub fn parameter_count(&self) -> usize {
+        unsafe { (0..).take_while(|&index| !cass_prepared_parameter_data_type(self.0, index).is_null()).count() }
+    }
 
     /// Gets the name of a parameter at the specified index.
     #[allow(cast_possible_truncation)]
-    pub fn parameter_name(&self, index: usize) -> Result<&str, str::Utf8Error> {
+    pub fn parameter_name(&self, index: usize) -> Result<&str> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
-            cass_prepared_parameter_name(self.0, index, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
+            cass_prepared_parameter_name(self.0, index, &mut name, &mut name_length).wrap(())?;
+            Ok(str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))?)
         }
     }
 
@@ -63,10 +72,9 @@ impl PreparedStatement {
     /// this reference as it is bound to the lifetime of the prepared.
     pub fn parameter_data_type_by_name(&self, name: &str) -> ConstDataType {
         unsafe {
-            ConstDataType(cass_prepared_parameter_data_type_by_name(self.0,
-                                                                    CString::new(name)
-                                                                        .expect("must be utf8")
-                                                                        .as_ptr()))
+            ConstDataType(cass_prepared_parameter_data_type_by_name_n(self.0,
+                                                                      name.as_ptr() as *const c_char,
+                                                                      name.len()))
         }
     }
 }
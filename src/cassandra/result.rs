@@ -4,8 +4,9 @@
 
 use cassandra::data_type::ConstDataType;
 use cassandra::error::CassError;
-use cassandra::row::Row;
-use cassandra::util::Protected;
+use cassandra::iterator::{LendingItem, LendingIterator};
+use cassandra::row::{FromRow, Row};
+use cassandra::util::{CassErrorExt, Protected};
 
 use cassandra::value::ValueType;
 use cassandra_sys::CassIterator as _CassIterator;
@@ -28,13 +29,13 @@ use cassandra_sys::cass_result_paging_state_token;
 use cassandra_sys::cass_result_row_count;
 
 use cassandra_sys::cass_true;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
-use std::mem;
+use std::marker::PhantomData;
+use std::ptr;
 use std::slice;
 use std::str;
 
@@ -53,7 +54,8 @@ impl Protected<*const _CassResult> for CassResult {
 impl Debug for CassResult {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Result row count: {:?}\n", self.row_count())?;
-        for row in self.iter() {
+        let mut rows = self.iter();
+        while let Some(row) = rows.next() {
             write!(f, "{:?}\n", row)?;
         }
         Ok(())
@@ -63,7 +65,8 @@ impl Debug for CassResult {
 impl Display for CassResult {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Result row count: {}\n", self.row_count())?;
-        for row in self.iter() {
+        let mut rows = self.iter();
+        while let Some(row) = rows.next() {
             write!(f, "{}\n", row)?;
         }
         Ok(())
@@ -85,15 +88,7 @@ impl CassResult {
     pub fn column_count(&self) -> u64 { unsafe { cass_result_column_count(self.0) as u64 } }
 
     /// Gets the column name at index for the specified result.
-    pub fn column_name(&self, index: usize) -> String {
-        unsafe {
-            let name = mem::zeroed();
-            let name_length = mem::zeroed();
-            cass_result_column_name(self.0, index, name, name_length);
-            let slice = slice::from_raw_parts(name as *const u8, name_length as usize);
-            str::from_utf8(slice).expect("must be utf8").to_owned()
-        }
-    }
+    pub fn column_name(&self, index: usize) -> Result<String> { column_name(self.0, index) }
 
     /// Gets the column type at index for the specified result.
     pub fn column_type(&self, index: usize) -> ValueType {
@@ -106,11 +101,11 @@ impl CassResult {
     }
 
     /// Gets the first row of the result.
-    pub fn first_row(&self) -> Option<Row> {
+    pub fn first_row(&self) -> Option<Row<'_>> {
         unsafe {
             match self.row_count() {
                 0 => None,
-                _ => Some(Row::build(cass_result_first_row(self.0))),
+                _ => Some(Row::build(cass_result_first_row(self.0)).with_result(self.0)),
             }
         }
     }
@@ -118,39 +113,51 @@ impl CassResult {
     /// Returns true if there are more pages.
     pub fn has_more_pages(&self) -> bool { unsafe { cass_result_has_more_pages(self.0) == cass_true } }
 
-    /// Sets the statement's paging state. This can be used to get the next page of
-    /// data in a multi-page query.
+    /// Gets the raw paging state token of the result, or `None` if this is the last page.
+    /// The token is opaque binary data; pass it to `Statement::set_paging_state_token` to
+    /// resume the query from the next page, e.g. from a stateless cursor.
     ///
     /// <b>Warning:</b> The paging state should not be exposed to or come from
     /// untrusted environments. The paging state could be spoofed and potentially
-    // used to gain access to other data.
-    pub fn set_paging_state_token(&mut self, paging_state: &str) -> Result<&mut Self> {
+    /// used to gain access to other data.
+    pub fn paging_state_token(&self) -> Result<Option<Vec<u8>>> {
+        if !self.has_more_pages() {
+            return Ok(None);
+        }
         unsafe {
-            let state = CString::new(paging_state).expect("must be utf8");
-
-            cass_result_paging_state_token(self.0, &mut state.as_ptr(), &mut (state.to_bytes().len()))
-                .to_result(self)
-                .chain_err(|| "")
+            let mut token = ptr::null();
+            let mut token_length = 0;
+            cass_result_paging_state_token(self.0, &mut token, &mut token_length).wrap(())?;
+            Ok(Some(slice::from_raw_parts(token as *const u8, token_length).to_vec()))
         }
     }
 
 
     /// Creates a new iterator for the specified result. This can be
     /// used to iterate over rows in the result.
-    pub fn iter(&self) -> ResultIterator { unsafe { ResultIterator(cass_iterator_from_result(self.0)) } }
+    pub fn iter(&self) -> ResultIterator<'_> {
+        unsafe { ResultIterator(cass_iterator_from_result(self.0), self.0, PhantomData) }
+    }
+
+    /// Maps every row of the result into `T`, failing on the first row that does not map
+    pub fn rows_as<T: FromRow>(&self) -> Result<Vec<T>> { self.iter().map(|row| T::from_row(&row)).collect() }
 }
 
-/// An iterator over the results of a query
+/// An iterator over the rows of a result. Each row is decoded in place, so it borrows the
+/// iterator and has to be dropped before the next one is read.
 #[derive(Debug)]
-pub struct ResultIterator(pub *mut _CassIterator);
+pub struct ResultIterator<'a>(pub *mut _CassIterator, *const _CassResult, PhantomData<&'a CassResult>);
 
-impl Drop for ResultIterator {
+impl<'a> Drop for ResultIterator<'a> {
     fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
 }
 
-impl Iterator for ResultIterator {
-    type Item = Row;
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+impl<'i, 'a> LendingItem<'i> for ResultIterator<'a> {
+    type Item = Row<'i>;
+}
+
+impl<'a> LendingIterator for ResultIterator<'a> {
+    fn next(&mut self) -> Option<Row<'_>> {
         unsafe {
             match cass_iterator_next(self.0) {
                 cass_false => None,
@@ -160,23 +167,18 @@ impl Iterator for ResultIterator {
     }
 }
 
-impl ResultIterator {
+impl<'a> ResultIterator<'a> {
     /// Gets the next row in the result set
-    pub fn get_row(&mut self) -> Row { unsafe { Row::build(cass_iterator_get_row(self.0)) } }
+    pub fn get_row(&mut self) -> Row<'_> { unsafe { Row::build(cass_iterator_get_row(self.0)).with_result(self.1) } }
 }
 
-impl IntoIterator for CassResult {
-    type Item = Row;
-    type IntoIter = ResultIterator;
-
-    fn into_iter(self) -> Self::IntoIter { self.iter() }
+/// Gets the name of the column at `index` of a result
+pub(crate) fn column_name(result: *const _CassResult, index: usize) -> Result<String> {
+    unsafe {
+        let mut name = ptr::null();
+        let mut name_length = 0;
+        cass_result_column_name(result, index, &mut name, &mut name_length).wrap(())?;
+        let slice = slice::from_raw_parts(name as *const u8, name_length);
+        Ok(str::from_utf8(slice)?.to_owned())
+    }
 }
-
-// impl<'a> IntoIterator for &'a CassandraResult {
-//    type Item = Row;
-//    type IntoIter = ResultIterator;
-//
-//    fn into_iter(self) -> Self::IntoIter {unsafe{
-//        ResultIterator(cass_iterator_from_result(self.0))
-//    }}
-// }
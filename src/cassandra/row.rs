@@ -1,11 +1,15 @@
 use cassandra::column::Column;
+use cassandra::cql_value::CqlValue;
 use cassandra::error::*;
 
 use cassandra::iterator::{MapIterator, SetIterator};
+use cassandra::result::column_name;
 use cassandra::util::Protected;
-use cassandra::value::Value;
+use cassandra::value::{FromValue, Value};
 use cassandra_sys::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
+use cassandra_sys::CASS_ERROR_LIB_NULL_VALUE;
 use cassandra_sys::CassIterator as _CassIterator;
+use cassandra_sys::CassResult as _CassResult;
 use cassandra_sys::CassRow as _Row;
 use cassandra_sys::cass_false;
 use cassandra_sys::cass_iterator_free;
@@ -13,26 +17,27 @@ use cassandra_sys::cass_iterator_from_row;
 use cassandra_sys::cass_iterator_get_column;
 use cassandra_sys::cass_iterator_next;
 use cassandra_sys::cass_row_get_column;
-use cassandra_sys::cass_row_get_column_by_name;
+use cassandra_sys::cass_row_get_column_by_name_n;
 use cassandra_sys::cass_true;
-use errors::*;
-use std::ffi::CString;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::iter;
 use std::iter::IntoIterator;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::ptr;
 
-/// A collection of column values.
-pub struct Row(*const _Row);
+/// A collection of column values, borrowed from the result or result iterator it was read from.
+pub struct Row<'a>(*const _Row, *const _CassResult, PhantomData<&'a _CassResult>);
 
-impl Protected<*const _Row> for Row {
+impl<'a> Protected<*const _Row> for Row<'a> {
     fn inner(&self) -> *const _Row { self.0 }
-    fn build(inner: *const _Row) -> Self { Row(inner) }
+    fn build(inner: *const _Row) -> Self { Row(inner, ptr::null(), PhantomData) }
 }
 
-impl Debug for Row {
+impl<'a> Debug for Row<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for column in self {
             write!(f, "{:?}\t", Value::build(column.inner()))?;
@@ -41,7 +46,7 @@ impl Debug for Row {
     }
 }
 
-impl Display for Row {
+impl<'a> Display for Row<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for column in self {
             write!(f, "{}\t", Value::build(column.inner()))?;
@@ -60,7 +65,7 @@ pub trait AsRustType<T> {
         where S: Into<String>;
 }
 
-impl AsRustType<bool> for Row {
+impl<'a> AsRustType<bool> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<bool> {
         let col = self.get_column(index)?;
         col.get_bool()
@@ -72,7 +77,7 @@ impl AsRustType<bool> for Row {
     }
 }
 
-impl AsRustType<String> for Row {
+impl<'a> AsRustType<String> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<String> {
         let col = self.get_column(index)?;
         col.get_string()
@@ -85,7 +90,20 @@ impl AsRustType<String> for Row {
     }
 }
 
-impl AsRustType<f64> for Row {
+impl<'a> AsRustType<&'a str> for Row<'a> {
+    fn get_col(&self, index: usize) -> Result<&'a str> {
+        let col = self.get_column(index)?;
+        col.get_str()
+    }
+
+    fn get_col_by_name<S>(&self, name: S) -> Result<&'a str>
+        where S: Into<String> {
+        let col = self.get_column_by_name(name)?;
+        col.get_str()
+    }
+}
+
+impl<'a> AsRustType<f64> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<f64> {
         let col = self.get_column(index)?;
         col.get_double()
@@ -98,7 +116,7 @@ impl AsRustType<f64> for Row {
     }
 }
 
-impl AsRustType<f32> for Row {
+impl<'a> AsRustType<f32> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<f32> {
         let col = self.get_column(index)?;
         col.get_float()
@@ -111,7 +129,7 @@ impl AsRustType<f32> for Row {
     }
 }
 
-impl AsRustType<i64> for Row {
+impl<'a> AsRustType<i64> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<i64> {
         let col = self.get_column(index)?;
         col.get_i64()
@@ -124,7 +142,7 @@ impl AsRustType<i64> for Row {
     }
 }
 
-impl AsRustType<i32> for Row {
+impl<'a> AsRustType<i32> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<i32> {
         let col = self.get_column(index)?;
         col.get_i32()
@@ -137,33 +155,33 @@ impl AsRustType<i32> for Row {
     }
 }
 
-impl AsRustType<SetIterator> for Row {
-    fn get_col(&self, index: usize) -> Result<SetIterator> {
+impl<'a> AsRustType<SetIterator<'a>> for Row<'a> {
+    fn get_col(&self, index: usize) -> Result<SetIterator<'a>> {
         let col = self.get_column(index)?;
         col.set_iter()
     }
 
-    fn get_col_by_name<S>(&self, name: S) -> Result<SetIterator>
+    fn get_col_by_name<S>(&self, name: S) -> Result<SetIterator<'a>>
         where S: Into<String> {
         let col = self.get_column_by_name(name)?;
         col.set_iter()
     }
 }
 
-impl AsRustType<MapIterator> for Row {
-    fn get_col(&self, index: usize) -> Result<MapIterator> {
+impl<'a> AsRustType<MapIterator<'a>> for Row<'a> {
+    fn get_col(&self, index: usize) -> Result<MapIterator<'a>> {
         let col = self.get_column(index)?;
         col.map_iter()
     }
 
-    fn get_col_by_name<S>(&self, name: S) -> Result<MapIterator>
+    fn get_col_by_name<S>(&self, name: S) -> Result<MapIterator<'a>>
         where S: Into<String> {
         let col = self.get_column_by_name(name)?;
         col.map_iter()
     }
 }
 
-impl AsRustType<Vec<u8>> for Row {
+impl<'a> AsRustType<Vec<u8>> for Row<'a> {
     fn get_col(&self, index: usize) -> Result<Vec<u8>> {
         let col = self.get_column(index)?;
         col.get_blob()
@@ -176,13 +194,90 @@ impl AsRustType<Vec<u8>> for Row {
     }
 }
 
-impl Row {
+impl<'a> AsRustType<&'a [u8]> for Row<'a> {
+    fn get_col(&self, index: usize) -> Result<&'a [u8]> {
+        let col = self.get_column(index)?;
+        col.get_bytes()
+    }
+
+    fn get_col_by_name<S>(&self, name: S) -> Result<&'a [u8]>
+        where S: Into<String> {
+        let col = self.get_column_by_name(name)?;
+        col.get_bytes()
+    }
+}
+
+/// Mapping of a whole row into a Rust type, usually implemented with `#[derive(FromRow)]`
+///
+/// ```ignore
+/// #[derive(FromRow)]
+/// struct User {
+///     id: Uuid,
+///     #[cass(rename = "user_name")]
+///     name: String,
+///     email: Option<String>,
+///     tags: Vec<String>,
+/// }
+///
+/// let users: Vec<User> = session.execute(&stmt!("SELECT * FROM users")).wait()?.rows_as()?;
+/// ```
+///
+/// The derive maps each field to the column of the same name. It also implements `FromValue`,
+/// so the same struct can be used as a field of another one to read a user defined type.
+/// Fields are found by name, so the derive is refused for a tuple struct:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate cassandra;
+///
+/// #[derive(FromRow)]
+/// struct Point(i32, i32);
+/// # fn main() {}
+/// ```
+pub trait FromRow: Sized {
+    /// Builds the type from the columns of a row
+    fn from_row(row: &Row) -> Result<Self>;
+}
+
+impl<'a> Row<'a> {
+    /// Records the result the row belongs to, which holds its column names
+    pub(crate) fn with_result(mut self, result: *const _CassResult) -> Self {
+        self.1 = result;
+        self
+    }
+
+    /// Copies every column of the row, paired with its name, into values that outlive the result.
+    pub fn to_values(&self) -> Result<Vec<(String, CqlValue)>> {
+        if self.1.is_null() {
+            return Err(CassError::with_message(CASS_ERROR_LIB_NULL_VALUE, "the row has no column names".to_owned())
+                .into());
+        }
+        self.into_iter()
+            .enumerate()
+            .map(|(index, column)| {
+                Ok((column_name(self.1, index)?, CqlValue::from_value(&Value::build(column.inner()))?))
+            })
+            .collect()
+    }
+
+    /// Gets the column at `index` as any type that can be read from a value
+    pub fn get<T: FromValue>(&self, index: usize) -> Result<T> {
+        T::from_value(&Value::build(self.get_column(index)?.inner()))
+    }
+
+    /// Gets the column named `name` as any type that can be read from a value
+    pub fn get_by_name<T, S>(&self, name: S) -> Result<T>
+        where T: FromValue,
+              S: Into<String> {
+        T::from_value(&Value::build(self.get_column_by_name(name)?.inner()))
+    }
+
     /// Get a particular column by index
-    pub fn get_column(&self, index: usize) -> Result<Column> {
+    pub fn get_column(&self, index: usize) -> Result<Column<'a>> {
         unsafe {
             let col = cass_row_get_column(self.0, index);
             if col.is_null() {
-                Err("LIB_INDEX_OUT_OF_BOUNDS".into())
+                Err(CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS.into())
             } else {
                 Ok(Column::build(col))
             }
@@ -190,13 +285,13 @@ impl Row {
     }
 
     /// Get a particular column by name
-    pub fn get_column_by_name<S>(&self, name: S) -> Result<Column>
+    pub fn get_column_by_name<S>(&self, name: S) -> Result<Column<'a>>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            let col = cass_row_get_column_by_name(self.0,
-                                                  CString::new(name.into()).expect("must be utf8").as_ptr());
+            let col = cass_row_get_column_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if col.is_null() {
-                Err("LIB_INDEX_OUT_OF_BOUNDS".into())
+                Err(CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS.into())
             } else {
                 Ok(Column::build(col))
             }
@@ -206,15 +301,15 @@ impl Row {
 
 /// An iterator over the columns in a row
 #[derive(Debug)]
-pub struct RowIterator(pub *mut _CassIterator);
+pub struct RowIterator<'a>(pub *mut _CassIterator, PhantomData<&'a _Row>);
 
 
-impl Drop for RowIterator {
+impl<'a> Drop for RowIterator<'a> {
     fn drop(&mut self) { unsafe { cass_iterator_free(self.0) } }
 }
 
-impl iter::Iterator for RowIterator {
-    type Item = Column;
+impl<'a> iter::Iterator for RowIterator<'a> {
+    type Item = Column<'a>;
 
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
         unsafe {
@@ -226,8 +321,8 @@ impl iter::Iterator for RowIterator {
     }
 }
 
-impl<'a> Iterator for &'a RowIterator {
-    type Item = Column;
+impl<'a> Iterator for &RowIterator<'a> {
+    type Item = Column<'a>;
 
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
         unsafe {
@@ -239,7 +334,7 @@ impl<'a> Iterator for &'a RowIterator {
     }
 }
 
-impl Display for RowIterator {
+impl<'a> Display for RowIterator<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for item in self {
             write!(f, "{}\t", Value::build(item.inner()))?;
@@ -248,17 +343,17 @@ impl Display for RowIterator {
     }
 }
 
-impl IntoIterator for Row {
-    type Item = Column;
-    type IntoIter = RowIterator;
+impl<'a> IntoIterator for Row<'a> {
+    type Item = Column<'a>;
+    type IntoIter = RowIterator<'a>;
 
     /// Creates a new iterator for the specified row. This can be
     /// used to iterate over columns in a row.
-    fn into_iter(self) -> Self::IntoIter { unsafe { RowIterator(cass_iterator_from_row(self.0)) } }
+    fn into_iter(self) -> Self::IntoIter { unsafe { RowIterator(cass_iterator_from_row(self.0), PhantomData) } }
 }
 
-impl<'a> IntoIterator for &'a Row {
-    type Item = Column;
-    type IntoIter = RowIterator;
-    fn into_iter(self) -> Self::IntoIter { unsafe { RowIterator(cass_iterator_from_row(self.0)) } }
+impl<'a> IntoIterator for &Row<'a> {
+    type Item = Column<'a>;
+    type IntoIter = RowIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter { unsafe { RowIterator(cass_iterator_from_row(self.0), PhantomData) } }
 }
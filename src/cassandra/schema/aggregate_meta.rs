@@ -1,14 +1,15 @@
 use cassandra::data_type::ConstDataType;
+use cassandra::error::*;
 use cassandra::iterator::FieldIterator;
 
 use cassandra::schema::function_meta::FunctionMeta;
-use cassandra::util::Protected;
+use cassandra::util::{Protected, to_owned_string};
 use cassandra::value::Value;
 
 use cassandra_sys::CassAggregateMeta as _CassAggregateMeta;
 use cassandra_sys::cass_aggregate_meta_argument_count;
 use cassandra_sys::cass_aggregate_meta_argument_type;
-use cassandra_sys::cass_aggregate_meta_field_by_name;
+use cassandra_sys::cass_aggregate_meta_field_by_name_n;
 use cassandra_sys::cass_aggregate_meta_final_func;
 use cassandra_sys::cass_aggregate_meta_full_name;
 use cassandra_sys::cass_aggregate_meta_init_cond;
@@ -17,9 +18,8 @@ use cassandra_sys::cass_aggregate_meta_return_type;
 use cassandra_sys::cass_aggregate_meta_state_func;
 use cassandra_sys::cass_aggregate_meta_state_type;
 use cassandra_sys::cass_iterator_fields_from_aggregate_meta;
-use cassandra_sys::raw2utf8;
-use std::ffi::CString;
 use std::mem;
+use std::os::raw::c_char;
 
 
 /// Metadata about a cassandra aggregate
@@ -33,28 +33,28 @@ impl Protected<*const _CassAggregateMeta> for AggregateMeta {
 
 impl AggregateMeta {
     /// An iterator over the fields of an aggregate
-    pub fn fields_iter(&self) -> FieldIterator {
+    pub fn fields_iter(&self) -> FieldIterator<'_> {
         unsafe { FieldIterator::build(cass_iterator_fields_from_aggregate_meta(self.0)) }
     }
 
 
     /// Gets the name of the aggregate.
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_aggregate_meta_name(self.0, &mut name, &mut name_length);
-            raw2utf8(name, name_length).expect("must be utf8")
+            to_owned_string(name, name_length)
         }
     }
 
     /// Gets the full name of the aggregate.
-    pub fn full_name(&self) -> String {
+    pub fn full_name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_aggregate_meta_full_name(self.0, &mut name, &mut name_length);
-            raw2utf8(name, name_length).expect("must be utf8")
+            to_owned_string(name, name_length)
         }
     }
 
@@ -79,13 +79,13 @@ impl AggregateMeta {
     pub fn final_func(&self) -> FunctionMeta { unsafe { FunctionMeta::build(cass_aggregate_meta_final_func(self.0)) } }
 
     ///  Gets the initial condition value for the aggregate.
-    pub fn init_cond(&self) -> Value { unsafe { Value::build(cass_aggregate_meta_init_cond(self.0)) } }
+    pub fn init_cond(&self) -> Value<'_> { unsafe { Value::build(cass_aggregate_meta_init_cond(self.0)) } }
 
     ///  Gets a metadata field for the provided name. Metadata fields allow direct
     /// access to the column data found in the underlying "aggregates" metadata table.
-    pub fn field_by_name(&self, name: &str) -> Option<Value> {
+    pub fn field_by_name(&self, name: &str) -> Option<Value<'_>> {
         unsafe {
-            let agg = cass_aggregate_meta_field_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr());
+            let agg = cass_aggregate_meta_field_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if agg.is_null() {
                 None
             } else {
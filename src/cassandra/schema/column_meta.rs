@@ -1,12 +1,13 @@
 use cassandra::data_type::ConstDataType;
+use cassandra::error::*;
 
 use cassandra::iterator::FieldIterator;
-use cassandra::util::Protected;
+use cassandra::util::{Protected, to_owned_string};
 use cassandra::value::Value;
 use cassandra_sys::CassColumnMeta as _CassColumnMeta;
 use cassandra_sys::CassColumnType as _CassColumnType;
 use cassandra_sys::cass_column_meta_data_type;
-use cassandra_sys::cass_column_meta_field_by_name;
+use cassandra_sys::cass_column_meta_field_by_name_n;
 use cassandra_sys::cass_column_meta_name;
 use cassandra_sys::cass_column_meta_type;
 use cassandra_sys::cass_iterator_fields_from_column_meta;
@@ -15,9 +16,8 @@ use cassandra_sys::cass_iterator_fields_from_column_meta;
 #[derive(Debug)]
 pub struct ColumnMeta(*const _CassColumnMeta);
 
-use std::ffi::CString;
 use std::mem;
-use std::slice;
+use std::os::raw::c_char;
 use std::str;
 
 impl Protected<*const _CassColumnMeta> for ColumnMeta {
@@ -28,19 +28,18 @@ impl Protected<*const _CassColumnMeta> for ColumnMeta {
 
 impl ColumnMeta {
     /// returns an iterator over the fields of this column
-    pub fn field_iter(&mut self) -> FieldIterator {
+    pub fn field_iter(&mut self) -> FieldIterator<'_> {
         unsafe { FieldIterator::build(cass_iterator_fields_from_column_meta(self.0)) }
     }
 
     /// Gets the name of the column.
     #[allow(cast_possible_truncation)]
-    pub fn name(&self) -> String {
+    pub fn name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_column_meta_name(self.0, &mut name, &mut name_length);
-            let slice = slice::from_raw_parts(name as *const u8, name_length as usize);
-            str::from_utf8(slice).expect("must be utf8").to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
@@ -52,9 +51,9 @@ impl ColumnMeta {
 
     /// Gets a metadata field for the provided name. Metadata fields allow direct
     /// access to the column data found in the underlying "columns" metadata table.
-    pub fn field_by_name(&self, name: &str) -> Option<Value> {
+    pub fn field_by_name(&self, name: &str) -> Option<Value<'_>> {
         unsafe {
-            let field = cass_column_meta_field_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr());
+            let field = cass_column_meta_field_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if field.is_null() {
                 None
             } else {
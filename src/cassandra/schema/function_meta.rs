@@ -4,26 +4,26 @@ use cassandra::data_type::ConstDataType;
 use cassandra::error::CassError;
 
 use cassandra::iterator::FieldIterator;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected, to_owned_string};
 use cassandra::value::Value;
 use cassandra_sys::CASS_OK;
 use cassandra_sys::CassFunctionMeta as _CassFunctionMeta;
 use cassandra_sys::cass_function_meta_argument;
 use cassandra_sys::cass_function_meta_argument_count;
-use cassandra_sys::cass_function_meta_argument_type_by_name;
+use cassandra_sys::cass_function_meta_argument_type_by_name_n;
 use cassandra_sys::cass_function_meta_body;
 use cassandra_sys::cass_function_meta_called_on_null_input;
-use cassandra_sys::cass_function_meta_field_by_name;
+use cassandra_sys::cass_function_meta_field_by_name_n;
 use cassandra_sys::cass_function_meta_full_name;
 use cassandra_sys::cass_function_meta_language;
 use cassandra_sys::cass_function_meta_name;
 use cassandra_sys::cass_function_meta_return_type;
 use cassandra_sys::cass_iterator_fields_from_function_meta;
 use cassandra_sys::cass_true;
-use errors::*;
+use cassandra::error::*;
 
-use std::{mem, slice, str};
-use std::ffi::CString;
+use std::{mem, str};
+use std::os::raw::c_char;
 /// The metadata for a function
 #[derive(Debug)]
 pub struct FunctionMeta(*const _CassFunctionMeta);
@@ -35,20 +35,18 @@ impl Protected<*const _CassFunctionMeta> for FunctionMeta {
 
 impl FunctionMeta {
     /// Iterator over the fields in this function
-    pub fn fields_iter(&self) -> FieldIterator {
+    pub fn fields_iter(&self) -> FieldIterator<'_> {
         unsafe { FieldIterator::build(cass_iterator_fields_from_function_meta(self.0)) }
     }
 
     /// Gets the name of the function.
     #[allow(cast_possible_truncation)]
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_function_meta_name(self.0, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                .expect("must be utf8")
-                .to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
@@ -56,40 +54,34 @@ impl FunctionMeta {
     /// function's name and the function's signature:
     /// "name(type1 type2.. typeN)".
     #[allow(cast_possible_truncation)]
-    pub fn full_name(&self) -> String {
+    pub fn full_name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_function_meta_full_name(self.0, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                .expect("must be utf8")
-                .to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
     /// Gets the body of the function.
     #[allow(cast_possible_truncation)]
-    pub fn body(&self) -> String {
+    pub fn body(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_function_meta_body(self.0, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                .expect("must be utf8")
-                .to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
     /// Gets the language of the function.
     #[allow(cast_possible_truncation)]
-    pub fn language(&self) -> String {
+    pub fn language(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_function_meta_language(self.0, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                .expect("must be utf8")
-                .to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
@@ -110,7 +102,7 @@ impl FunctionMeta {
 
             match cass_function_meta_argument(self.0, index, &mut name, &mut name_length, &mut data_type) {
                 CASS_OK => Ok(()),
-                err => err.to_result(()).chain_err(|| ""),
+                err => err.wrap(()),
             }
         }
     }
@@ -118,10 +110,9 @@ impl FunctionMeta {
     /// Gets the function's argument and type for the provided name.
     pub fn argument_type_by_name(&self, name: &str) -> ConstDataType {
         unsafe {
-            ConstDataType(cass_function_meta_argument_type_by_name(self.0,
-                                                                   CString::new(name)
-                                                                       .expect("must be utf8")
-                                                                       .as_ptr()))
+            ConstDataType(cass_function_meta_argument_type_by_name_n(self.0,
+                                                                     name.as_ptr() as *const c_char,
+                                                                     name.len()))
         }
     }
 
@@ -130,9 +121,9 @@ impl FunctionMeta {
 
     /// Gets a metadata field for the provided name. Metadata fields allow direct
     /// access to the column data found in the underlying "functions" metadata table.
-    pub fn field_by_name(&self, name: &str) -> Value {
+    pub fn field_by_name(&self, name: &str) -> Value<'_> {
         unsafe {
-            Value::build(cass_function_meta_field_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr()))
+            Value::build(cass_function_meta_field_by_name_n(self.0, name.as_ptr() as *const c_char, name.len()))
         }
     }
 }
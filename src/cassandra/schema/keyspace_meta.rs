@@ -1,4 +1,5 @@
 use cassandra::data_type::ConstDataType;
+use cassandra::error::*;
 use cassandra::iterator::AggregateIterator;
 use cassandra::iterator::FieldIterator;
 use cassandra::iterator::FunctionIterator;
@@ -8,7 +9,7 @@ use cassandra::schema::aggregate_meta::AggregateMeta;
 
 use cassandra::schema::function_meta::FunctionMeta;
 use cassandra::schema::table_meta::TableMeta;
-use cassandra::util::Protected;
+use cassandra::util::{Protected, to_owned_string};
 
 use cassandra_sys::CassKeyspaceMeta as _CassKeyspaceMeta;
 use cassandra_sys::CassValue as _CassValue;
@@ -18,15 +19,14 @@ use cassandra_sys::cass_iterator_functions_from_keyspace_meta;
 
 use cassandra_sys::cass_iterator_tables_from_keyspace_meta;
 use cassandra_sys::cass_iterator_user_types_from_keyspace_meta;
-use cassandra_sys::cass_keyspace_meta_aggregate_by_name;
-use cassandra_sys::cass_keyspace_meta_field_by_name;
-use cassandra_sys::cass_keyspace_meta_function_by_name;
+use cassandra_sys::cass_keyspace_meta_aggregate_by_name_n;
+use cassandra_sys::cass_keyspace_meta_field_by_name_n;
+use cassandra_sys::cass_keyspace_meta_function_by_name_n;
 use cassandra_sys::cass_keyspace_meta_name;
-use cassandra_sys::cass_keyspace_meta_table_by_name;
-use cassandra_sys::cass_keyspace_meta_user_type_by_name;
-use cassandra_sys::raw2utf8;
-use std::ffi::CString;
+use cassandra_sys::cass_keyspace_meta_table_by_name_n;
+use cassandra_sys::cass_keyspace_meta_user_type_by_name_n;
 use std::mem;
+use std::os::raw::c_char;
 
 /// A snapshot of the schema's metadata.
 #[derive(Debug)]
@@ -47,14 +47,14 @@ impl KeyspaceMeta {
     }
 
     /// Iterator over the field in this keyspace
-    pub fn fields_iter(&self) -> FieldIterator {
+    pub fn fields_iter(&self) -> FieldIterator<'_> {
         unsafe { FieldIterator::build(cass_iterator_fields_from_keyspace_meta(self.0)) }
     }
 
     /// Gets the table metadata for the provided table name.
     pub fn table_by_name(&self, name: &str) -> Option<TableMeta> {
         unsafe {
-            let value = cass_keyspace_meta_table_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr());
+            let value = cass_keyspace_meta_table_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if value.is_null() {
                 None
             } else {
@@ -66,8 +66,7 @@ impl KeyspaceMeta {
     /// Gets the data type for the provided type name.
     pub fn user_type_by_name(&self, name: &str) -> Option<ConstDataType> {
         unsafe {
-            let value = cass_keyspace_meta_user_type_by_name(self.0,
-                                                             CString::new(name).expect("must be utf8").as_ptr());
+            let value = cass_keyspace_meta_user_type_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if value.is_null() {
                 None
             } else {
@@ -79,11 +78,12 @@ impl KeyspaceMeta {
     /// Gets the function metadata for the provided function name.
     pub fn get_function_by_name(&self, name: &str, arguments: Vec<&str>) -> Option<FunctionMeta> {
         unsafe {
-            let value = cass_keyspace_meta_function_by_name(self.0,
-                                                            CString::new(name).expect("must be utf8").as_ptr(),
-                                                            CString::new(arguments.join(","))
-                                                                .expect("must be utf8")
-                                                                .as_ptr());
+            let arguments = arguments.join(",");
+            let value = cass_keyspace_meta_function_by_name_n(self.0,
+                                                              name.as_ptr() as *const c_char,
+                                                              name.len(),
+                                                              arguments.as_ptr() as *const c_char,
+                                                              arguments.len());
             if value.is_null() {
                 None
             } else {
@@ -95,11 +95,12 @@ impl KeyspaceMeta {
     /// Gets the aggregate metadata for the provided aggregate name.
     pub fn aggregate_by_name(&self, name: &str, arguments: Vec<&str>) -> Option<AggregateMeta> {
         unsafe {
-            let agg = cass_keyspace_meta_aggregate_by_name(self.0,
-                                                           CString::new(name).expect("must be utf8").as_ptr(),
-                                                           CString::new(arguments.join(","))
-                                                               .expect("must be utf8")
-                                                               .as_ptr());
+            let arguments = arguments.join(",");
+            let agg = cass_keyspace_meta_aggregate_by_name_n(self.0,
+                                                             name.as_ptr() as *const c_char,
+                                                             name.len(),
+                                                             arguments.as_ptr() as *const c_char,
+                                                             arguments.len());
             if agg.is_null() {
                 None
             } else {
@@ -124,12 +125,12 @@ impl KeyspaceMeta {
     }
 
     /// Gets the name of the keyspace.
-    pub fn name(&self) -> String {
+    pub fn name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_keyspace_meta_name(self.0, &mut name, &mut name_length);
-            raw2utf8(name, name_length).expect("must be utf8")
+            to_owned_string(name, name_length)
         }
     }
 
@@ -137,7 +138,7 @@ impl KeyspaceMeta {
     /// access to the column data found in the underlying "keyspaces" metadata table.
     pub fn field_by_name(&self, name: &str) -> Option<MetadataFieldValue> {
         unsafe {
-            let value = cass_keyspace_meta_field_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr());
+            let value = cass_keyspace_meta_field_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if value.is_null() {
                 None
             } else {
@@ -5,8 +5,9 @@ use cassandra::util::Protected;
 use cassandra_sys::CassSchemaMeta as _CassSchemaMeta;
 use cassandra_sys::cass_iterator_keyspaces_from_schema_meta;
 use cassandra_sys::cass_schema_meta_free;
-use cassandra_sys::cass_schema_meta_keyspace_by_name;
+use cassandra_sys::cass_schema_meta_keyspace_by_name_n;
 use cassandra_sys::cass_schema_meta_snapshot_version;
+use std::os::raw::c_char;
 
 /// A snapshot of the schema's metadata
 #[derive(Debug)]
@@ -31,7 +32,11 @@ impl SchemaMeta {
 
     /// Gets the keyspace metadata for the provided keyspace name.
     pub fn get_keyspace_by_name(&self, keyspace: &str) -> KeyspaceMeta {
-        unsafe { KeyspaceMeta::build(cass_schema_meta_keyspace_by_name(self.0, keyspace.as_ptr() as *const i8)) }
+        unsafe {
+            KeyspaceMeta::build(cass_schema_meta_keyspace_by_name_n(self.0,
+                                                                    keyspace.as_ptr() as *const c_char,
+                                                                    keyspace.len()))
+        }
     }
 
     /// Returns an iterator over the keyspaces in this schema
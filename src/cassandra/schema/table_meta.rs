@@ -1,8 +1,9 @@
+use cassandra::error::*;
 use cassandra::iterator::ColumnIterator;
 use cassandra::iterator::FieldIterator;
 
 use cassandra::schema::column_meta::ColumnMeta;
-use cassandra::util::Protected;
+use cassandra::util::{Protected, to_owned_string};
 use cassandra::value::Value;
 use cassandra_sys::CassTableMeta as _CassTableMeta;
 use cassandra_sys::cass_iterator_columns_from_table_meta;
@@ -10,14 +11,14 @@ use cassandra_sys::cass_iterator_fields_from_table_meta;
 use cassandra_sys::cass_table_meta_clustering_key;
 use cassandra_sys::cass_table_meta_clustering_key_count;
 use cassandra_sys::cass_table_meta_column;
-use cassandra_sys::cass_table_meta_column_by_name;
+use cassandra_sys::cass_table_meta_column_by_name_n;
 use cassandra_sys::cass_table_meta_column_count;
-use cassandra_sys::cass_table_meta_field_by_name;
+use cassandra_sys::cass_table_meta_field_by_name_n;
 use cassandra_sys::cass_table_meta_name;
 use cassandra_sys::cass_table_meta_partition_key;
 use cassandra_sys::cass_table_meta_partition_key_count;
 use std::mem;
-use std::slice;
+use std::os::raw::c_char;
 
 use std::str;
 
@@ -32,7 +33,7 @@ impl Protected<*const _CassTableMeta> for TableMeta {
 
 impl TableMeta {
     /// returns an iterator over the fields of this table
-    pub fn field_iter(&mut self) -> FieldIterator {
+    pub fn field_iter(&mut self) -> FieldIterator<'_> {
         unsafe { FieldIterator::build(cass_iterator_fields_from_table_meta(self.0)) }
     }
 
@@ -43,19 +44,19 @@ impl TableMeta {
 
     /// Gets the column metadata for the provided column name.
     pub fn column_by_name(&self, name: &str) -> ColumnMeta {
-        unsafe { ColumnMeta::build(cass_table_meta_column_by_name(self.0, name.as_ptr() as *const i8)) }
+        unsafe {
+            ColumnMeta::build(cass_table_meta_column_by_name_n(self.0, name.as_ptr() as *const c_char, name.len()))
+        }
     }
 
     /// Gets the name of the table.
     #[allow(cast_possible_truncation)]
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self) -> Result<String> {
         unsafe {
             let mut name = mem::zeroed();
             let mut name_length = mem::zeroed();
             cass_table_meta_name(self.0, &mut name, &mut name_length);
-            str::from_utf8(slice::from_raw_parts(name as *const u8, name_length as usize))
-                .expect("must be utf8")
-                .to_owned()
+            to_owned_string(name, name_length)
         }
     }
 
@@ -100,10 +101,10 @@ impl TableMeta {
 
     /// Gets a metadata field for the provided name. Metadata fields allow direct
     /// access to the column data found in the underlying "tables" metadata table.
-    pub fn field_by_name(&self, name: &str) -> Option<Value> {
+    pub fn field_by_name(&self, name: &str) -> Option<Value<'_>> {
         // fixme replace CassValule with a custom type
         unsafe {
-            let value = cass_table_meta_field_by_name(self.0, name.as_ptr() as *const i8);
+            let value = cass_table_meta_field_by_name_n(self.0, name.as_ptr() as *const c_char, name.len());
             if value.is_null() {
                 None
             } else {
@@ -0,0 +1,249 @@
+//! Serde support, enabled with the `serde` cargo feature.
+//!
+//! Rows serialize as maps from column names to values, and results as sequences of rows.
+//! Values map onto the serde data model as follows: collections and tuples become sequences,
+//! maps and user types become maps, and types without a serde counterpart ("varint" values
+//! beyond 64 bits, "decimal", "date", "time", "timestamp", "duration", "uuid", "timeuuid" and
+//! "inet") become their text form. Rows can in turn be decoded into any `Deserialize` type.
+
+use cassandra::column::Column;
+use cassandra::cql_value::CqlValue;
+use cassandra::error::*;
+use cassandra::iterator::LendingIterator;
+use cassandra::numeric::format_decimal;
+use cassandra::result::CassResult;
+use cassandra::row::Row;
+use cassandra::util::Protected;
+use cassandra::value::{FromValue, Value};
+use cassandra_sys::CASS_ERROR_LIB_INVALID_DATA;
+use chrono::SecondsFormat;
+use num::ToPrimitive;
+use serde::de::{self, DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::fmt::Display;
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        CassError::with_message(CASS_ERROR_LIB_INVALID_DATA, msg.to_string()).into()
+    }
+}
+
+impl CqlValue {
+    /// The text form of the values that have no serde counterpart
+    fn to_text(&self) -> Option<String> {
+        match *self {
+            CqlValue::Decimal { ref unscaled, scale } => Some(format_decimal(unscaled, scale)),
+            CqlValue::Varint(ref value) => Some(value.to_string()),
+            CqlValue::Date(value) => Some(value.to_string()),
+            CqlValue::Time(value) => Some(value.to_string()),
+            CqlValue::Timestamp(value) => {
+                Some(value.to_datetime()
+                    .map(|datetime| datetime.to_rfc3339_opts(SecondsFormat::Millis, true))
+                    .unwrap_or_else(|_| value.to_string()))
+            }
+            CqlValue::Duration(value) => Some(value.to_string()),
+            CqlValue::Uuid(value) |
+            CqlValue::Timeuuid(value) => Some(value.to_string()),
+            CqlValue::Inet(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for CqlValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        match *self {
+            CqlValue::Null => serializer.serialize_none(),
+            CqlValue::Ascii(ref text) |
+            CqlValue::Text(ref text) => serializer.serialize_str(text),
+            CqlValue::Blob(ref bytes) |
+            CqlValue::Custom(ref bytes) => serializer.serialize_bytes(bytes),
+            CqlValue::Boolean(value) => serializer.serialize_bool(value),
+            CqlValue::TinyInt(value) => serializer.serialize_i8(value),
+            CqlValue::SmallInt(value) => serializer.serialize_i16(value),
+            CqlValue::Int(value) => serializer.serialize_i32(value),
+            CqlValue::BigInt(value) |
+            CqlValue::Counter(value) => serializer.serialize_i64(value),
+            CqlValue::Varint(ref value) => {
+                match value.to_i64() {
+                    Some(value) => serializer.serialize_i64(value),
+                    None => serializer.serialize_str(&value.to_string()),
+                }
+            }
+            CqlValue::Float(value) => serializer.serialize_f32(value),
+            CqlValue::Double(value) => serializer.serialize_f64(value),
+            CqlValue::List(ref items) |
+            CqlValue::Set(ref items) |
+            CqlValue::Tuple(ref items) => serializer.collect_seq(items),
+            CqlValue::Map(ref entries) => {
+                serializer.collect_map(entries.iter().map(|(key, value)| (key, value)))
+            }
+            CqlValue::UserType(ref fields) => {
+                serializer.collect_map(fields.iter().map(|(name, value)| (name, value)))
+            }
+            _ => {
+                match self.to_text() {
+                    Some(text) => serializer.serialize_str(&text),
+                    None => Err(ser::Error::custom(format!("{:?} has no text form", self))),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Serialize for Value<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        CqlValue::from_value(self).map_err(ser::Error::custom)?.serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for Column<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        Value::build(self.inner()).serialize(serializer)
+    }
+}
+
+/// A row serializes as a map from its column names to its values
+impl<'a> Serialize for Row<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        let values = self.to_values().map_err(ser::Error::custom)?;
+        let mut map = serializer.serialize_map(Some(values.len()))?;
+        for (name, value) in &values {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// A result serializes as the sequence of its rows
+impl Serialize for CassResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.row_count() as usize))?;
+        let mut rows = self.iter();
+        while let Some(row) = rows.next() {
+            seq.serialize_element(&row)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Decodes the row into any `Deserialize` type, such as a struct whose fields are named
+    /// after the columns. Null columns decode into `Option` fields.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(MapDeserializer::new(self.to_values()?.into_iter()))
+    }
+}
+
+impl CassResult {
+    /// Decodes every row of the result into a `Deserialize` type, failing on the first row
+    /// that does not decode
+    pub fn deserialize_rows<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.iter().map(|row| row.deserialize()).collect()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for CqlValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self { self }
+}
+
+impl<'de> Deserializer<'de> for CqlValue {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            CqlValue::Null => visitor.visit_unit(),
+            CqlValue::Ascii(text) |
+            CqlValue::Text(text) => visitor.visit_string(text),
+            CqlValue::Blob(bytes) |
+            CqlValue::Custom(bytes) => visitor.visit_byte_buf(bytes),
+            CqlValue::Boolean(value) => visitor.visit_bool(value),
+            CqlValue::TinyInt(value) => visitor.visit_i8(value),
+            CqlValue::SmallInt(value) => visitor.visit_i16(value),
+            CqlValue::Int(value) => visitor.visit_i32(value),
+            CqlValue::BigInt(value) |
+            CqlValue::Counter(value) => visitor.visit_i64(value),
+            CqlValue::Varint(value) => {
+                match value.to_i64() {
+                    Some(value) => visitor.visit_i64(value),
+                    None => visitor.visit_string(value.to_string()),
+                }
+            }
+            CqlValue::Float(value) => visitor.visit_f32(value),
+            CqlValue::Double(value) => visitor.visit_f64(value),
+            CqlValue::List(items) |
+            CqlValue::Set(items) |
+            CqlValue::Tuple(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            CqlValue::Map(entries) => visitor.visit_map(MapDeserializer::new(entries.into_iter())),
+            CqlValue::UserType(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+            value => {
+                match value.to_text() {
+                    Some(text) => visitor.visit_string(text),
+                    None => Err(de::Error::custom(format!("{:?} has no text form", value))),
+                }
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            CqlValue::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    /// Reads a "blob" as a sequence of bytes too, so it decodes into a `Vec<u8>`
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            CqlValue::Blob(bytes) |
+            CqlValue::Custom(bytes) => visitor.visit_seq(SeqDeserializer::new(bytes.into_iter())),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    /// Reads a "decimal" as a float too, rounding it to the nearest float
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            CqlValue::Decimal { ref unscaled, scale } => {
+                visitor.visit_f64(format_decimal(unscaled, scale).parse().map_err(<Error as de::Error>::custom)?)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> { self.deserialize_f64(visitor) }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string bytes byte_buf unit unit_struct tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[test]
+fn test_deserialize_nested_values() {
+    use serde::de::Deserialize;
+    use std::collections::HashMap;
+
+    let value = CqlValue::Map(vec![(CqlValue::Text("a".to_owned()),
+                                    CqlValue::List(vec![CqlValue::Int(1), CqlValue::Int(2)]))]);
+    let map: HashMap<String, Vec<i64>> = Deserialize::deserialize(value).unwrap();
+    assert_eq!(map["a"], vec![1, 2]);
+
+    let value = CqlValue::Tuple(vec![CqlValue::Null, CqlValue::Blob(vec![1, 2])]);
+    let tuple: (Option<bool>, Vec<u8>) = Deserialize::deserialize(value).unwrap();
+    assert_eq!(tuple, (None, vec![1, 2]));
+
+    let value = CqlValue::Decimal {
+        unscaled: ::num::BigInt::from(-1250),
+        scale: 2,
+    };
+    assert_eq!(String::deserialize(value.clone()).unwrap(), "-12.50");
+    assert_eq!(f64::deserialize(value).unwrap(), -12.5);
+}
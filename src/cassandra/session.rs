@@ -4,9 +4,15 @@
 
 use cassandra::batch::Batch;
 use cassandra::cluster::Cluster;
-use cassandra::error::CassError;
-use cassandra::future::{CloseFuture, Future, PreparedFuture, ResultFuture, SessionFuture};
+use cassandra::error::*;
+use cassandra::execution_profile::{ExecutionProfile, ExecutionProfiles};
+use cassandra::future::{CloseFuture, ConnectFuture, Future, PreparedFuture, ResultFuture, SessionFuture};
 use cassandra::metrics::SessionMetrics;
+use cassandra::paging::ResultPages;
+use cassandra::policy::load_balancing::{BalancedFuture, BalancedHosts};
+use cassandra::policy::retry::{Request, RetryFuture, RetryPolicy};
+use cassandra::policy::speculative::{ConstantSpeculativeExecutionPolicy, SpeculativeExecutionCounters,
+                                     SpeculativeFuture};
 use cassandra::schema::schema_meta::SchemaMeta;
 use cassandra::statement::Statement;
 use cassandra::util::Protected;
@@ -14,18 +20,18 @@ use cassandra::util::Protected;
 use cassandra_sys::CassSession as _Session;
 use cassandra_sys::cass_session_close;
 use cassandra_sys::cass_session_connect;
-use cassandra_sys::cass_session_connect_keyspace;
+use cassandra_sys::cass_session_connect_keyspace_n;
 use cassandra_sys::cass_session_execute;
 use cassandra_sys::cass_session_execute_batch;
 use cassandra_sys::cass_session_free;
 use cassandra_sys::cass_session_get_metrics;
 use cassandra_sys::cass_session_get_schema_meta;
 use cassandra_sys::cass_session_new;
-use cassandra_sys::cass_session_prepare;
+use cassandra_sys::cass_session_prepare_n;
 
-use std::ffi::CString;
-use std::ffi::NulError;
 use std::mem;
+use std::os::raw::c_char;
+use std::time::Duration;
 
 /// A session object is used to execute queries and maintains cluster state through
 /// the control connection. The control connection is used to auto-discover nodes and
@@ -34,13 +40,28 @@ use std::mem;
 ///
 /// Instances of the session object are thread-safe to execute queries.
 #[derive(Debug)]
-pub struct Session(pub *mut _Session);
+pub struct Session {
+    /// The driver's session object
+    pub inner: *mut _Session,
+    speculative_execution_policy: Option<ConstantSpeculativeExecutionPolicy>,
+    speculative_executions: SpeculativeExecutionCounters,
+    execution_profiles: ExecutionProfiles,
+    balanced_hosts: Option<BalancedHosts>,
+}
 unsafe impl Sync for Session {}
 unsafe impl Send for Session {}
 
 impl Protected<*mut _Session> for Session {
-    fn inner(&self) -> *mut _Session { self.0 }
-    fn build(inner: *mut _Session) -> Self { Session(inner) }
+    fn inner(&self) -> *mut _Session { self.inner }
+    fn build(inner: *mut _Session) -> Self {
+        Session {
+            inner,
+            speculative_execution_policy: None,
+            speculative_executions: SpeculativeExecutionCounters::default(),
+            execution_profiles: ExecutionProfiles::default(),
+            balanced_hosts: None,
+        }
+    }
 }
 
 impl Drop for Session {
@@ -48,7 +69,7 @@ impl Drop for Session {
     /// closed before being deallocated.
     fn drop(&mut self) {
         debug!("dropping session");
-        unsafe { cass_session_free(self.0) }
+        unsafe { cass_session_free(self.inner) }
     }
 }
 
@@ -59,70 +80,158 @@ impl Default for Session {
 impl Session {
     /// Create a new Cassanda session.
     /// It's recommended to use Cluster.connect() instead
-    pub fn new() -> Session { unsafe { Session(cass_session_new()) } }
+    pub fn new() -> Session { unsafe { Session::build(cass_session_new()) } }
 
     //    pub fn new2() -> *mut _Session {
     //        unsafe { cass_session_new() }
     //    }
 
-    /// Connects a session.
-    pub fn connect(self, cluster: &Cluster) -> SessionFuture {
-        unsafe { SessionFuture::build(cass_session_connect(self.0, cluster.inner())) }
+    /// Takes over the settings the cluster keeps on the Rust side: the speculative execution
+    /// policy used by `execute_speculative`, the profiles looked up by `execution_profile` and
+    /// the hosts `execute_balanced` routes through, whose connections are returned
+    pub(crate) fn configure(&mut self, cluster: &Cluster) -> Vec<ConnectFuture> {
+        self.speculative_execution_policy = cluster.speculative_execution_policy();
+        self.execution_profiles = cluster.execution_profiles().clone();
+        let (balanced_hosts, connecting) = match cluster.connect_balanced_hosts() {
+            Some((balanced_hosts, connecting)) => (Some(balanced_hosts), connecting),
+            None => (None, Vec::new()),
+        };
+        self.balanced_hosts = balanced_hosts;
+        connecting
+    }
+
+    /// Gets the execution profile registered on the cluster under `name`, to select it for a
+    /// statement or batch with `set_execution_profile`.
+    pub fn execution_profile(&self, name: &str) -> Result<&ExecutionProfile> { self.execution_profiles.get(name) }
+
+    /// Connects a session. The hosts of the cluster's load balancing policy, if any, connect in
+    /// the background, and are passed over by `execute_balanced` until they have.
+    pub fn connect(&mut self, cluster: &Cluster) -> SessionFuture {
+        self.configure(cluster);
+        unsafe { SessionFuture::build(cass_session_connect(self.inner, cluster.inner())) }
     }
 
     /// Connects a session and sets the keyspace.
-    pub fn connect_keyspace(&self, cluster: &Cluster, keyspace: &str) -> Result<Future, NulError> {
+    pub fn connect_keyspace(&mut self, cluster: &Cluster, keyspace: &str) -> Result<Future> {
+        self.configure(cluster);
         unsafe {
-            Ok(Future::build(cass_session_connect_keyspace(self.0, cluster.inner(), CString::new(keyspace)?.as_ptr())))
+            Ok(Future::build(cass_session_connect_keyspace_n(self.inner,
+                                                             cluster.inner(),
+                                                             keyspace.as_ptr() as *const c_char,
+                                                             keyspace.len())))
         }
     }
 
     /// Closes the session instance, outputs a close future which can
     /// be used to determine when the session has been terminated. This allows
     /// in-flight requests to finish.
-    pub fn close(self) -> CloseFuture { unsafe { CloseFuture::build(cass_session_close(self.0)) } }
+    pub fn close(self) -> CloseFuture { unsafe { CloseFuture::build(cass_session_close(self.inner)) } }
 
     /// Create a prepared statement.
-    pub fn prepare(&self, query: &str) -> Result<PreparedFuture, CassError> {
+    pub fn prepare(&self, query: &str) -> Result<PreparedFuture> {
         unsafe {
-            Ok(PreparedFuture::build(cass_session_prepare(self.0, CString::new(query).expect("must be utf8").as_ptr())))
+            Ok(PreparedFuture::build(cass_session_prepare_n(self.inner, query.as_ptr() as *const c_char, query.len())))
         }
     }
 
     //    ///Execute a query or bound statement.
     //    pub fn execute(&self, statement: &str, parameter_count: u64) -> ResultFuture {
     //        unsafe {
-    //            ResultFuture::build(cass_session_execute(self.0,
+    //            ResultFuture::build(cass_session_execute(self.inner,
     //            Statement::new(statement, parameter_count).inner()))
     //        }
     //    }
 
     /// Execute a batch statement.
-    pub fn execute_batch(&self, batch: Batch) -> ResultFuture {
-        ResultFuture::build(unsafe { cass_session_execute_batch(self.0, batch.inner()) })
+    pub fn execute_batch(&self, batch: Batch) -> ResultFuture { self.execute_batch_ref(&batch) }
+
+    pub(crate) fn execute_batch_ref(&self, batch: &Batch) -> ResultFuture {
+        ResultFuture::build(unsafe { cass_session_execute_batch(self.inner, batch.inner()) })
+    }
+
+    /// Execute a batch statement with `retry_policy`, retrying it for as long as the policy asks.
+    ///
+    /// A policy written in Rust with `RetryPolicy::custom` is consulted here, the driver being
+    /// handed the fallthrough policy for the batch; a built-in one is handed to the driver, and
+    /// the batch executed once. The batch is taken over, since it may be executed again at
+    /// another consistency level.
+    pub fn execute_batch_with_retry(&self, batch: Batch, retry_policy: RetryPolicy) -> RetryFuture<'_> {
+        RetryFuture::new(self, Request::Batch(batch), retry_policy)
     }
 
     /// Execute a statement.
     pub fn execute(&self, statement: &Statement) -> ResultFuture {
-        unsafe { ResultFuture::build(cass_session_execute(self.0, statement.inner())) }
+        unsafe { ResultFuture::build(cass_session_execute(self.inner, statement.inner())) }
+    }
+
+    /// Execute a statement with `retry_policy`, retrying it for as long as the policy asks.
+    ///
+    /// A policy written in Rust with `RetryPolicy::custom` is consulted here, the driver being
+    /// handed the fallthrough policy for the statement; a built-in one is handed to the driver,
+    /// and the statement executed once. The statement is taken over, since it may be executed
+    /// again at another consistency level.
+    pub fn execute_with_retry(&self, statement: Statement, retry_policy: RetryPolicy) -> RetryFuture<'_> {
+        RetryFuture::new(self, Request::Statement(statement), retry_policy)
+    }
+
+    /// Execute a statement, speculatively if it may be.
+    ///
+    /// If the cluster has a speculative execution policy and the statement is an idempotent
+    /// prepared statement, the statement is executed again each time the policy's delay passes
+    /// without an answer, and the first execution to succeed gives the result. Other statements
+    /// are executed once.
+    pub fn execute_speculative<'a>(&'a self, statement: &'a Statement) -> SpeculativeFuture<'a> {
+        let policy = match self.speculative_execution_policy {
+            Some(policy) if statement.is_idempotent() && statement.is_prepared() => policy,
+            _ => {
+                ConstantSpeculativeExecutionPolicy {
+                    delay: Duration::from_secs(0),
+                    max_executions: 1,
+                }
+            }
+        };
+        SpeculativeFuture::new(self, statement, policy, &self.speculative_executions)
+    }
+
+    /// Execute a statement on the hosts of its query plan, made by the load balancing policy of
+    /// the execution profile selected for it, or else by the cluster's.
+    ///
+    /// The statement is sent through the session connected to the first host of the plan, and
+    /// on to the next host whenever one could not take it, such as one that is down or
+    /// overloaded. Without a load balancing policy, the statement is routed by the driver.
+    pub fn execute_balanced<'a>(&'a self, statement: &'a Statement) -> BalancedFuture<'a> {
+        BalancedFuture::new(self, self.balanced_hosts.as_ref(), statement)
+    }
+
+    /// Execute a statement, iterating over all of its result pages.
+    /// The next page is only fetched once the current one has been handed out; use
+    /// `Statement::set_paging_size` to control how many rows each page holds. The statement is
+    /// taken over, since its paging state is moved to each page in turn.
+    pub fn execute_paged(&self, statement: Statement) -> ResultPages<'_> {
+        ResultPages::new(self, statement)
     }
 
     /// Gets a snapshot of this session's schema metadata. The returned
     /// snapshot of the schema metadata is not updated. This function
     /// must be called again to retrieve any schema changes since the
     /// previous call.
-    pub fn get_schema_meta(&self) -> SchemaMeta { unsafe { SchemaMeta::build(cass_session_get_schema_meta(self.0)) } }
+    pub fn get_schema_meta(&self) -> SchemaMeta {
+        unsafe { SchemaMeta::build(cass_session_get_schema_meta(self.inner)) }
+    }
 
     /// Gets a copy of this session's performance/diagnostic metrics.
     pub fn get_metrics(&self) -> SessionMetrics {
         unsafe {
             let mut metrics = mem::zeroed();
-            cass_session_get_metrics(self.0, &mut metrics);
-            SessionMetrics::build(&metrics)
+            cass_session_get_metrics(self.inner, &mut metrics);
+            let mut metrics: SessionMetrics = metrics.into();
+            metrics.speculative_executions.fired = self.speculative_executions.fired();
+            metrics.speculative_executions.won = self.speculative_executions.won();
+            metrics
         }
     }
 
     //    pub fn get_schema(&self) -> Schema {
-    //        unsafe { Schema(cass_session_get_schema(self.0)) }
+    //        unsafe { Schema(cass_session_get_schema(self.inner)) }
     //    }
 }
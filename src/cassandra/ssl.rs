@@ -1,16 +1,16 @@
 
 
 use cassandra::error::CassError;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 use cassandra_sys::CassSsl as _Ssl;
-use cassandra_sys::cass_ssl_add_trusted_cert;
+use cassandra_sys::cass_ssl_add_trusted_cert_n;
 use cassandra_sys::cass_ssl_free;
 use cassandra_sys::cass_ssl_new;
-use cassandra_sys::cass_ssl_set_cert;
-use cassandra_sys::cass_ssl_set_private_key;
+use cassandra_sys::cass_ssl_set_cert_n;
+use cassandra_sys::cass_ssl_set_private_key_n;
 use cassandra_sys::cass_ssl_set_verify_flags;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
+use std::os::raw::c_char;
 
 /// Describes the SSL configuration of a cluster.
 #[derive(Debug)]
@@ -37,9 +37,8 @@ impl Ssl {
     /// the peer's certificate.
     pub fn add_trusted_cert(&mut self, cert: &str) -> Result<&mut Self> {
         unsafe {
-            cass_ssl_add_trusted_cert(self.0, CString::new(cert).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_ssl_add_trusted_cert_n(self.0, cert.as_ptr() as *const c_char, cert.len())
+                .wrap(self)
         }
     }
 
@@ -61,9 +60,8 @@ impl Ssl {
     /// Certificate chain starting with the certificate itself.
     pub fn set_cert(&mut self, cert: &str) -> Result<&mut Self> {
         unsafe {
-            cass_ssl_set_cert(self.0, CString::new(cert).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_ssl_set_cert_n(self.0, cert.as_ptr() as *const c_char, cert.len())
+                .wrap(self)
         }
     }
 
@@ -71,11 +69,12 @@ impl Ssl {
     /// the client on the server-side.
     pub fn set_private_key(&mut self, key: &str, password: &str) -> Result<&mut Self> {
         unsafe {
-            cass_ssl_set_private_key(self.0,
-                                     CString::new(key).expect("must be utf8").as_ptr(),
-                                     password.as_ptr() as *const i8)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_ssl_set_private_key_n(self.0,
+                                       key.as_ptr() as *const c_char,
+                                       key.len(),
+                                       password.as_ptr() as *const c_char,
+                                       password.len())
+                .wrap(self)
         }
     }
 }
@@ -1,72 +1,83 @@
 
 
 use cassandra::batch::CustomPayload;
-use cassandra::collection::List;
+use cassandra::collection::{CollectionItem, List};
 use cassandra::collection::Map;
-// use decimal::d128;
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use decimal::d128;
+use num::BigInt;
 use cassandra::collection::Set;
 use cassandra::consistency::Consistency;
+use cassandra::data_type::ConstDataType;
 use cassandra::error::CassError;
+use cassandra::execution_profile::{ExecutionProfile, ProfileTarget};
 use cassandra::inet::Inet;
+use cassandra::numeric::{encode_decimal, encode_varint};
+use cassandra::policy::load_balancing::LoadBalancingPolicy;
 use cassandra::policy::retry::RetryPolicy;
 use cassandra::result::CassResult;
+use cassandra::session::Session;
 use cassandra::tuple::Tuple;
 use cassandra::user_type::UserType;
-use cassandra::util::Protected;
+use cassandra::util::{CLUSTER_REQUEST_TIMEOUT, CassErrorExt, Protected, timeout_millis};
 use cassandra::uuid::Uuid;
 use cassandra_sys::CassStatement as _Statement;
 use cassandra_sys::cass_false;
 use cassandra_sys::cass_statement_add_key_index;
 use cassandra_sys::cass_statement_bind_bool;
-use cassandra_sys::cass_statement_bind_bool_by_name;
+use cassandra_sys::cass_statement_bind_bool_by_name_n;
 use cassandra_sys::cass_statement_bind_bytes;
-use cassandra_sys::cass_statement_bind_bytes_by_name;
+use cassandra_sys::cass_statement_bind_bytes_by_name_n;
 use cassandra_sys::cass_statement_bind_collection;
-use cassandra_sys::cass_statement_bind_collection_by_name;
-#[allow(unused_imports)]
+use cassandra_sys::cass_statement_bind_collection_by_name_n;
 use cassandra_sys::cass_statement_bind_decimal;
-#[allow(unused_imports)]
-use cassandra_sys::cass_statement_bind_decimal_by_name;
+use cassandra_sys::cass_statement_bind_decimal_by_name_n;
 use cassandra_sys::cass_statement_bind_double;
-use cassandra_sys::cass_statement_bind_double_by_name;
+use cassandra_sys::cass_statement_bind_double_by_name_n;
 use cassandra_sys::cass_statement_bind_float;
-use cassandra_sys::cass_statement_bind_float_by_name;
+use cassandra_sys::cass_statement_bind_float_by_name_n;
 use cassandra_sys::cass_statement_bind_inet;
-use cassandra_sys::cass_statement_bind_inet_by_name;
+use cassandra_sys::cass_statement_bind_inet_by_name_n;
 use cassandra_sys::cass_statement_bind_int16;
-use cassandra_sys::cass_statement_bind_int16_by_name;
+use cassandra_sys::cass_statement_bind_int16_by_name_n;
 use cassandra_sys::cass_statement_bind_int32;
-use cassandra_sys::cass_statement_bind_int32_by_name;
+use cassandra_sys::cass_statement_bind_int32_by_name_n;
 use cassandra_sys::cass_statement_bind_int64;
-use cassandra_sys::cass_statement_bind_int64_by_name;
+use cassandra_sys::cass_statement_bind_int64_by_name_n;
 use cassandra_sys::cass_statement_bind_int8;
-use cassandra_sys::cass_statement_bind_int8_by_name;
+use cassandra_sys::cass_statement_bind_int8_by_name_n;
 use cassandra_sys::cass_statement_bind_null;
-use cassandra_sys::cass_statement_bind_null_by_name;
-use cassandra_sys::cass_statement_bind_string;
-use cassandra_sys::cass_statement_bind_string_by_name;
+use cassandra_sys::cass_statement_bind_null_by_name_n;
+use cassandra_sys::cass_statement_bind_string_by_name_n;
+use cassandra_sys::cass_statement_bind_string_n;
 use cassandra_sys::cass_statement_bind_tuple;
-use cassandra_sys::cass_statement_bind_tuple_by_name;
+use cassandra_sys::cass_statement_bind_tuple_by_name_n;
 use cassandra_sys::cass_statement_bind_uint32;
-use cassandra_sys::cass_statement_bind_uint32_by_name;
+use cassandra_sys::cass_statement_bind_uint32_by_name_n;
 use cassandra_sys::cass_statement_bind_user_type;
-use cassandra_sys::cass_statement_bind_user_type_by_name;
+use cassandra_sys::cass_statement_bind_user_type_by_name_n;
 use cassandra_sys::cass_statement_bind_uuid;
-use cassandra_sys::cass_statement_bind_uuid_by_name;
+use cassandra_sys::cass_statement_bind_uuid_by_name_n;
 use cassandra_sys::cass_statement_free;
-use cassandra_sys::cass_statement_new;
+use cassandra_sys::cass_statement_new_n;
 use cassandra_sys::cass_statement_set_consistency;
 use cassandra_sys::cass_statement_set_custom_payload;
-use cassandra_sys::cass_statement_set_keyspace;
+use cassandra_sys::cass_statement_set_keyspace_n;
 use cassandra_sys::cass_statement_set_paging_size;
 use cassandra_sys::cass_statement_set_paging_state;
 use cassandra_sys::cass_statement_set_paging_state_token;
+use cassandra_sys::cass_statement_set_request_timeout;
 use cassandra_sys::cass_statement_set_retry_policy;
 use cassandra_sys::cass_statement_set_serial_consistency;
 use cassandra_sys::cass_statement_set_timestamp;
+use cassandra_sys::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
 use cassandra_sys::cass_true;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::os::raw;
+use std::sync::Arc;
+use time::Duration;
 /// A statement object is an executable query. It represents either a regular
 /// (adhoc) statement or a prepared statement. It maintains the queries' parameter
 /// values along with query options (consistency level, paging state, etc.)
@@ -74,35 +85,53 @@ use std::ffi::CString;
 /// <b>Note:</b> Parameters for regular queries are not supported by the binary protocol
 /// version 1.
 #[derive(Debug)]
-pub struct Statement(*mut _Statement);
+pub struct Statement {
+    inner: *mut _Statement,
+    parameter_count: Option<usize>,
+    parameter_types: Vec<ConstDataType>,
+    is_idempotent: bool,
+    is_prepared: bool,
+    load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
+}
 
 impl Protected<*mut _Statement> for Statement {
-    fn inner(&self) -> *mut _Statement { self.0 }
-    fn build(inner: *mut _Statement) -> Self { Statement(inner) }
+    fn inner(&self) -> *mut _Statement { self.inner }
+    fn build(inner: *mut _Statement) -> Self {
+        Statement {
+            inner,
+            parameter_count: None,
+            parameter_types: Vec::new(),
+            is_idempotent: false,
+            is_prepared: false,
+            load_balancing_policy: None,
+        }
+    }
 }
 
+/// Creates a `Statement`, sized by counting the bind markers in the query with
+/// `Statement::bind_marker_count`.
+///
+/// Values can then be bound all at once with `bind_values`, which checks them against the
+/// number of markers.
+///
+/// ```ignore
+/// let select = stmt!("SELECT * FROM examples.basic");
+/// let mut insert = stmt!("INSERT INTO examples.basic (key, i32) VALUES (?, ?)");
+/// insert.bind_values(("test", 42i32))?;
+/// ```
 #[macro_export]
 macro_rules! stmt {
-    ( $( $x:expr ),*) => {
+    ( $query:expr ) => {
         {
-            $(
-        	let query = $x;
-        	let param_count = query.matches("?").count();
-        	let statement = Statement::new(query, param_count);
-            )*
-            statement
+            let query = $query;
+            $crate::Statement::new(query, $crate::Statement::bind_marker_count(query))
         }
     };
 }
 
-// statement,
-// 	key,
-// 	i % 2 == 0,
-// 	i as f32 / 2.0f32,
-// 	i as f64 / 200.0,
-// 	i as i32 * 10,
-// 	i as i64 * 100);
-
+/// The constants that may follow a colon in a map or user type literal, which are not named
+/// bind markers
+const CONSTANTS: [&str; 5] = ["true", "false", "null", "nan", "infinity"];
 
 impl Drop for Statement {
     /// Frees a statement instance. Statements can be immediately freed after
@@ -159,6 +188,12 @@ impl<'a> BindRustType<&'a str> for Statement {
     fn bind_by_name(&mut self, col: &str, value: &str) -> Result<&mut Self> { self.bind_string_by_name(col, value) }
 }
 
+impl BindRustType<String> for Statement {
+    fn bind(&mut self, index: usize, value: String) -> Result<&mut Self> { self.bind_string(index, &value) }
+
+    fn bind_by_name(&mut self, col: &str, value: String) -> Result<&mut Self> { self.bind_string_by_name(col, &value) }
+}
+
 impl BindRustType<Set> for Statement {
     fn bind(&mut self, index: usize, value: Set) -> Result<&mut Self> { self.bind_set(index, value) }
 
@@ -183,23 +218,262 @@ impl BindRustType<Vec<u8>> for Statement {
     fn bind_by_name(&mut self, col: &str, value: Vec<u8>) -> Result<&mut Self> { self.bind_bytes_by_name(col, value) }
 }
 
+impl BindRustType<CqlDate> for Statement {
+    fn bind(&mut self, index: usize, value: CqlDate) -> Result<&mut Self> { self.bind_uint32(index, value.0) }
+
+    fn bind_by_name(&mut self, col: &str, value: CqlDate) -> Result<&mut Self> { self.bind_uint32_by_name(col, value.0) }
+}
+
+impl BindRustType<CqlTime> for Statement {
+    fn bind(&mut self, index: usize, value: CqlTime) -> Result<&mut Self> { self.bind_int64(index, value.0) }
+
+    fn bind_by_name(&mut self, col: &str, value: CqlTime) -> Result<&mut Self> { self.bind_int64_by_name(col, value.0) }
+}
+
+impl BindRustType<CqlTimestamp> for Statement {
+    fn bind(&mut self, index: usize, value: CqlTimestamp) -> Result<&mut Self> { self.bind_int64(index, value.0) }
+
+    fn bind_by_name(&mut self, col: &str, value: CqlTimestamp) -> Result<&mut Self> {
+        self.bind_int64_by_name(col, value.0)
+    }
+}
+
+impl BindRustType<CqlDuration> for Statement {
+    fn bind(&mut self, index: usize, value: CqlDuration) -> Result<&mut Self> { self.bind_bytes(index, value.to_bytes()) }
+
+    fn bind_by_name(&mut self, col: &str, value: CqlDuration) -> Result<&mut Self> {
+        self.bind_bytes_by_name(col, value.to_bytes())
+    }
+}
+
+impl BindRustType<NaiveDate> for Statement {
+    fn bind(&mut self, index: usize, value: NaiveDate) -> Result<&mut Self> { self.bind(index, CqlDate::from(value)) }
+
+    fn bind_by_name(&mut self, col: &str, value: NaiveDate) -> Result<&mut Self> {
+        self.bind_by_name(col, CqlDate::from(value))
+    }
+}
+
+impl BindRustType<NaiveTime> for Statement {
+    fn bind(&mut self, index: usize, value: NaiveTime) -> Result<&mut Self> { self.bind(index, CqlTime::from(value)) }
+
+    fn bind_by_name(&mut self, col: &str, value: NaiveTime) -> Result<&mut Self> {
+        self.bind_by_name(col, CqlTime::from(value))
+    }
+}
+
+impl BindRustType<DateTime<Utc>> for Statement {
+    fn bind(&mut self, index: usize, value: DateTime<Utc>) -> Result<&mut Self> {
+        self.bind(index, CqlTimestamp::from(value))
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: DateTime<Utc>) -> Result<&mut Self> {
+        self.bind_by_name(col, CqlTimestamp::from(value))
+    }
+}
+
+impl BindRustType<d128> for Statement {
+    fn bind(&mut self, index: usize, value: d128) -> Result<&mut Self> { self.bind_decimal(index, value) }
+
+    fn bind_by_name(&mut self, col: &str, value: d128) -> Result<&mut Self> { self.bind_decimal_by_name(col, value) }
+}
+
+impl BindRustType<BigInt> for Statement {
+    fn bind(&mut self, index: usize, value: BigInt) -> Result<&mut Self> { self.bind_varint(index, &value) }
+
+    fn bind_by_name(&mut self, col: &str, value: BigInt) -> Result<&mut Self> { self.bind_varint_by_name(col, &value) }
+}
+
+impl<T: CollectionItem> BindRustType<Vec<T>> for Statement {
+    fn bind(&mut self, index: usize, value: Vec<T>) -> Result<&mut Self> {
+        let list = List::from_items(self.parameter_type(index), value)?;
+        self.bind_list(index, list)
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: Vec<T>) -> Result<&mut Self> {
+        self.bind_list_by_name(col, List::from_items(None, value)?)
+    }
+}
+
+impl<T: CollectionItem> BindRustType<HashSet<T>> for Statement {
+    fn bind(&mut self, index: usize, value: HashSet<T>) -> Result<&mut Self> {
+        let set = Set::from_items(self.parameter_type(index), value)?;
+        self.bind_set(index, set)
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: HashSet<T>) -> Result<&mut Self> {
+        self.bind_set_by_name(col, Set::from_items(None, value)?)
+    }
+}
+
+impl<T: CollectionItem> BindRustType<BTreeSet<T>> for Statement {
+    fn bind(&mut self, index: usize, value: BTreeSet<T>) -> Result<&mut Self> {
+        let set = Set::from_items(self.parameter_type(index), value)?;
+        self.bind_set(index, set)
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: BTreeSet<T>) -> Result<&mut Self> {
+        self.bind_set_by_name(col, Set::from_items(None, value)?)
+    }
+}
+
+impl<K: CollectionItem, V: CollectionItem> BindRustType<HashMap<K, V>> for Statement {
+    fn bind(&mut self, index: usize, value: HashMap<K, V>) -> Result<&mut Self> {
+        let map = Map::from_entries(self.parameter_type(index), value)?;
+        self.bind_map(index, map)
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: HashMap<K, V>) -> Result<&mut Self> {
+        self.bind_map_by_name(col, Map::from_entries(None, value)?)
+    }
+}
+
+impl<K: CollectionItem, V: CollectionItem> BindRustType<BTreeMap<K, V>> for Statement {
+    fn bind(&mut self, index: usize, value: BTreeMap<K, V>) -> Result<&mut Self> {
+        let map = Map::from_entries(self.parameter_type(index), value)?;
+        self.bind_map(index, map)
+    }
+
+    fn bind_by_name(&mut self, col: &str, value: BTreeMap<K, V>) -> Result<&mut Self> {
+        self.bind_map_by_name(col, Map::from_entries(None, value)?)
+    }
+}
+
+/// A group of values that binds all of a statement's parameters at once.
+///
+/// Implemented for tuples of up to 16 values, which bind by position, and derivable with
+/// `#[derive(ToCqlValues)]` for structs, which bind each field by name.
+pub trait ToCqlValues {
+    /// The number of values
+    fn value_count(&self) -> usize;
+
+    /// Binds the values to the statement's parameters
+    fn bind_to(self, statement: &mut Statement) -> Result<()>;
+}
+
+macro_rules! tuple_to_cql_values {
+    ( $count:expr; $( $name:ident : $index:tt ),+ ) => {
+        impl<$( $name ),+> ToCqlValues for ($( $name, )+)
+            where $( Statement: BindRustType<$name> ),+ {
+            fn value_count(&self) -> usize { $count }
+
+            fn bind_to(self, statement: &mut Statement) -> Result<()> {
+                $( BindRustType::<$name>::bind(statement, $index, self.$index)?; )+
+                Ok(())
+            }
+        }
+    };
+}
+
+tuple_to_cql_values!(1; A: 0);
+tuple_to_cql_values!(2; A: 0, B: 1);
+tuple_to_cql_values!(3; A: 0, B: 1, C: 2);
+tuple_to_cql_values!(4; A: 0, B: 1, C: 2, D: 3);
+tuple_to_cql_values!(5; A: 0, B: 1, C: 2, D: 3, E: 4);
+tuple_to_cql_values!(6; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+tuple_to_cql_values!(7; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+tuple_to_cql_values!(8; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+tuple_to_cql_values!(9; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8);
+tuple_to_cql_values!(10; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9);
+tuple_to_cql_values!(11; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10);
+tuple_to_cql_values!(12; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11);
+tuple_to_cql_values!(13; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12);
+tuple_to_cql_values!(14; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13);
+tuple_to_cql_values!(15; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13,
+                     O: 14);
+tuple_to_cql_values!(16; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13,
+                     O: 14, P: 15);
+
 impl Statement {
     /// Creates a new query statement.
     pub fn new(query: &str, parameter_count: usize) -> Self {
-        unsafe {
-            Statement(cass_statement_new(CString::new(query).expect("must be utf8").as_ptr(),
-                                         parameter_count))
+        let query_ptr = query.as_ptr() as *const raw::c_char;
+        let mut statement = unsafe { Statement::build(cass_statement_new_n(query_ptr, query.len(), parameter_count)) };
+        statement.parameter_count = Some(parameter_count);
+        statement
+    }
+
+    unsafe fn free(&mut self) { cass_statement_free(self.inner) }
+
+    /// Counts the bind markers of a query, both `?` and named `:name` ones, leaving out those in
+    /// string literals, quoted identifiers and comments.
+    pub fn bind_marker_count(query: &str) -> usize {
+        let query = query.as_bytes();
+        // the position just past the next `end`, or the end of the query
+        let skip_to = |from: usize, end: &[u8]| {
+            query[from.min(query.len())..]
+                .windows(end.len())
+                .position(|window| window == end)
+                .map_or(query.len(), |offset| from + offset + end.len())
+        };
+        let mut count = 0;
+        let mut pos = 0;
+        while pos < query.len() {
+            pos = match (query[pos], query.get(pos + 1).cloned()) {
+                (b'\'', _) => skip_to(pos + 1, b"'"),
+                (b'"', _) => skip_to(pos + 1, b"\""),
+                (b'$', Some(b'$')) => skip_to(pos + 2, b"$$"),
+                (b'-', Some(b'-')) | (b'/', Some(b'/')) => skip_to(pos + 2, b"\n"),
+                (b'/', Some(b'*')) => skip_to(pos + 2, b"*/"),
+                (b'?', _) => {
+                    count += 1;
+                    pos + 1
+                }
+                (b':', Some(b'"')) => {
+                    count += 1;
+                    skip_to(pos + 2, b"\"")
+                }
+                (b':', Some(first)) if first.is_ascii_alphabetic() => {
+                    let name = &query[pos + 1..];
+                    let len = name.iter()
+                        .position(|&byte| !byte.is_ascii_alphanumeric() && byte != b'_')
+                        .unwrap_or(name.len());
+                    if !CONSTANTS.iter().any(|constant| name[..len].eq_ignore_ascii_case(constant.as_bytes())) {
+                        count += 1;
+                    }
+                    pos + 1 + len
+                }
+                _ => pos + 1,
+            };
         }
+        count
     }
 
-    unsafe fn free(&mut self) { cass_statement_free(self.0) }
+    /// Records the types of the statement's parameters, so `bind_values` can check its arity and
+    /// collections can be checked against the types they are bound to.
+    ///
+    /// The types belong to the prepared statement, which the bound statement keeps alive.
+    pub(crate) fn with_parameter_types(mut self, parameter_types: Vec<ConstDataType>) -> Self {
+        self.parameter_count = Some(parameter_types.len());
+        self.parameter_types = parameter_types;
+        self.is_prepared = true;
+        self
+    }
 
-    //    ///Binds an arbitrary CassBindable type to a cassandra statement
-    //    ///FIXME not yet implemented
-    //    pub fn bind(&mut self, params: Vec<CassBindable>) {
-    //        let _ = params;
-    //        unimplemented!();
-    //    }
+    /// Whether the statement was bound from a prepared statement
+    pub(crate) fn is_prepared(&self) -> bool { self.is_prepared }
+
+    /// Gets the expected type of the parameter at `index`, if the statement was prepared
+    fn parameter_type(&self, index: usize) -> Option<&ConstDataType> { self.parameter_types.get(index) }
+
+    /// Binds every parameter of the statement at once, from a tuple or a `#[derive(ToCqlValues)]`
+    /// struct. Fails without binding anything if the number of values does not match the number
+    /// of parameters.
+    ///
+    /// ```ignore
+    /// let mut statement = prepared.bind();
+    /// statement.bind_values((id, name, tags))?;
+    /// ```
+    pub fn bind_values<V: ToCqlValues>(&mut self, values: V) -> Result<&mut Self> {
+        if let Some(expected) = self.parameter_count {
+            if values.value_count() != expected {
+                let message = format!("expected {} values to bind, got {}", expected, values.value_count());
+                return Err(CassError::with_message(CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS, message).into());
+            }
+        }
+        values.bind_to(self)?;
+        Ok(self)
+    }
 
     /// Adds a key index specifier to this a statement.
     /// When using token-aware routing, this can be used to tell the driver which
@@ -211,7 +485,7 @@ impl Statement {
     /// This is not necessary for prepared statements, as the key
     /// parameters are determined in the metadata processed in the prepare phase.
     pub fn add_key_index(&mut self, index: usize) -> Result<&mut Self> {
-        unsafe { cass_statement_add_key_index(self.0, index).to_result(self).chain_err(|| "") }
+        unsafe { cass_statement_add_key_index(self.inner, index).wrap(self) }
     }
 
     /// Sets the statement's keyspace for use with token-aware routing.
@@ -220,21 +494,31 @@ impl Statement {
     /// is determined in the metadata processed in the prepare phase.
     pub fn set_keyspace(&mut self, keyspace: String) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_keyspace(self.0,
-                                        (CString::new(keyspace).expect("must be utf8").as_ptr()))
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_set_keyspace_n(self.inner, keyspace.as_ptr() as *const raw::c_char, keyspace.len())
+                .wrap(self)
         }
     }
 
+    /// Marks the statement as idempotent, meaning it can be executed more than once with the same
+    /// effect. Only idempotent prepared statements are executed speculatively by
+    /// `Session::execute_speculative`.
+    ///
+    /// <b>Default:</b> false
+    pub fn set_is_idempotent(&mut self, is_idempotent: bool) -> &mut Self {
+        self.is_idempotent = is_idempotent;
+        self
+    }
+
+    /// Whether the statement is marked as idempotent
+    pub fn is_idempotent(&self) -> bool { self.is_idempotent }
+
     /// Sets the statement's consistency level.
     ///
     /// <b>Default:</b> CASS_CONSISTENCY_LOCAL_ONE
     pub fn set_consistency(&mut self, consistency: Consistency) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_consistency(self.0, consistency.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_set_consistency(self.inner, consistency.inner())
+                .wrap(self)
         }
     }
 
@@ -243,9 +527,8 @@ impl Statement {
     /// <b>Default:</b> Not set
     pub fn set_serial_consistency(&mut self, serial_consistency: Consistency) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_serial_consistency(self.0, serial_consistency.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_set_serial_consistency(self.inner, serial_consistency.inner())
+                .wrap(self)
         }
     }
 
@@ -253,64 +536,91 @@ impl Statement {
     ///
     /// <b>Default:</b> -1 (Disabled)
     pub fn set_paging_size(&mut self, page_size: i32) -> Result<&mut Self> {
-        unsafe { cass_statement_set_paging_size(self.0, page_size).to_result(self).chain_err(|| "") }
+        unsafe { cass_statement_set_paging_size(self.inner, page_size).wrap(self) }
     }
 
     /// Sets the statement's paging state. This can be used to get the next page of
     /// data in a multi-page query.
-    pub fn set_paging_state(&mut self, result: CassResult) -> Result<&mut Self> {
-        unsafe { cass_statement_set_paging_state(self.0, result.inner()).to_result(self).chain_err(|| "") }
+    pub fn set_paging_state(&mut self, result: &CassResult) -> Result<&mut Self> {
+        unsafe { cass_statement_set_paging_state(self.inner, result.inner()).wrap(self) }
     }
 
-    /// Sets the statement's paging state.  This can be used to get the next page of
-    /// data in a multi-page query.
+    /// Sets the statement's paging state from a token previously read with
+    /// `CassResult::paging_state_token`. This positions a new statement, for the same
+    /// query, at the page following the one that produced the token.
     ///
     /// <b>Warning:</b> The paging state should not be exposed to or come from
     /// untrusted environments. The paging state could be spoofed and potentially
     /// used to gain access to other data.
-    pub fn set_paging_state_token(&mut self, paging_state: &str) -> Result<&mut Self> {
+    pub fn set_paging_state_token(&mut self, paging_state: &[u8]) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_paging_state_token(self.0,
-                                                  paging_state.as_ptr() as *const i8,
+            cass_statement_set_paging_state_token(self.inner,
+                                                  paging_state.as_ptr() as *const raw::c_char,
                                                   paging_state.len())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets the statement's timestamp.
     pub fn set_timestamp(&mut self, timestamp: i64) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_timestamp(self.0, timestamp)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_set_timestamp(self.inner, timestamp)
+                .wrap(self)
         }
     }
 
-    /// Sets the statement's retry policy.
+    /// Sets the statement's retry policy. A policy written in Rust is refused: pass it to
+    /// `Session::execute_with_retry` instead.
     pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> Result<&mut Self> {
-        unsafe {
-            cass_statement_set_retry_policy(self.0, retry_policy.inner())
-                .to_result(self)
-                .chain_err(|| "")
-        }
+        self.apply_retry_policy(&retry_policy)?;
+        Ok(self)
+    }
+
+    /// Sets the statement's timeout for waiting for a response from a node. A zero timeout
+    /// disables it, and a negative one is rejected.
+    ///
+    /// <b>Default:</b> The cluster's request timeout
+    pub fn set_request_timeout(&mut self, timeout: Duration) -> Result<&mut Self> {
+        let timeout = timeout_millis(timeout)?;
+        unsafe { cass_statement_set_request_timeout(self.inner, timeout).wrap(self) }
+    }
+
+    /// Makes the statement use the cluster's request timeout again.
+    pub fn reset_request_timeout(&mut self) -> Result<&mut Self> {
+        unsafe { cass_statement_set_request_timeout(self.inner, CLUSTER_REQUEST_TIMEOUT).wrap(self) }
+    }
+
+    /// Applies the settings of an execution profile, as returned by `Session::execution_profile`,
+    /// to the statement. Settings the profile leaves unset are not changed.
+    pub fn set_execution_profile(&mut self, profile: &ExecutionProfile) -> Result<&mut Self> {
+        profile.apply_to(self)?;
+        Ok(self)
+    }
+
+    /// Applies the settings of the execution profile registered under `name` on the cluster
+    /// `session` was connected to. Fails if there is no such profile.
+    pub fn set_execution_profile_name(&mut self, session: &Session, name: &str) -> Result<&mut Self> {
+        self.set_execution_profile(session.execution_profile(name)?)
+    }
+
+    /// The load balancing policy of the execution profile selected for the statement, if any
+    pub(crate) fn load_balancing_policy(&self) -> Option<&Arc<dyn LoadBalancingPolicy>> {
+        self.load_balancing_policy.as_ref()
     }
 
     /// Sets the statement's custom payload.
     pub fn set_custom_payload(&mut self, payload: CustomPayload) -> Result<&mut Self> {
         unsafe {
-            cass_statement_set_custom_payload(self.0, payload.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_set_custom_payload(self.inner, payload.inner())
+                .wrap(self)
         }
     }
 
     /// Binds null to a query or bound statement at the specified index.
     pub fn bind_null(&mut self, index: usize) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_null(self.0, index)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_null(self.inner, index)
+                .wrap(self)
         }
     }
 
@@ -320,78 +630,64 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_null_by_name(&mut self, name: &str) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_null_by_name(self.0, CString::new(name).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_null_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len())
+                .wrap(self)
         }
     }
 
     /// Binds a "tinyint" to a query or bound statement at the specified index.
     pub fn bind_int8(&mut self, index: usize, value: i8) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int8(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int8(self.inner, index, value)
+                .wrap(self)
         }
     }
 
     /// Binds a "tinyint" to all the values with the specified name.
     pub fn bind_int8_by_name(&mut self, name: &str, value: i8) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int8_by_name(self.0,
-                                             CString::new(name).expect("must be utf8").as_ptr(),
-                                             value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int8_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds an "smallint" to a query or bound statement at the specified index.
     pub fn bind_int16(&mut self, index: usize, value: i16) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int16(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int16(self.inner, index, value)
+                .wrap(self)
         }
     }
 
     /// Binds a "smallint" to all the values with the specified name.
     pub fn bind_int16_by_name(&mut self, name: &str, value: i16) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int16_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int16_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds an "int" to a query or bound statement at the specified index.
     pub fn bind_int32(&mut self, index: usize, value: i32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int32(self.inner, index, value)
+                .wrap(self)
         }
     }
 
     /// Binds an "int" to all the values with the specified name.
     pub fn bind_int32_by_name(&mut self, name: &str, value: i32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int32_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int32_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds a "date" to a query or bound statement at the specified index.
     pub fn bind_uint32(&mut self, index: usize, value: u32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_uint32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_uint32(self.inner, index, value)
+                .wrap(self)
         }
     }
 
@@ -401,11 +697,8 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_uint32_by_name(&mut self, name: &str, value: u32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_uint32_by_name(self.0,
-                                               CString::new(name).expect("must be utf8").as_ptr(),
-                                               value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_uint32_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -413,9 +706,8 @@ impl Statement {
     /// bound statement at the specified index.
     pub fn bind_int64(&mut self, index: usize, value: i64) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int64(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int64(self.inner, index, value)
+                .wrap(self)
         }
     }
 
@@ -423,20 +715,16 @@ impl Statement {
     /// with the specified name.
     pub fn bind_int64_by_name(&mut self, name: &str, value: i64) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_int64_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_int64_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds a "float" to a query or bound statement at the specified index.
     pub fn bind_float(&mut self, index: usize, value: f32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_float(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_float(self.inner, index, value)
+                .wrap(self)
         }
     }
 
@@ -446,20 +734,16 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_float_by_name(&mut self, name: &str, value: f32) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_float_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_float_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds a "double" to a query or bound statement at the specified index.
     pub fn bind_double(&mut self, index: usize, value: f64) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_double(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_double(self.inner, index, value)
+                .wrap(self)
         }
     }
 
@@ -469,20 +753,16 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_double_by_name(&mut self, name: &str, value: f64) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_double_by_name(self.0,
-                                               CString::new(name).expect("must be utf8").as_ptr(),
-                                               value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_double_by_name_n(self.inner, name.as_ptr() as *const raw::c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
     /// Binds a "boolean" to a query or bound statement at the specified index.
     pub fn bind_bool(&mut self, index: usize, value: bool) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_bool(self.0, index, if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_bool(self.inner, index, if value { cass_true } else { cass_false })
+                .wrap(self)
         }
     }
 
@@ -492,11 +772,11 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_bool_by_name(&mut self, name: &str, value: bool) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_bool_by_name(self.0,
-                                             CString::new(name).expect("must be utf8").as_ptr(),
-                                             if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_bool_by_name_n(self.inner,
+                                               name.as_ptr() as *const raw::c_char,
+                                               name.len(),
+                                               if value { cass_true } else { cass_false })
+                .wrap(self)
         }
     }
 
@@ -504,11 +784,8 @@ impl Statement {
     /// at the specified index.
     pub fn bind_string(&mut self, index: usize, value: &str) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_string(self.0,
-                                       index,
-                                       CString::new(value).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_string_n(self.inner, index, value.as_ptr() as *const raw::c_char, value.len())
+                .wrap(self)
         }
     }
 
@@ -519,11 +796,12 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_string_by_name(&mut self, name: &str, value: &str) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_string_by_name(self.0,
-                                               CString::new(name).expect("must be utf8").as_ptr(),
-                                               CString::new(value).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_string_by_name_n(self.inner,
+                                                 name.as_ptr() as *const raw::c_char,
+                                                 name.len(),
+                                                 value.as_ptr() as *const raw::c_char,
+                                                 value.len())
+                .wrap(self)
 
         }
     }
@@ -531,9 +809,8 @@ impl Statement {
     /// Binds a "blob", "varint" or "custom" to a query or bound statement at the specified index.
     pub fn bind_bytes(&mut self, index: usize, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_bytes(self.0, index, value.as_ptr(), value.len())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_bytes(self.inner, index, value.as_ptr(), value.len())
+                .wrap(self)
         }
     }
 
@@ -544,21 +821,20 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_bytes_by_name(&mut self, name: &str, mut value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_bytes_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value.as_mut_ptr(),
-                                              value.len())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_bytes_by_name_n(self.inner,
+                                                name.as_ptr() as *const raw::c_char,
+                                                name.len(),
+                                                value.as_mut_ptr(),
+                                                value.len())
+                .wrap(self)
         }
     }
 
     /// Binds a "uuid" or "timeuuid" to a query or bound statement at the specified index.
     pub fn bind_uuid(&mut self, index: usize, value: Uuid) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_uuid(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_uuid(self.inner, index, value.inner())
+                .wrap(self)
         }
     }
 
@@ -569,77 +845,78 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_uuid_by_name(&mut self, name: &str, value: Uuid) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_uuid_by_name(self.0,
-                                             CString::new(name).expect("must be utf8").as_ptr(),
-                                             value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_uuid_by_name_n(self.inner,
+                                               name.as_ptr() as *const raw::c_char,
+                                               name.len(),
+                                               value.inner())
+                .wrap(self)
         }
     }
 
     /// Binds an "inet" to a query or bound statement at the specified index.
     pub fn bind_inet(&mut self, index: usize, value: Inet) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_inet(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_inet(self.inner, index, value.inner())
+                .wrap(self)
         }
     }
 
     /// Binds an "inet" to all the values with the specified name.
     pub fn bind_inet_by_name(&mut self, name: &str, value: Inet) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_inet_by_name(self.0,
-                                             CString::new(name).expect("must be utf8").as_ptr(),
-                                             value.inner())
-                .to_result(self)
-                .chain_err(|| "")
-        }
-    }
-
-
-    // 	///Bind a "decimal" to a query or bound statement at the specified index.
-    //    pub fn bind_decimal(&self,
-    //                                index: i32,
-    //                                value: d128)
-    //                                -> Result<&mut Self, CassError> {
-    //            unsafe {
-    //                CassError::build(
-    //                    cass_statement_bind_decimal(
-    //                        self.0,
-    //                        index,
-    //                        value
-    //                    )
-    //                ).wrap(&mut self)
-    //            }
-    //        }
-
-    // Binds a "decimal" to all the values with the specified name.
-    //
-    // This can only be used with statements created by
-    // cass_prepared_bind().
-    //    pub fn bind_decimal_by_name<'a>(&'a self,
-    //                                    name: &str,
-    //                                    value: String)
-    //                                    -> Result<&'a Self, CassError> {
-    //        unsafe {
-    //            let name = CString::new(name).unwrap();
-    //            CassError::build(
-    //            cass_statement_bind_decimal_by_name(
-    //                self.0,
-    //                name.as_ptr(),
-    //                value
-    //            )
-    //        ).wrap(&self)
-    //        }
-    //    }
+            cass_statement_bind_inet_by_name_n(self.inner,
+                                               name.as_ptr() as *const raw::c_char,
+                                               name.len(),
+                                               value.inner())
+                .wrap(self)
+        }
+    }
+
+
+    /// Binds a "decimal" to a query or bound statement at the specified index.
+    pub fn bind_decimal(&mut self, index: usize, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe {
+            cass_statement_bind_decimal(self.inner, index, varint.as_ptr(), varint.len(), scale)
+                .wrap(self)
+        }
+    }
+
+    /// Binds a "decimal" to all the values with the specified name.
+    ///
+    /// This can only be used with statements created by
+    /// cass_prepared_bind().
+    pub fn bind_decimal_by_name(&mut self, name: &str, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe {
+            cass_statement_bind_decimal_by_name_n(self.inner,
+                                                  name.as_ptr() as *const raw::c_char,
+                                                  name.len(),
+                                                  varint.as_ptr(),
+                                                  varint.len(),
+                                                  scale)
+                .wrap(self)
+        }
+    }
+
+    /// Binds a "varint" to a query or bound statement at the specified index.
+    pub fn bind_varint(&mut self, index: usize, value: &BigInt) -> Result<&mut Self> {
+        self.bind_bytes(index, encode_varint(value))
+    }
+
+    /// Binds a "varint" to all the values with the specified name.
+    ///
+    /// This can only be used with statements created by
+    /// cass_prepared_bind().
+    pub fn bind_varint_by_name(&mut self, name: &str, value: &BigInt) -> Result<&mut Self> {
+        self.bind_bytes_by_name(name, encode_varint(value))
+    }
 
     /// Bind a "map" to a query or bound statement at the specified index.
     pub fn bind_map(&mut self, index: usize, map: Map) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection(self.0, index, map.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection(self.inner, index, map.inner())
+                .wrap(self)
         }
     }
 
@@ -650,19 +927,18 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_map_by_name(&mut self, name: &str, map: Map) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection_by_name(self.0,
-                                                   CString::new(name).expect("must be utf8").as_ptr(),
-                                                   map.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection_by_name_n(self.inner,
+                                                     name.as_ptr() as *const raw::c_char,
+                                                     name.len(),
+                                                     map.inner())
+                .wrap(self)
         }
     }
     /// Bind a "set" to a query or bound statement at the specified index.
     pub fn bind_set(&mut self, index: usize, collection: Set) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection(self.0, index, collection.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection(self.inner, index, collection.inner())
+                .wrap(self)
         }
     }
 
@@ -673,20 +949,19 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_set_by_name(&mut self, name: &str, collection: Set) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection_by_name(self.0,
-                                                   CString::new(name).expect("must be utf8").as_ptr(),
-                                                   collection.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection_by_name_n(self.inner,
+                                                     name.as_ptr() as *const raw::c_char,
+                                                     name.len(),
+                                                     collection.inner())
+                .wrap(self)
         }
     }
 
     /// Bind a "list" to a query or bound statement at the specified index.
     pub fn bind_list(&mut self, index: usize, collection: List) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection(self.0, index, collection.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection(self.inner, index, collection.inner())
+                .wrap(self)
         }
     }
 
@@ -697,20 +972,19 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_list_by_name(&mut self, name: &str, collection: List) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_collection_by_name(self.0,
-                                                   CString::new(name).expect("must be utf8").as_ptr(),
-                                                   collection.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_collection_by_name_n(self.inner,
+                                                     name.as_ptr() as *const raw::c_char,
+                                                     name.len(),
+                                                     collection.inner())
+                .wrap(self)
         }
     }
 
     /// Bind a "tuple" to a query or bound statement at the specified index.
     pub fn bind_tuple(&mut self, index: usize, value: Tuple) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_tuple(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_tuple(self.inner, index, value.inner())
+                .wrap(self)
         }
     }
 
@@ -720,11 +994,11 @@ impl Statement {
     /// cass_prepared_bind().
     pub fn bind_tuple_by_name(&mut self, name: &str, value: Tuple) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_tuple_by_name(self.0,
-                                              CString::new(name).expect("must be utf8").as_ptr(),
-                                              value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_tuple_by_name_n(self.inner,
+                                                name.as_ptr() as *const raw::c_char,
+                                                name.len(),
+                                                value.inner())
+                .wrap(self)
         }
     }
 
@@ -732,9 +1006,8 @@ impl Statement {
     /// specified index.
     pub fn bind_user_type(&mut self, index: usize, value: &UserType) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_user_type(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_user_type(self.inner, index, value.inner())
+                .wrap(self)
         }
     }
 
@@ -742,11 +1015,50 @@ impl Statement {
     /// specified name.
     pub fn bind_user_type_by_name(&mut self, name: &str, value: &UserType) -> Result<&mut Self> {
         unsafe {
-            cass_statement_bind_user_type_by_name(self.0,
-                                                  CString::new(name).expect("must be utf8").as_ptr(),
-                                                  value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_statement_bind_user_type_by_name_n(self.inner,
+                                                    name.as_ptr() as *const raw::c_char,
+                                                    name.len(),
+                                                    value.inner())
+                .wrap(self)
         }
     }
 }
+
+impl ProfileTarget for Statement {
+    fn apply_consistency(&mut self, consistency: Consistency) -> Result<()> {
+        self.set_consistency(consistency).map(|_| ())
+    }
+
+    fn apply_serial_consistency(&mut self, serial_consistency: Consistency) -> Result<()> {
+        self.set_serial_consistency(serial_consistency).map(|_| ())
+    }
+
+    fn apply_request_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.set_request_timeout(timeout).map(|_| ())
+    }
+
+    fn apply_retry_policy(&mut self, retry_policy: &RetryPolicy) -> Result<()> {
+        retry_policy.check_driver_policy()?;
+        unsafe { cass_statement_set_retry_policy(self.inner, retry_policy.inner()).wrap(()) }
+    }
+
+    fn apply_load_balancing_policy(&mut self, policy: &Arc<dyn LoadBalancingPolicy>) {
+        self.load_balancing_policy = Some(policy.clone());
+    }
+}
+
+#[test]
+fn test_bind_marker_count() {
+    assert_eq!(Statement::bind_marker_count("SELECT * FROM users"), 0);
+    assert_eq!(Statement::bind_marker_count("INSERT INTO users (id, name) VALUES (?, ?)"), 2);
+    assert_eq!(Statement::bind_marker_count("SELECT * FROM users WHERE id = :id AND name = :\"Name\""), 2);
+    assert_eq!(Statement::bind_marker_count("UPDATE users SET name = 'who?' WHERE id = ?"), 1);
+    assert_eq!(Statement::bind_marker_count("UPDATE users SET name = 'it''s :me?' WHERE \"why?\" = ?"), 1);
+    assert_eq!(Statement::bind_marker_count("SELECT * FROM users -- by id?\nWHERE id = ?"), 1);
+    assert_eq!(Statement::bind_marker_count("SELECT * FROM users // by id?\nWHERE id = ? /* or :name? */"), 1);
+    assert_eq!(Statement::bind_marker_count("CREATE FUNCTION f() RETURNS text LANGUAGE java AS $$ return '?'; $$"),
+               0);
+    assert_eq!(Statement::bind_marker_count("INSERT INTO flags (id, f) VALUES (?, {'a':true, 'b': :b, 'c':1})"), 2);
+    assert_eq!(Statement::bind_marker_count("SELECT * FROM users WHERE name = 'unterminated ?"), 0);
+    assert_eq!(Statement::bind_marker_count("SELECT ?"), 1);
+}
@@ -1,12 +1,17 @@
+use cassandra::error::*;
 use cassandra::util::Protected;
 
+use cassandra_sys::CASS_ERROR_LIB_INVALID_DATA;
 use cassandra_sys::CassTimestampGen as _TimestampGen;
+use cassandra_sys::cass_date_from_epoch;
+use cassandra_sys::cass_date_time_to_epoch;
 use cassandra_sys::cass_time_from_epoch;
 use cassandra_sys::cass_timestamp_gen_free;
 use cassandra_sys::cass_timestamp_gen_monotonic_new;
 use cassandra_sys::cass_timestamp_gen_server_side_new;
-// use cassandra_sys::cass_date_from_epoch;
-// use cassandra_sys::cass_date_time_to_epoch;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use time::Duration;
 
 /// Generators of client-side, microsecond-precision timestamps.
@@ -22,20 +27,208 @@ impl Protected<*mut _TimestampGen> for TimestampGen {
 }
 
 
-// ///Cassandra representation of the number of days since epoch
-// pub struct Date(u32);
+/// The Cassandra "date" type: the number of days since the Epoch (1970-01-01), with the
+/// Epoch centered at the value 2^31.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CqlDate(pub u32);
 
-/// Converts a unix timestamp (in seconds) to the Cassandra "time" type. The "time" type
-/// represents the number of nanoseconds since midnight (range 0 to 86399999999999).
-#[derive(Debug)]
-pub struct Time(i64);
+/// The Cassandra "time" type: the number of nanoseconds since midnight
+/// (range 0 to 86399999999999).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CqlTime(pub i64);
+
+/// The Cassandra "timestamp" type: the number of milliseconds since the Epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CqlTimestamp(pub i64);
+
+/// The Cassandra "duration" type. Months and days are kept apart from the nanoseconds
+/// because their length varies, so a duration only has a fixed length when `months` is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CqlDuration {
+    /// The number of months
+    pub months: i32,
+    /// The number of days
+    pub days: i32,
+    /// The number of nanoseconds
+    pub nanoseconds: i64,
+}
+
+const EPOCH_DATE: u32 = 1 << 31;
+const DAYS_FROM_CE_TO_EPOCH: i64 = 719_163;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_DAY: i64 = 86_400 * NANOS_PER_SECOND;
 
+fn out_of_range(what: &str, value: &dyn Display) -> Error {
+    CassError::with_message(CASS_ERROR_LIB_INVALID_DATA,
+                            format!("{} {} is out of range", what, value))
+        .into()
+}
+
+impl CqlDate {
+    /// Converts to a `NaiveDate`, failing if the date is outside of chrono's range.
+    pub fn to_naive_date(&self) -> Result<NaiveDate> {
+        let days_from_ce = self.0 as i64 - EPOCH_DATE as i64 + DAYS_FROM_CE_TO_EPOCH;
+        if days_from_ce < i32::MIN as i64 || days_from_ce > i32::MAX as i64 {
+            return Err(out_of_range("date", &self.0));
+        }
+        NaiveDate::from_num_days_from_ce_opt(days_from_ce as i32).ok_or_else(|| out_of_range("date", &self.0))
+    }
+}
+
+impl From<NaiveDate> for CqlDate {
+    fn from(date: NaiveDate) -> Self {
+        CqlDate((date.num_days_from_ce() as i64 - DAYS_FROM_CE_TO_EPOCH + EPOCH_DATE as i64) as u32)
+    }
+}
+
+impl CqlTime {
+    /// Converts to a `NaiveTime`, failing if the time is not within a day.
+    pub fn to_naive_time(&self) -> Result<NaiveTime> {
+        if self.0 < 0 || self.0 >= NANOS_PER_DAY {
+            return Err(out_of_range("time", &self.0));
+        }
+        NaiveTime::from_num_seconds_from_midnight_opt((self.0 / NANOS_PER_SECOND) as u32,
+                                                      (self.0 % NANOS_PER_SECOND) as u32)
+            .ok_or_else(|| out_of_range("time", &self.0))
+    }
+}
+
+impl From<NaiveTime> for CqlTime {
+    /// Converts from a `NaiveTime`. A leap second, which "time" cannot represent, is clamped
+    /// to the last nanosecond of its second.
+    fn from(time: NaiveTime) -> Self {
+        let nanos = (time.nanosecond() as i64).min(NANOS_PER_SECOND - 1);
+        CqlTime(time.num_seconds_from_midnight() as i64 * NANOS_PER_SECOND + nanos)
+    }
+}
+
+impl CqlTimestamp {
+    /// Converts to a `DateTime<Utc>`, failing if the timestamp is outside of chrono's range.
+    pub fn to_datetime(&self) -> Result<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.0).single().ok_or_else(|| out_of_range("timestamp", &self.0))
+    }
+}
+
+impl From<DateTime<Utc>> for CqlTimestamp {
+    /// Converts from a `DateTime<Utc>`, truncating to the millisecond precision of "timestamp".
+    fn from(datetime: DateTime<Utc>) -> Self { CqlTimestamp(datetime.timestamp_millis()) }
+}
+
+impl Display for CqlDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.to_naive_date() {
+            Ok(date) => write!(f, "{}", date),
+            Err(_) => write!(f, "{}", self.0 as i64 - EPOCH_DATE as i64),
+        }
+    }
+}
+
+impl Display for CqlTime {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.to_naive_time() {
+            Ok(time) => write!(f, "{}", time),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl Display for CqlTimestamp {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.to_datetime() {
+            Ok(datetime) => write!(f, "{}", datetime),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl Display for CqlDuration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}mo{}d{}ns", self.months, self.days, self.nanoseconds)
+    }
+}
+
+impl CqlDuration {
+    /// Creates a duration from its months, days and nanoseconds
+    pub fn new(months: i32, days: i32, nanoseconds: i64) -> Self {
+        CqlDuration {
+            months,
+            days,
+            nanoseconds,
+        }
+    }
+
+    /// Encodes the duration as its three zigzag-encoded vints, the form "duration" takes on
+    /// the wire. The driver binds and reads it as a "custom" value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(3);
+        encode_vint(self.months as i64, &mut bytes);
+        encode_vint(self.days as i64, &mut bytes);
+        encode_vint(self.nanoseconds, &mut bytes);
+        bytes
+    }
+
+    /// Decodes a duration from its wire form
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut rest = bytes;
+        let months = decode_vint(&mut rest)?;
+        let days = decode_vint(&mut rest)?;
+        let nanoseconds = decode_vint(&mut rest)?;
+        if !rest.is_empty() || months != months as i32 as i64 || days != days as i32 as i64 {
+            return Err(CassError::with_message(CASS_ERROR_LIB_INVALID_DATA, "malformed duration".to_owned()).into());
+        }
+        Ok(CqlDuration::new(months as i32, days as i32, nanoseconds))
+    }
+}
+
+/// Appends a signed vint, in Cassandra's variable length encoding, to `out`
+fn encode_vint(value: i64, out: &mut Vec<u8>) {
+    let value = ((value << 1) ^ (value >> 63)) as u64;
+    let magnitude = (value | 1).leading_zeros() as usize;
+    let size = (639 - magnitude * 9) >> 6;
+    if size == 9 {
+        out.push(0xff);
+    }
+    let bytes = size.min(8);
+    for i in (0..bytes).rev() {
+        out.push((value >> (8 * i)) as u8);
+    }
+    if size > 1 && size < 9 {
+        let first = out.len() - bytes;
+        out[first] |= !(0xffu8 >> (size - 1));
+    }
+}
+
+/// Reads a signed vint from the front of `bytes`, advancing past it
+fn decode_vint(bytes: &mut &[u8]) -> Result<i64> {
+    let malformed = || CassError::with_message(CASS_ERROR_LIB_INVALID_DATA, "malformed duration".to_owned());
+    let first = *bytes.first().ok_or_else(malformed)?;
+    let extra = (!first).leading_zeros() as usize;
+    if bytes.len() < extra + 1 {
+        return Err(malformed().into());
+    }
+    let mut value = (first as u64) & (0xff >> extra);
+    for byte in &bytes[1..extra + 1] {
+        value = (value << 8) | *byte as u64;
+    }
+    *bytes = &bytes[extra + 1..];
+    Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+}
 
 impl TimestampGen {
     /// Converts a unix timestamp (in seconds) to the Cassandra "time" type. The "time" type
     /// represents the number of nanoseconds since midnight (range 0 to 86399999999999).
-    pub fn time_from_epoch(epoch_seconds: Duration) -> Time {
-        unsafe { Time(cass_time_from_epoch(epoch_seconds.num_seconds())) }
+    pub fn time_from_epoch(epoch_seconds: Duration) -> CqlTime {
+        unsafe { CqlTime(cass_time_from_epoch(epoch_seconds.num_seconds())) }
+    }
+
+    /// Converts a unix timestamp (in seconds) to the Cassandra "date" type.
+    pub fn date_from_epoch(epoch_seconds: Duration) -> CqlDate {
+        unsafe { CqlDate(cass_date_from_epoch(epoch_seconds.num_seconds())) }
+    }
+
+    /// Combines the Cassandra "date" and "time" types to Epoch time in seconds.
+    pub fn date_time_to_epoch(date: CqlDate, time: CqlTime) -> Duration {
+        unsafe { Duration::seconds(cass_date_time_to_epoch(date.0, time.0)) }
     }
 
     /// Creates a new monotonically increasing timestamp generator. This generates
@@ -51,24 +244,48 @@ impl TimestampGen {
     ///
     /// <b>Note:</b> This is the default timestamp generator.
     pub fn gen_server_side_new() -> Self { unsafe { TimestampGen(cass_timestamp_gen_server_side_new()) } }
+}
+
+impl Drop for TimestampGen {
+    fn drop(&mut self) { unsafe { cass_timestamp_gen_free(self.0) } }
+}
 
-    //    pub fn from_epoch() -> Self {
-    //        unsafe { TimestampGen(cass_timestamp_gen_monotonic_new()) }
-    //    }
+#[test]
+fn test_date_conversions() {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    assert_eq!(CqlDate::from(epoch), CqlDate(1 << 31));
+    let date = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+    assert_eq!(CqlDate::from(date), CqlDate((1 << 31) - 1));
+    assert_eq!(CqlDate::from(date).to_naive_date().unwrap(), date);
+    assert!(CqlDate(0).to_naive_date().is_err());
 }
 
-// Converts a unix timestamp (in seconds) to the Cassandra "date" type. The "date" type
-// represents the number of days since the Epoch (1970-01-01) with the Epoch centered at
-// the value 2^31.
-// fn date_from_epoch(epoch_secs: Duration) -> Date {
-//    unsafe { Date(cass_date_from_epoch(epoch_secs.num_days())) }
-// }
+#[test]
+fn test_time_conversions() {
+    let time = NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap();
+    assert_eq!(CqlTime::from(time), CqlTime(86_399_999_999_999));
+    assert_eq!(CqlTime::from(time).to_naive_time().unwrap(), time);
+    assert!(CqlTime(86_400_000_000_000).to_naive_time().is_err());
+    assert!(CqlTime(-1).to_naive_time().is_err());
+}
 
-// Combines the Cassandra "date" and "time" types to Epoch time in seconds.
-// fn date_time_to_epoch(date: Date, time: Time) -> Duration {
-//   unsafe { Duration::seconds(cass_date_time_to_epoch(date.0, time.0)) }
-// }
+#[test]
+fn test_timestamp_conversions() {
+    let datetime = Utc.timestamp_millis_opt(-1_234_567).unwrap();
+    assert_eq!(CqlTimestamp::from(datetime), CqlTimestamp(-1_234_567));
+    assert_eq!(CqlTimestamp(-1_234_567).to_datetime().unwrap(), datetime);
+}
 
-impl Drop for TimestampGen {
-    fn drop(&mut self) { unsafe { cass_timestamp_gen_free(self.0) } }
+#[test]
+fn test_duration_bytes() {
+    assert_eq!(CqlDuration::new(1, -1, 0).to_bytes(), vec![2, 1, 0]);
+    assert_eq!(CqlDuration::new(0, 0, 100).to_bytes(), vec![0, 0, 0x80, 200]);
+    for duration in &[CqlDuration::new(0, 0, 0),
+                      CqlDuration::new(14, 3, 1_500_000_000),
+                      CqlDuration::new(i32::MIN, i32::MAX, i64::MIN),
+                      CqlDuration::new(-1, -30, i64::MAX)] {
+        assert_eq!(CqlDuration::from_bytes(&duration.to_bytes()).unwrap(), *duration);
+    }
+    assert!(CqlDuration::from_bytes(&[0, 0]).is_err());
+    assert!(CqlDuration::from_bytes(&[0, 0, 0xc0, 1]).is_err());
 }
@@ -4,8 +4,10 @@ use cassandra::data_type::DataType;
 use cassandra::error::CassError;
 
 use cassandra::inet::AsInet;
+use cassandra::numeric::{encode_decimal, encode_varint};
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
 use cassandra::user_type::UserType;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 use cassandra::uuid::Uuid;
 use cassandra_sys::CassTuple as _Tuple;
 use cassandra_sys::cass_false;
@@ -17,7 +19,6 @@ use cassandra_sys::cass_tuple_new_from_data_type;
 use cassandra_sys::cass_tuple_set_bool;
 use cassandra_sys::cass_tuple_set_bytes;
 use cassandra_sys::cass_tuple_set_collection;
-#[allow(unused_imports)]
 use cassandra_sys::cass_tuple_set_decimal;
 use cassandra_sys::cass_tuple_set_double;
 use cassandra_sys::cass_tuple_set_float;
@@ -27,14 +28,16 @@ use cassandra_sys::cass_tuple_set_int32;
 use cassandra_sys::cass_tuple_set_int64;
 use cassandra_sys::cass_tuple_set_int8;
 use cassandra_sys::cass_tuple_set_null;
-use cassandra_sys::cass_tuple_set_string;
+use cassandra_sys::cass_tuple_set_string_n;
 use cassandra_sys::cass_tuple_set_tuple;
 use cassandra_sys::cass_tuple_set_uint32;
 use cassandra_sys::cass_tuple_set_user_type;
 use cassandra_sys::cass_tuple_set_uuid;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
+use decimal::d128;
+use num::BigInt;
 use std::net::SocketAddr;
+use std::os::raw::c_char;
 
 
 /// A tuple of values.
@@ -62,8 +65,7 @@ impl Tuple {
     pub fn set_null(&mut self, index: usize) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_null(self.0, index)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -71,8 +73,7 @@ impl Tuple {
     pub fn set_int8(&mut self, index: usize, value: i8) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_int8(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -80,8 +81,7 @@ impl Tuple {
     pub fn set_int16(&mut self, index: usize, value: i16) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_int16(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -89,8 +89,7 @@ impl Tuple {
     pub fn set_int32(&mut self, index: usize, value: i32) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_int32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -98,8 +97,7 @@ impl Tuple {
     pub fn set_uint32(&mut self, index: usize, value: u32) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_uint32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -108,8 +106,7 @@ impl Tuple {
     pub fn set_int64(&mut self, index: usize, value: i64) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_int64(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -117,8 +114,7 @@ impl Tuple {
     pub fn set_float(&mut self, index: usize, value: f32) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_float(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -126,8 +122,7 @@ impl Tuple {
     pub fn set_double(&mut self, index: usize, value: f64) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_double(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -135,20 +130,17 @@ impl Tuple {
     pub fn set_bool(&mut self, index: usize, value: bool) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_bool(self.0, index, if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets an "ascii", "text" or "varchar" in a tuple at the specified index.
     pub fn set_string<S>(&mut self, index: usize, value: S) -> Result<&mut Self>
         where S: Into<String> {
+        let value = value.into();
         unsafe {
-            cass_tuple_set_string(self.0,
-                                  index,
-                                  CString::new(value.into()).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_tuple_set_string_n(self.0, index, value.as_ptr() as *const c_char, value.len())
+                .wrap(self)
         }
     }
 
@@ -156,18 +148,46 @@ impl Tuple {
     pub fn set_bytes(&mut self, index: usize, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_bytes(self.0, index, value.as_ptr(), value.len())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
+    /// Sets a "decimal" in a tuple at the specified index.
+    pub fn set_decimal(&mut self, index: usize, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe {
+            cass_tuple_set_decimal(self.0, index, varint.as_ptr(), varint.len(), scale)
+                .wrap(self)
+        }
+    }
+
+    /// Sets a "varint" in a tuple at the specified index.
+    pub fn set_varint(&mut self, index: usize, value: &BigInt) -> Result<&mut Self> {
+        self.set_bytes(index, encode_varint(value))
+    }
+
+    /// Sets a "date" in a tuple at the specified index.
+    pub fn set_date(&mut self, index: usize, value: CqlDate) -> Result<&mut Self> { self.set_uint32(index, value.0) }
+
+    /// Sets a "time" in a tuple at the specified index.
+    pub fn set_time(&mut self, index: usize, value: CqlTime) -> Result<&mut Self> { self.set_int64(index, value.0) }
+
+    /// Sets a "timestamp" in a tuple at the specified index.
+    pub fn set_timestamp(&mut self, index: usize, value: CqlTimestamp) -> Result<&mut Self> {
+        self.set_int64(index, value.0)
+    }
+
+    /// Sets a "duration" in a tuple at the specified index.
+    pub fn set_duration(&mut self, index: usize, value: CqlDuration) -> Result<&mut Self> {
+        self.set_bytes(index, value.to_bytes())
+    }
+
     /// Sets a "uuid" or "timeuuid" in a tuple at the specified index.
     pub fn set_uuid<S>(&mut self, index: usize, value: S) -> Result<&mut Self>
         where S: Into<Uuid> {
         unsafe {
             cass_tuple_set_uuid(self.0, index, value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -176,8 +196,7 @@ impl Tuple {
         let inet = AsInet::as_cass_inet(&value);
         unsafe {
             cass_tuple_set_inet(self.0, index, inet.inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -186,8 +205,7 @@ impl Tuple {
         where S: Into<Set> {
         unsafe {
             cass_tuple_set_collection(self.0, index, value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -195,8 +213,7 @@ impl Tuple {
     pub fn set_tuple(&mut self, index: usize, value: Tuple) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_tuple(self.0, index, value.0)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -204,8 +221,7 @@ impl Tuple {
     pub fn set_user_type(&mut self, index: usize, value: &UserType) -> Result<&mut Self> {
         unsafe {
             cass_tuple_set_user_type(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 }
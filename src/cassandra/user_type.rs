@@ -4,8 +4,10 @@ use cassandra::collection::Set;
 use cassandra::data_type::ConstDataType;
 use cassandra::error::CassError;
 use cassandra::inet::Inet;
+use cassandra::numeric::{encode_decimal, encode_varint};
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
 use cassandra::tuple::Tuple;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 
 use cassandra::uuid::Uuid;
 use cassandra_sys::CassUserType as _UserType;
@@ -15,44 +17,44 @@ use cassandra_sys::cass_true;
 use cassandra_sys::cass_user_type_data_type;
 use cassandra_sys::cass_user_type_free;
 use cassandra_sys::cass_user_type_set_bool;
-use cassandra_sys::cass_user_type_set_bool_by_name;
+use cassandra_sys::cass_user_type_set_bool_by_name_n;
 use cassandra_sys::cass_user_type_set_bytes;
-use cassandra_sys::cass_user_type_set_bytes_by_name;
+use cassandra_sys::cass_user_type_set_bytes_by_name_n;
 use cassandra_sys::cass_user_type_set_collection;
-use cassandra_sys::cass_user_type_set_collection_by_name;
-#[allow(unused_imports)]
+use cassandra_sys::cass_user_type_set_collection_by_name_n;
 use cassandra_sys::cass_user_type_set_decimal;
-#[allow(unused_imports)]
-use cassandra_sys::cass_user_type_set_decimal_by_name;
+use cassandra_sys::cass_user_type_set_decimal_by_name_n;
 use cassandra_sys::cass_user_type_set_double;
-use cassandra_sys::cass_user_type_set_double_by_name;
+use cassandra_sys::cass_user_type_set_double_by_name_n;
 use cassandra_sys::cass_user_type_set_float;
-use cassandra_sys::cass_user_type_set_float_by_name;
+use cassandra_sys::cass_user_type_set_float_by_name_n;
 use cassandra_sys::cass_user_type_set_inet;
-use cassandra_sys::cass_user_type_set_inet_by_name;
+use cassandra_sys::cass_user_type_set_inet_by_name_n;
 use cassandra_sys::cass_user_type_set_int16;
-use cassandra_sys::cass_user_type_set_int16_by_name;
+use cassandra_sys::cass_user_type_set_int16_by_name_n;
 use cassandra_sys::cass_user_type_set_int32;
-use cassandra_sys::cass_user_type_set_int32_by_name;
+use cassandra_sys::cass_user_type_set_int32_by_name_n;
 use cassandra_sys::cass_user_type_set_int64;
-use cassandra_sys::cass_user_type_set_int64_by_name;
+use cassandra_sys::cass_user_type_set_int64_by_name_n;
 use cassandra_sys::cass_user_type_set_int8;
-use cassandra_sys::cass_user_type_set_int8_by_name;
+use cassandra_sys::cass_user_type_set_int8_by_name_n;
 use cassandra_sys::cass_user_type_set_null;
-use cassandra_sys::cass_user_type_set_null_by_name;
-use cassandra_sys::cass_user_type_set_string;
-use cassandra_sys::cass_user_type_set_string_by_name;
+use cassandra_sys::cass_user_type_set_null_by_name_n;
+use cassandra_sys::cass_user_type_set_string_n;
+use cassandra_sys::cass_user_type_set_string_by_name_n;
 use cassandra_sys::cass_user_type_set_tuple;
-use cassandra_sys::cass_user_type_set_tuple_by_name;
+use cassandra_sys::cass_user_type_set_tuple_by_name_n;
 use cassandra_sys::cass_user_type_set_uint32;
-use cassandra_sys::cass_user_type_set_uint32_by_name;
+use cassandra_sys::cass_user_type_set_uint32_by_name_n;
 use cassandra_sys::cass_user_type_set_user_type;
-use cassandra_sys::cass_user_type_set_user_type_by_name;
+use cassandra_sys::cass_user_type_set_user_type_by_name_n;
 use cassandra_sys::cass_user_type_set_uuid;
 
-use cassandra_sys::cass_user_type_set_uuid_by_name;
-use errors::*;
-use std::ffi::CString;
+use cassandra_sys::cass_user_type_set_uuid_by_name_n;
+use cassandra::error::*;
+use decimal::d128;
+use num::BigInt;
+use std::os::raw::c_char;
 // use cassandra::iterator::FieldIterator;
 
 /// A user defined type
@@ -83,19 +85,17 @@ impl UserType {
     pub fn set_null(&mut self, index: usize) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_null(self.0, index)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a null in a user defined type at the specified name.
     pub fn set_null_by_name<S>(&mut self, name: S) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_null_by_name(self.0,
-                                            CString::new(name.into()).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_null_by_name_n(self.0, name.as_ptr() as *const c_char, name.len())
+                .wrap(self)
         }
     }
 
@@ -103,24 +103,17 @@ impl UserType {
     pub fn set_int8(&mut self, index: usize, value: i8) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_int8(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "tinyint" in a user defined type at the specified name.
     pub fn set_int8_by_name<S>(&mut self, name: S, value: i8) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            match CString::new(name.into()) {
-                Ok(name) => {
-                    cass_user_type_set_int8_by_name(self.0, name.as_ptr(), value)
-                        .to_result(self)
-                        .chain_err(|| "")
-                }
-                Err(err) => panic!("error: {}", err),
-            }
-
+            cass_user_type_set_int8_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -128,24 +121,17 @@ impl UserType {
     pub fn set_int16(&mut self, index: usize, value: i16) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_int16(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets an "smallint" in a user defined type at the specified name.
     pub fn set_int16_by_name<S>(&mut self, name: S, value: i16) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            match CString::new(name.into()) {
-                Ok(name) => {
-                    cass_user_type_set_int16_by_name(self.0, name.as_ptr(), value)
-                        .to_result(self)
-                        .chain_err(|| "")
-                }
-                Err(err) => panic!("error: {}", err),
-            }
-
+            cass_user_type_set_int16_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -153,24 +139,17 @@ impl UserType {
     pub fn set_int32(&mut self, index: usize, value: i32) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_int32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets an "int" in a user defined type at the specified name.
     pub fn set_int32_by_name<S>(&mut self, name: S, value: i32) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            match CString::new(name.into()) {
-                Ok(name) => {
-                    cass_user_type_set_int32_by_name(self.0, name.as_ptr(), value)
-                        .to_result(self)
-                        .chain_err(|| "")
-                }
-                Err(err) => panic!("error: {}", err),
-            }
-
+            cass_user_type_set_int32_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -178,24 +157,17 @@ impl UserType {
     pub fn set_uint32(&mut self, index: usize, value: u32) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_uint32(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "date" in a user defined type at the specified name.
     pub fn set_uint32_by_name<S>(&mut self, name: S, value: u32) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            match CString::new(name.into()) {
-                Ok(name) => {
-                    cass_user_type_set_uint32_by_name(self.0, name.as_ptr(), value)
-                        .to_result(self)
-                        .chain_err(|| "")
-                }
-                Err(err) => panic!("error: {}", err),
-            }
-
+            cass_user_type_set_uint32_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -205,8 +177,7 @@ impl UserType {
     pub fn set_int64(&mut self, index: usize, value: i64) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_int64(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -214,14 +185,10 @@ impl UserType {
     /// user defined type at the specified name.
     pub fn set_int64_by_name<S>(&mut self, name: S, value: i64) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_int64_by_name(self.0,
-                                             CString::new(name.into())
-                                                 .expect("must be utf8")
-                                                 .as_ptr(),
-                                             value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_int64_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -229,22 +196,17 @@ impl UserType {
     pub fn set_float(&mut self, index: usize, value: f32) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_float(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "float" in a user defined type at the specified name.
     pub fn set_float_by_name<S>(&mut self, name: S, value: f32) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_float_by_name(self.0,
-                                             CString::new(name.into())
-                                                 .expect("must be utf8")
-                                                 .as_ptr(),
-                                             value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_float_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -252,8 +214,7 @@ impl UserType {
     pub fn set_double(&mut self, index: usize, value: f64) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_double(self.0, index, value)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -261,14 +222,10 @@ impl UserType {
 
     pub fn set_double_by_name<S>(&mut self, name: S, value: f64) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_double_by_name(self.0,
-                                              CString::new(name.into())
-                                                  .expect("must be utf8")
-                                                  .as_ptr(),
-                                              value)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_double_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value)
+                .wrap(self)
         }
     }
 
@@ -276,20 +233,20 @@ impl UserType {
     pub fn set_bool(&mut self, index: usize, value: bool) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_bool(self.0, index, if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "boolean" in a user defined type at the specified name.
     pub fn set_bool_by_name<S>(&mut self, name: S, value: bool) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_bool_by_name(self.0,
-                                            CString::new(name.into()).expect("must be utf8").as_ptr(),
-                                            if value { cass_true } else { cass_false })
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_bool_by_name_n(self.0,
+                                              name.as_ptr() as *const c_char,
+                                              name.len(),
+                                              if value { cass_true } else { cass_false })
+                .wrap(self)
         }
     }
 
@@ -297,12 +254,10 @@ impl UserType {
     /// specified index.
     pub fn set_stringl<S>(&mut self, index: usize, value: S) -> Result<&mut Self>
         where S: Into<String> {
+        let value = value.into();
         unsafe {
-            cass_user_type_set_string(self.0,
-                                      index,
-                                      CString::new(value.into()).expect("must be utf8").as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_string_n(self.0, index, value.as_ptr() as *const c_char, value.len())
+                .wrap(self)
         }
     }
 
@@ -310,16 +265,15 @@ impl UserType {
     /// specified name.
     pub fn set_string_by_name<S>(&mut self, name: S, value: S) -> Result<&mut Self>
         where S: Into<String> {
+        let value = value.into();
+        let name = name.into();
         unsafe {
-            cass_user_type_set_string_by_name(self.0,
-                                              CString::new(name.into())
-                                                  .expect("must be utf8")
-                                                  .as_ptr(),
-                                              CString::new(value.into())
-                                                  .expect("must be utf8")
-                                                  .as_ptr())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_string_by_name_n(self.0,
+                                                name.as_ptr() as *const c_char,
+                                                name.len(),
+                                                value.as_ptr() as *const c_char,
+                                                value.len())
+                .wrap(self)
         }
     }
 
@@ -328,45 +282,119 @@ impl UserType {
     pub fn set_bytes(&mut self, index: usize, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_bytes(self.0, index, value.as_ptr(), value.len())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "blob", "varint" or "custom" in a user defined type at the specified name.
     pub fn set_bytes_by_name<S>(&mut self, name: S, value: Vec<u8>) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
+        unsafe {
+            cass_user_type_set_bytes_by_name_n(self.0,
+                                               name.as_ptr() as *const c_char,
+                                               name.len(),
+                                               value.as_ptr(),
+                                               value.len())
+                .wrap(self)
+        }
+    }
+
+    /// Sets a "decimal" in a user defined type at the specified index.
+    pub fn set_decimal(&mut self, index: usize, value: d128) -> Result<&mut Self> {
+        let (varint, scale) = encode_decimal(value)?;
         unsafe {
-            cass_user_type_set_bytes_by_name(self.0,
-                                             CString::new(name.into())
-                                                 .expect("must be utf8")
-                                                 .as_ptr(),
-                                             value.as_ptr(),
-                                             value.len())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_decimal(self.0, index, varint.as_ptr(), varint.len(), scale)
+                .wrap(self)
         }
     }
 
+    /// Sets a "decimal" in a user defined type at the specified name.
+    pub fn set_decimal_by_name<S>(&mut self, name: S, value: d128) -> Result<&mut Self>
+        where S: Into<String> {
+        let name = name.into();
+        let (varint, scale) = encode_decimal(value)?;
+        unsafe {
+            cass_user_type_set_decimal_by_name_n(self.0,
+                                                 name.as_ptr() as *const c_char,
+                                                 name.len(),
+                                                 varint.as_ptr(),
+                                                 varint.len(),
+                                                 scale)
+                .wrap(self)
+        }
+    }
+
+    /// Sets a "varint" in a user defined type at the specified index.
+    pub fn set_varint(&mut self, index: usize, value: &BigInt) -> Result<&mut Self> {
+        self.set_bytes(index, encode_varint(value))
+    }
+
+    /// Sets a "varint" in a user defined type at the specified name.
+    pub fn set_varint_by_name<S>(&mut self, name: S, value: &BigInt) -> Result<&mut Self>
+        where S: Into<String> {
+        self.set_bytes_by_name(name, encode_varint(value))
+    }
+
+    /// Sets a "date" in a user defined type at the specified index.
+    pub fn set_date(&mut self, index: usize, value: CqlDate) -> Result<&mut Self> { self.set_uint32(index, value.0) }
+
+    /// Sets a "date" in a user defined type at the specified name.
+    pub fn set_date_by_name<S>(&mut self, name: S, value: CqlDate) -> Result<&mut Self>
+        where S: Into<String> {
+        self.set_uint32_by_name(name, value.0)
+    }
+
+    /// Sets a "time" in a user defined type at the specified index.
+    pub fn set_time(&mut self, index: usize, value: CqlTime) -> Result<&mut Self> { self.set_int64(index, value.0) }
+
+    /// Sets a "time" in a user defined type at the specified name.
+    pub fn set_time_by_name<S>(&mut self, name: S, value: CqlTime) -> Result<&mut Self>
+        where S: Into<String> {
+        self.set_int64_by_name(name, value.0)
+    }
+
+    /// Sets a "timestamp" in a user defined type at the specified index.
+    pub fn set_timestamp(&mut self, index: usize, value: CqlTimestamp) -> Result<&mut Self> {
+        self.set_int64(index, value.0)
+    }
+
+    /// Sets a "timestamp" in a user defined type at the specified name.
+    pub fn set_timestamp_by_name<S>(&mut self, name: S, value: CqlTimestamp) -> Result<&mut Self>
+        where S: Into<String> {
+        self.set_int64_by_name(name, value.0)
+    }
+
+    /// Sets a "duration" in a user defined type at the specified index.
+    pub fn set_duration(&mut self, index: usize, value: CqlDuration) -> Result<&mut Self> {
+        self.set_bytes(index, value.to_bytes())
+    }
+
+    /// Sets a "duration" in a user defined type at the specified name.
+    pub fn set_duration_by_name<S>(&mut self, name: S, value: CqlDuration) -> Result<&mut Self>
+        where S: Into<String> {
+        self.set_bytes_by_name(name, value.to_bytes())
+    }
+
     /// Sets a "uuid" or "timeuuid" in a user defined type at the specified index.
     pub fn set_uuid<S>(&mut self, index: usize, value: S) -> Result<&mut Self>
         where S: Into<Uuid> {
         unsafe {
             cass_user_type_set_uuid(self.0, index, value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "uuid" or "timeuuid" in a user defined type at the specified name.
     pub fn set_uuid_by_name<S, U>(&mut self, name: S, value: U) -> Result<&mut Self>
         where S: Into<String>, U: Into<Uuid> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_uuid_by_name(self.0,
-                                            CString::new(name.into()).expect("must be utf8").as_ptr(),
-                                            value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_uuid_by_name_n(self.0,
+                                              name.as_ptr() as *const c_char,
+                                              name.len(),
+                                              value.into().inner())
+                .wrap(self)
         }
     }
 
@@ -375,20 +403,20 @@ impl UserType {
         where S: Into<Inet> {
         unsafe {
             cass_user_type_set_inet(self.0, index, value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "inet" in a user defined type at the specified name.
     pub fn set_inet_by_name<S, U>(&mut self, name: S, value: U) -> Result<&mut Self>
         where S: Into<String>, U: Into<Inet> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_inet_by_name(self.0,
-                                            CString::new(name.into()).expect("must be utf8").as_ptr(),
-                                            value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_inet_by_name_n(self.0,
+                                              name.as_ptr() as *const c_char,
+                                              name.len(),
+                                              value.into().inner())
+                .wrap(self)
         }
     }
 
@@ -397,8 +425,7 @@ impl UserType {
         where S: Into<Set> {
         unsafe {
             cass_user_type_set_collection(self.0, index, value.into().inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
@@ -406,14 +433,10 @@ impl UserType {
     /// specified name.
     pub fn set_collection_by_name<S>(&mut self, name: S, value: Set) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_collection_by_name(self.0,
-                                                  CString::new(name.into())
-                                                      .expect("must be utf8")
-                                                      .as_ptr(),
-                                                  value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_collection_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value.inner())
+                .wrap(self)
         }
     }
 
@@ -421,22 +444,17 @@ impl UserType {
     pub fn set_tuple(&mut self, index: usize, value: Tuple) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_tuple(self.0, index, value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a "tuple" in a user defined type at the specified name.
     pub fn set_tuple_by_name<S>(&mut self, name: S, value: Tuple) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_tuple_by_name(self.0,
-                                             CString::new(name.into())
-                                                 .expect("must be utf8")
-                                                 .as_ptr(),
-                                             value.inner())
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_tuple_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value.inner())
+                .wrap(self)
         }
     }
 
@@ -444,22 +462,17 @@ impl UserType {
     pub fn set_user_type(&mut self, index: usize, value: UserType) -> Result<&mut Self> {
         unsafe {
             cass_user_type_set_user_type(self.0, index, value.0)
-                .to_result(self)
-                .chain_err(|| "")
+                .wrap(self)
         }
     }
 
     /// Sets a user defined type in a user defined type at the specified name.
     pub fn set_user_type_by_name<S>(&mut self, name: S, value: UserType) -> Result<&mut Self>
         where S: Into<String> {
+        let name = name.into();
         unsafe {
-            cass_user_type_set_user_type_by_name(self.0,
-                                                 CString::new(name.into())
-                                                     .expect("must be utf8")
-                                                     .as_ptr(),
-                                                 value.0)
-                .to_result(self)
-                .chain_err(|| "")
+            cass_user_type_set_user_type_by_name_n(self.0, name.as_ptr() as *const c_char, name.len(), value.0)
+                .wrap(self)
         }
     }
 }
@@ -1,4 +1,122 @@
+use cassandra::error::{CassError, Result};
+use cassandra_sys::{CASS_ERROR_LIB_BAD_PARAMS, CASS_OK};
+use cassandra_sys::CassError as _CassError;
+use std::os::raw::c_char;
+use std::{slice, str};
+use time::Duration;
+
 pub trait Protected<T> {
     fn build(inner: T) -> Self;
     fn inner(&self) -> T;
 }
+
+/// Converts the error codes returned by driver calls into results
+pub trait CassErrorExt {
+    /// Returns `wrappee` if the call succeeded, or the error otherwise
+    fn wrap<T>(self, wrappee: T) -> Result<T>;
+}
+
+impl CassErrorExt for _CassError {
+    fn wrap<T>(self, wrappee: T) -> Result<T> {
+        match self {
+            CASS_OK => Ok(wrappee),
+            err => Err(err.into()),
+        }
+    }
+}
+
+/// The request timeout the driver reads as "use the cluster's", its `CASS_UINT64_MAX`
+pub const CLUSTER_REQUEST_TIMEOUT: u64 = u64::MAX;
+
+/// Converts a request timeout into the milliseconds the driver expects, where 0 means no
+/// timeout. A timeout shorter than a millisecond is rounded up, so it does not disable the
+/// timeout, and a negative timeout is rejected.
+pub fn timeout_millis(timeout: Duration) -> Result<u64> {
+    if timeout < Duration::zero() {
+        return Err(CassError::with_message(CASS_ERROR_LIB_BAD_PARAMS, "request timeout must not be negative").into());
+    }
+    let millis = timeout.num_milliseconds();
+    if timeout > Duration::milliseconds(millis) {
+        Ok(millis as u64 + 1)
+    } else {
+        Ok(millis as u64)
+    }
+}
+
+/// Copies a string handed out by the driver, failing if it is not valid UTF-8
+pub unsafe fn to_owned_string(name: *const c_char, length: usize) -> Result<String> {
+    let slice = slice::from_raw_parts(name as *const u8, length);
+    Ok(str::from_utf8(slice)?.to_owned())
+}
+
+/// Deterministic pseudo-random byte strings for fuzzing the conversions of driver input and output,
+/// drawn mostly from `alphabet` and occasionally from the whole byte range
+#[cfg(test)]
+pub fn fuzz_inputs(alphabet: &[u8], count: usize) -> Vec<Vec<u8>> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..count)
+        .map(|_| {
+            let len = (next() % 32) as usize;
+            (0..len)
+                .map(|_| match next() % 8 {
+                    0 => next() as u8,
+                    _ => alphabet[(next() % alphabet.len() as u64) as usize],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_timeout_millis() {
+    assert_eq!(timeout_millis(Duration::zero()).unwrap(), 0);
+    assert_eq!(timeout_millis(Duration::microseconds(1)).unwrap(), 1);
+    assert_eq!(timeout_millis(Duration::milliseconds(1500)).unwrap(), 1500);
+    assert_eq!(timeout_millis(Duration::microseconds(1_500_001)).unwrap(), 1501);
+    assert!(timeout_millis(Duration::milliseconds(-1)).is_err());
+    assert!(timeout_millis(Duration::nanoseconds(-1)).is_err());
+}
+
+#[cfg(test)]
+fn convert(input: &[u8]) -> Result<String> { unsafe { to_owned_string(input.as_ptr() as *const c_char, input.len()) } }
+
+#[test]
+fn test_to_owned_string() {
+    use cassandra::error::Error;
+
+    assert_eq!(convert(b"").unwrap(), "");
+    assert_eq!(convert(b"keyspace").unwrap(), "keyspace");
+    assert_eq!(convert("caf\u{e9} \u{20ac}".as_bytes()).unwrap(), "caf\u{e9} \u{20ac}");
+    assert_eq!(convert(b"a\0b\0").unwrap(), "a\0b\0");
+
+    match convert(b"caf\xc3") {
+        Err(Error::InvalidUtf8(err)) => assert_eq!(err.valid_up_to(), 3),
+        other => panic!("expected invalid utf8, got {:?}", other.map_err(|_| ())),
+    }
+    match convert(b"\xff\0abc") {
+        Err(Error::InvalidUtf8(err)) => assert_eq!(err.valid_up_to(), 0),
+        other => panic!("expected invalid utf8, got {:?}", other.map_err(|_| ())),
+    }
+}
+
+#[test]
+fn test_to_owned_string_fuzz() {
+    use cassandra::error::Error;
+
+    for input in fuzz_inputs(b"abc\0\xc3\xa9\xe2\x82\xac\xff", 10_000) {
+        match convert(&input) {
+            Ok(text) => assert_eq!(text.as_bytes(), &input[..]),
+            Err(Error::InvalidUtf8(err)) => {
+                let valid = &input[..err.valid_up_to()];
+                assert_eq!(convert(valid).unwrap().as_bytes(), valid);
+            }
+            Err(_) => panic!("unexpected error converting {:?}", input),
+        }
+    }
+}
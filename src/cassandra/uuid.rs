@@ -1,13 +1,13 @@
 
 
 use cassandra::error::CassError;
-use cassandra::util::Protected;
+use cassandra::util::{CassErrorExt, Protected};
 
 
 use cassandra_sys::CASS_OK;
 use cassandra_sys::CassUuid as _Uuid;
 use cassandra_sys::CassUuidGen as _UuidGen;
-use cassandra_sys::cass_uuid_from_string;
+use cassandra_sys::cass_uuid_from_string_n;
 use cassandra_sys::cass_uuid_gen_free;
 use cassandra_sys::cass_uuid_gen_from_time;
 use cassandra_sys::cass_uuid_gen_new;
@@ -20,12 +20,13 @@ use cassandra_sys::cass_uuid_min_from_time;
 use cassandra_sys::cass_uuid_string;
 use cassandra_sys::cass_uuid_timestamp;
 use cassandra_sys::cass_uuid_version;
-use errors::*;
+use cassandra::error::*;
 use std::ffi::CString;
 use std::fmt;
 use std::fmt::{Debug, Display};
 use std::fmt::Formatter;
 use std::mem;
+use std::os::raw::c_char;
 use std::str;
 
 const CASS_UUID_STRING_LENGTH: usize = 37;
@@ -56,6 +57,14 @@ impl Drop for UuidGen {
     fn drop(&mut self) { unsafe { cass_uuid_gen_free(self.0) } }
 }
 
+impl PartialEq for Uuid {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.0.time_and_version == other.0.time_and_version && self.0.clock_seq_and_node == other.0.clock_seq_and_node
+    }
+}
+
+impl Eq for Uuid {}
+
 impl Debug for Uuid {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result { fmt::Display::fmt(self, f) }
 }
@@ -96,11 +105,10 @@ impl str::FromStr for Uuid {
     fn from_str(str: &str) -> Result<Uuid> {
         unsafe {
             let mut uuid = mem::zeroed();
-            match cass_uuid_from_string(CString::new(str).expect("must be utf8").as_ptr(), &mut uuid) {
+            match cass_uuid_from_string_n(str.as_ptr() as *const c_char, str.len(), &mut uuid) {
                 CASS_OK => Ok(Uuid(uuid)),
                 err => {
-                    err.to_result(Uuid(uuid))
-                        .chain_err(|| "")
+                    err.wrap(Uuid(uuid))
                 }
             }
         }
@@ -4,11 +4,13 @@ use cassandra::data_type::ConstDataType;
 
 use cassandra::error::CassError;
 use cassandra::inet::Inet;
-use cassandra::iterator::MapIterator;
-use cassandra::iterator::SetIterator;
-use cassandra::util::Protected;
+use cassandra::iterator::{LendingIterator, MapIterator, SetIterator, TupleIterator, UserTypeFieldIterator};
+use cassandra::numeric::{decode_decimal, decode_varint};
+use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp};
+use cassandra::util::{CassErrorExt, Protected};
 use cassandra::uuid::Uuid;
-use cassandra_sys::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
+use cassandra_sys::{CASS_ERROR_LIB_INVALID_ITEM_COUNT, CASS_ERROR_LIB_INVALID_VALUE_TYPE,
+                    CASS_ERROR_LIB_NAME_DOES_NOT_EXIST};
 use cassandra_sys::CASS_VALUE_TYPE_ASCII;
 use cassandra_sys::CASS_VALUE_TYPE_BIGINT;
 use cassandra_sys::CASS_VALUE_TYPE_BLOB;
@@ -41,18 +43,22 @@ use cassandra_sys::CassValue as _CassValue;
 use cassandra_sys::CassValueType as _CassValueType;
 #[allow(unused_imports)]
 use cassandra_sys::cass_collection_append_decimal;
+use cassandra_sys::cass_iterator_fields_from_user_type;
+use cassandra_sys::cass_iterator_from_tuple;
 use cassandra_sys::cass_iterator_from_collection;
 use cassandra_sys::cass_iterator_from_map;
 use cassandra_sys::cass_true;
 use cassandra_sys::cass_value_data_type;
 use cassandra_sys::cass_value_get_bool;
 use cassandra_sys::cass_value_get_bytes;
+use cassandra_sys::cass_value_get_decimal;
 use cassandra_sys::cass_value_get_double;
 use cassandra_sys::cass_value_get_float;
 use cassandra_sys::cass_value_get_inet;
 use cassandra_sys::cass_value_get_int16;
 use cassandra_sys::cass_value_get_int32;
 use cassandra_sys::cass_value_get_int64;
+use cassandra_sys::cass_value_get_uint32;
 use cassandra_sys::cass_value_get_int8;
 use cassandra_sys::cass_value_get_string;
 use cassandra_sys::cass_value_get_uuid;
@@ -65,21 +71,29 @@ use cassandra_sys::cass_value_primary_sub_type;
 #[allow(unused_imports)]
 use cassandra_sys::cass_value_secondary_sub_type;
 use cassandra_sys::cass_value_type;
-use errors::*;
-use std::ffi::CString;
+use cassandra::error::*;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use decimal::d128;
+use num::BigInt;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::marker::PhantomData;
 
 use std::mem;
+use std::ptr;
 use std::slice;
 use std::str;
 
-/// A single primitive value or a collection of values.
-pub struct Value(*const _CassValue);
+/// A single primitive value or a collection of values, borrowed from the result, row or
+/// collection it was read from.
+pub struct Value<'a>(*const _CassValue, PhantomData<&'a _CassValue>);
 
-impl Protected<*const _CassValue> for Value {
+impl<'a> Protected<*const _CassValue> for Value<'a> {
     fn inner(&self) -> *const _CassValue { self.0 }
-    fn build(inner: *const _CassValue) -> Self { Value(inner) }
+    fn build(inner: *const _CassValue) -> Self { Value(inner, PhantomData) }
 }
 
 #[derive(Debug)]
@@ -97,7 +111,7 @@ impl Protected<_CassValueType> for ValueType {
     fn build(inner: _CassValueType) -> Self { ValueType(inner) }
 }
 
-impl Debug for Value {
+impl<'a> Debug for Value<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         if self.is_null() {
             Ok(())
@@ -107,53 +121,57 @@ impl Debug for Value {
                 CASS_VALUE_TYPE_CUSTOM => write!(f, "{:?}", "custom"),
                 CASS_VALUE_TYPE_ASCII |
                 CASS_VALUE_TYPE_TEXT |
-                CASS_VALUE_TYPE_VARCHAR => write!(f, "{:?}", self.get_string().unwrap()),
-                CASS_VALUE_TYPE_DECIMAL => write!(f, "{:?}", self.get_bytes().unwrap()),
-                CASS_VALUE_TYPE_COUNTER => write!(f, "{:?}", self.get_i64().unwrap()),
-                CASS_VALUE_TYPE_BIGINT => write!(f, "{:?}", self.get_i64().unwrap()),
-                CASS_VALUE_TYPE_DATE => write!(f, "{:?}", self.get_string().unwrap()),
-                CASS_VALUE_TYPE_TIME => write!(f, "{:?}", self.get_string().unwrap()),
-                CASS_VALUE_TYPE_VARINT => write!(f, "{:?}", self.get_bytes().unwrap()),
-                CASS_VALUE_TYPE_BOOLEAN => write!(f, "{:?}", self.get_bool().unwrap()),
-                CASS_VALUE_TYPE_DOUBLE => write!(f, "{:?}", self.get_dbl().unwrap()),
-                CASS_VALUE_TYPE_FLOAT => write!(f, "{:?}", self.get_flt().unwrap()),
-                CASS_VALUE_TYPE_BLOB => write!(f, "{:?}", self.get_bytes().unwrap()),
-                CASS_VALUE_TYPE_INT => write!(f, "{:?}", self.get_i32().unwrap()),
-                CASS_VALUE_TYPE_SMALL_INT => write!(f, "{:?}", self.get_i16().unwrap()),
-                CASS_VALUE_TYPE_TINY_INT => write!(f, "{:?}", self.get_i8().unwrap()),
-                CASS_VALUE_TYPE_INET => write!(f, "{:?}", self.get_inet().unwrap()),
-                CASS_VALUE_TYPE_TIMESTAMP => write!(f, "{:?}", self.get_i64().unwrap()),
-                CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID: {}", self.get_uuid().unwrap()),
-                CASS_VALUE_TYPE_LAST_ENTRY => unimplemented!(),
-                CASS_VALUE_TYPE_UUID => write!(f, "UUID: {}", self.get_uuid().unwrap()),
+                CASS_VALUE_TYPE_VARCHAR => write!(f, "{:?}", self.get_string_lossy().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DECIMAL => self.fmt_decimal(f),
+                CASS_VALUE_TYPE_COUNTER => write!(f, "{:?}", self.get_i64().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_BIGINT => write!(f, "{:?}", self.get_i64().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DATE => write!(f, "{}", self.get_date().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIME => write!(f, "{}", self.get_time().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_VARINT => write!(f, "{}", self.get_varint().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_BOOLEAN => write!(f, "{:?}", self.get_bool().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DOUBLE => write!(f, "{:?}", self.get_dbl().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_FLOAT => write!(f, "{:?}", self.get_flt().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_BLOB => write!(f, "{:?}", self.get_bytes().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_INT => write!(f, "{:?}", self.get_i32().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_SMALL_INT => write!(f, "{:?}", self.get_i16().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TINY_INT => write!(f, "{:?}", self.get_i8().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_INET => write!(f, "{:?}", self.get_inet().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIMESTAMP => write!(f, "{}", self.get_timestamp().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID: {}", self.get_uuid().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_LAST_ENTRY => write!(f, "{:?}", "unknown"),
+                CASS_VALUE_TYPE_UUID => write!(f, "UUID: {}", self.get_uuid().map_err(|_| fmt::Error)?),
                 CASS_VALUE_TYPE_SET |
                 CASS_VALUE_TYPE_LIST => {
                     write!(f, "[")?;
-                    for item in self.get_set().expect("set must be a set") {
+                    let mut items = collection_items(self).map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
                         write!(f, "SET {:?} ", item)?
                     }
                     write!(f, "]")?;
                     Ok(())
                 }
                 CASS_VALUE_TYPE_MAP => {
-                    for item in self.get_map().expect("map must be a map") {
+                    let mut items = self.get_map().map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
                         write!(f, "MAP {:?}:{:?}", item.0, item.1)?
                     }
                     Ok(())
                 }
                 CASS_VALUE_TYPE_UDT => {
-                    debug!("unimplemented for udt!");
-                    //                    for item in self.as_map_iterator().unwrap() {
-                    //                        try!(write!(f, "MAP {:?}:{:?}", item.0,item.1))
-                    //                    }
-                    Ok(())
+                    write!(f, "{{")?;
+                    let mut fields = self.get_user_type().map_err(|_| fmt::Error)?;
+                    while let Some(field) = fields.next() {
+                        write!(f, "{}: {:?} ", field.name, field.value)?
+                    }
+                    write!(f, "}}")
                 }
                 CASS_VALUE_TYPE_TUPLE => {
-                    debug!("unimplemented for tuple!");
-                    //                    for item in self.as_map_iterator().unwrap() {
-                    //                        try!(write!(f, "MAP {:?}:{:?}", item.0,item.1))
-                    //                    }
-                    Ok(())
+                    write!(f, "(")?;
+                    let mut items = self.get_tuple().map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
+                        write!(f, "{:?} ", item)?
+                    }
+                    write!(f, ")")
                 }
                 // FIXME
                 // err => write!(f, "{:?}", err),
@@ -162,7 +180,7 @@ impl Debug for Value {
     }
 }
 
-impl Display for Value {
+impl<'a> Display for Value<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         if self.is_null() {
             Ok(())
@@ -170,24 +188,48 @@ impl Display for Value {
             match self.get_type().0 {
                 CASS_VALUE_TYPE_UNKNOWN => write!(f, "{}", "unknown"),
                 CASS_VALUE_TYPE_CUSTOM => write!(f, "{}", "custom"),
-                CASS_VALUE_TYPE_ASCII => write!(f, "{}", self.get_string().unwrap()),
-                CASS_VALUE_TYPE_BIGINT => write!(f, "{}", self.get_i64().unwrap()),
-                CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string().unwrap()),
-                CASS_VALUE_TYPE_BOOLEAN => write!(f, "{}", self.get_bool().unwrap()),
-                CASS_VALUE_TYPE_DOUBLE => write!(f, "{}", self.get_dbl().unwrap()),
-                CASS_VALUE_TYPE_FLOAT => write!(f, "{}", self.get_flt().unwrap()),
-                CASS_VALUE_TYPE_INT => write!(f, "{}", self.get_i32().unwrap()),
-                CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID: {}", self.get_uuid().unwrap()),
-                CASS_VALUE_TYPE_SET => {
+                CASS_VALUE_TYPE_ASCII => write!(f, "{}", self.get_string_lossy().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_BIGINT => write!(f, "{}", self.get_i64().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_VARCHAR => write!(f, "{}", self.get_string_lossy().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_BOOLEAN => write!(f, "{}", self.get_bool().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DOUBLE => write!(f, "{}", self.get_dbl().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_FLOAT => write!(f, "{}", self.get_flt().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_INT => write!(f, "{}", self.get_i32().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DECIMAL => self.fmt_decimal(f),
+                CASS_VALUE_TYPE_VARINT => write!(f, "{}", self.get_varint().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_DATE => write!(f, "{}", self.get_date().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIME => write!(f, "{}", self.get_time().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIMESTAMP => write!(f, "{}", self.get_timestamp().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_TIMEUUID => write!(f, "TIMEUUID: {}", self.get_uuid().map_err(|_| fmt::Error)?),
+                CASS_VALUE_TYPE_SET |
+                CASS_VALUE_TYPE_LIST => {
                     write!(f, "[")?;
-                    for item in self.get_set().expect("set must be a set") {
+                    let mut items = collection_items(self).map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
                         write!(f, "{} ", item)?
                     }
                     write!(f, "]")?;
                     Ok(())
                 }
+                CASS_VALUE_TYPE_TUPLE => {
+                    write!(f, "(")?;
+                    let mut items = self.get_tuple().map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
+                        write!(f, "{} ", item)?
+                    }
+                    write!(f, ")")
+                }
+                CASS_VALUE_TYPE_UDT => {
+                    write!(f, "{{")?;
+                    let mut fields = self.get_user_type().map_err(|_| fmt::Error)?;
+                    while let Some(field) = fields.next() {
+                        write!(f, "{}: {} ", field.name, field.value)?
+                    }
+                    write!(f, "}}")
+                }
                 CASS_VALUE_TYPE_MAP => {
-                    for item in self.get_map().expect("map must be a map") {
+                    let mut items = self.get_map().map_err(|_| fmt::Error)?;
+                    while let Some(item) = items.next() {
                         write!(f, "MAP {}:{}", item.0, item.1)?
                     }
                     Ok(())
@@ -199,7 +241,13 @@ impl Display for Value {
     }
 }
 
-impl Value {
+impl<'a> Value<'a> {
+    /// Writes a "decimal" as its exact unscaled value and exponent, which may not fit a `d128`
+    fn fmt_decimal(&self, f: &mut Formatter) -> fmt::Result {
+        let (varint, scale) = self.get_decimal_parts().map_err(|_| fmt::Error)?;
+        write!(f, "{}E{}", decode_varint(varint), -(scale as i64))
+    }
+
     // FIXME a low level optimization. not sure whether to include or not
     //    pub fn fill_uuid(&self, mut uuid: Uuid) -> Result<Uuid, CassError> {
     //        unsafe { CassError::build(cass_value_get_uuid(self.0, &mut uuid.0), None).wrap(uuid) }
@@ -231,23 +279,36 @@ impl Value {
     //
     //    }
 
-    /// Gets the name of the keyspace.
+    /// Borrows the raw bytes of this value, without copying them
     #[allow(cast_possible_truncation)]
-    pub fn get_bytes(&self) -> Result<&[u8]> {
+    pub fn get_bytes(&self) -> Result<&'a [u8]> {
         unsafe {
-            let mut output = mem::zeroed();
-            let mut output_size = mem::zeroed();
-            let result = cass_value_get_bytes(self.0, &mut output, &mut output_size);
-            // raw2utf8(output, output_size).unwrap()
-            let slice = slice::from_raw_parts(output, output_size as usize);
-            result.to_result(slice).chain_err(|| "")
+            let mut output = ptr::null();
+            let mut output_size = 0;
+            cass_value_get_bytes(self.0, &mut output, &mut output_size).wrap(())?;
+            Ok(slice::from_raw_parts(output, output_size))
         }
     }
-    // pub fn get_decimal<'a>(&'a self, mut output: String) ->
-    // Result<String,CassError> {unsafe{
-    // CassError::build(cass_value_get_decimal(self.0,&mut
-    // output)).wrap(output)
-    //    }}
+
+    /// Gets the unscaled varint bytes and the scale of a "decimal"
+    pub(crate) fn get_decimal_parts(&self) -> Result<(&'a [u8], i32)> {
+        unsafe {
+            let mut varint = ptr::null();
+            let mut varint_size = 0;
+            let mut scale = 0;
+            cass_value_get_decimal(self.0, &mut varint, &mut varint_size, &mut scale).wrap(())?;
+            Ok((slice::from_raw_parts(varint, varint_size), scale))
+        }
+    }
+
+    /// Gets a "decimal", failing if it holds more digits than a `d128` can represent
+    pub fn get_decimal(&self) -> Result<d128> {
+        let (varint, scale) = self.get_decimal_parts()?;
+        decode_decimal(varint, scale)
+    }
+
+    /// Gets a "varint"
+    pub fn get_varint(&self) -> Result<BigInt> { Ok(decode_varint(self.get_bytes()?)) }
 
     /// Get the type of this Cassandra value
     pub fn get_type(&self) -> ValueType { unsafe { ValueType(cass_value_type(self.0)) } }
@@ -279,33 +340,62 @@ impl Value {
     //    }
 
     /// Gets this value as a set iterator.
-    pub fn get_set(&self) -> Result<SetIterator> {
+    pub fn get_set(&self) -> Result<SetIterator<'a>> {
         unsafe {
             match self.get_type().0 {
                 CASS_VALUE_TYPE_SET => Ok(SetIterator::build(cass_iterator_from_collection(self.0))),
-                _ => Err("LIB_INVALID_VALUE_TYPE".into()),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
     /// Gets this value as a map iterator.
-    pub fn get_map(&self) -> Result<MapIterator> {
+    pub fn get_map(&self) -> Result<MapIterator<'a>> {
         unsafe {
             match self.get_type().0 {
                 CASS_VALUE_TYPE_MAP => Ok(MapIterator::build(cass_iterator_from_map(self.0))),
-                _ => Err("LIB_INVALID_VALUE_TYPE".into()),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
             }
         }
     }
 
-    //    pub fn as_user_type_iterator(&self) -> Result<UserTypeIterator, CassError> {
-    //        unsafe {
-    //            match self.get_type() {
-    //                ValueType::UDT => Ok(UserTypeIterator(cass_iterator_from_user_type(self.0))),
-    //                _ => Err(CassError::build(CassErrorTypes::LIB_INVALID_VALUE_TYPE as u32)),
-    //            }
-    //        }
-    //    }
+    /// Gets this value as a list iterator.
+    pub fn get_list(&self) -> Result<SetIterator<'a>> {
+        unsafe {
+            match self.get_type().0 {
+                CASS_VALUE_TYPE_LIST => Ok(SetIterator::build(cass_iterator_from_collection(self.0))),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
+            }
+        }
+    }
+
+    /// Gets this value as an iterator over the items of a tuple.
+    pub fn get_tuple(&self) -> Result<TupleIterator<'a>> {
+        unsafe {
+            match self.get_type().0 {
+                CASS_VALUE_TYPE_TUPLE => Ok(TupleIterator::build(cass_iterator_from_tuple(self.0))),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
+            }
+        }
+    }
+
+    /// Gets this value as an iterator over the named fields of a user defined type.
+    pub fn get_user_type(&self) -> Result<UserTypeFieldIterator<'a>> {
+        unsafe {
+            match self.get_type().0 {
+                CASS_VALUE_TYPE_UDT => Ok(UserTypeFieldIterator::build(cass_iterator_fields_from_user_type(self.0))),
+                _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
+            }
+        }
+    }
+
+    /// Reads the fields of a user defined type into a map keyed by field name, failing on the
+    /// first field that does not convert to `V`.
+    pub fn get_user_type_map<V: FromValue>(&self) -> Result<HashMap<String, V>> {
+        self.get_user_type()?
+            .map(|field| Ok((field.name, V::from_value(&field.value)?)))
+            .collect()
+    }
 
 
     // ~ pub fn map_iter(&self) -> Result<MapIterator,CassError> {unsafe{
@@ -319,19 +409,21 @@ impl Value {
     // ~ }
     // ~ }}
 
-    /// Get this value as a string
-    #[allow(cast_possible_truncation)]
-    pub fn get_string(&self) -> Result<&str> {
+    /// Get this value as a string, borrowed without copying it. Fails if the text is not
+    /// valid UTF-8; see `get_string_lossy` and `get_string_bytes` for text that may not be.
+    pub fn get_string(&self) -> Result<&'a str> { Ok(str::from_utf8(self.get_string_bytes()?)?) }
+
+    /// Get this value as a string, replacing any invalid UTF-8 sequences with U+FFFD.
+    /// Only text that is not valid UTF-8 is copied.
+    pub fn get_string_lossy(&self) -> Result<Cow<'a, str>> { Ok(String::from_utf8_lossy(self.get_string_bytes()?)) }
+
+    /// Borrows the raw bytes of a string value, without checking that they are valid UTF-8
+    pub fn get_string_bytes(&self) -> Result<&'a [u8]> {
         unsafe {
-            let message: CString = mem::zeroed();
-            let mut message = message.as_ptr();
-            let mut message_length = mem::zeroed();
-            cass_value_get_string(self.0, &mut message, &mut (message_length));
-
-            let slice = slice::from_raw_parts(message as *const u8, message_length as usize);
-            let err = cass_value_get_string(self.0, &mut message, &mut (message_length));
-            str::from_utf8(slice).chain_err(|| "")
-            // unimplemented!()
+            let mut message = ptr::null();
+            let mut message_length = 0;
+            cass_value_get_string(self.0, &mut message, &mut message_length).wrap(())?;
+            Ok(slice::from_raw_parts(message as *const u8, message_length))
         }
     }
 
@@ -355,10 +447,9 @@ impl Value {
     /// Get this value as an Inet
     pub fn get_inet(&self) -> Result<Inet> {
         unsafe {
-            let output: Inet = mem::zeroed();
-            cass_value_get_inet(self.0, &mut Inet::inner(&output))
-                .to_result(output)
-                .chain_err(|| "")
+            let mut output = mem::zeroed();
+            cass_value_get_inet(self.0, &mut output)
+                .wrap(Inet::build(output))
         }
     }
 
@@ -367,8 +458,7 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int32(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -377,8 +467,7 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int16(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -387,8 +476,7 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int8(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -397,18 +485,34 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_int64(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
+        }
+    }
+
+    /// Get this value as a "date"
+    pub fn get_date(&self) -> Result<CqlDate> {
+        unsafe {
+            let mut output = 0;
+            cass_value_get_uint32(self.0, &mut output)
+                .wrap(CqlDate(output))
         }
     }
 
+    /// Get this value as a "time"
+    pub fn get_time(&self) -> Result<CqlTime> { self.get_i64().map(CqlTime) }
+
+    /// Get this value as a "timestamp"
+    pub fn get_timestamp(&self) -> Result<CqlTimestamp> { self.get_i64().map(CqlTimestamp) }
+
+    /// Get this value as a "duration", which the driver reads as a "custom" value
+    pub fn get_duration(&self) -> Result<CqlDuration> { CqlDuration::from_bytes(self.get_bytes()?) }
+
     /// Get this value as a float
     pub fn get_flt(&self) -> Result<f32> {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_float(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -417,8 +521,7 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_double(self.0, &mut output)
-                .to_result(output)
-                .chain_err(|| "")
+                .wrap(output)
         }
     }
 
@@ -427,8 +530,7 @@ impl Value {
         unsafe {
             let mut output = mem::zeroed();
             cass_value_get_bool(self.0, &mut output)
-                .to_result(output == cass_true)
-                .chain_err(|| "")
+                .wrap(output == cass_true)
         }
     }
 
@@ -437,8 +539,184 @@ impl Value {
         unsafe {
             let mut uuid = mem::zeroed();
             cass_value_get_uuid(self.0, &mut uuid)
-                .to_result(Uuid::build(uuid))
-                .chain_err(|| "")
+                .wrap(Uuid::build(uuid))
         }
     }
+
+    /// Gets the field with the given name of this user defined type value
+    pub fn get_user_type_field<T: FromValue>(&self, name: &str) -> Result<T> {
+        let mut fields = self.get_user_type()?;
+        while let Some(field) = fields.next() {
+            if field.name == name {
+                return T::from_value(&field.value);
+            }
+        }
+        Err(CASS_ERROR_LIB_NAME_DOES_NOT_EXIST.into())
+    }
+}
+
+/// Conversion from a Cassandra value into a Rust type
+pub trait FromValue: Sized {
+    /// Reads the value, failing if it is null or of an incompatible type
+    fn from_value(value: &Value) -> Result<Self>;
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self> { value.get_bool() }
+}
+
+impl FromValue for i8 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_i8() }
+}
+
+impl FromValue for i16 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_i16() }
+}
+
+impl FromValue for i32 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_i32() }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_i64() }
+}
+
+impl FromValue for f32 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_flt() }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_dbl() }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self> { value.get_string().map(str::to_owned) }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: &Value) -> Result<Self> { value.get_bytes().map(<[u8]>::to_vec) }
+}
+
+impl FromValue for d128 {
+    fn from_value(value: &Value) -> Result<Self> { value.get_decimal() }
+}
+
+impl FromValue for BigInt {
+    fn from_value(value: &Value) -> Result<Self> { value.get_varint() }
+}
+
+impl FromValue for CqlDate {
+    fn from_value(value: &Value) -> Result<Self> { value.get_date() }
+}
+
+impl FromValue for CqlTime {
+    fn from_value(value: &Value) -> Result<Self> { value.get_time() }
+}
+
+impl FromValue for CqlTimestamp {
+    fn from_value(value: &Value) -> Result<Self> { value.get_timestamp() }
+}
+
+impl FromValue for CqlDuration {
+    fn from_value(value: &Value) -> Result<Self> { value.get_duration() }
+}
+
+impl FromValue for NaiveDate {
+    fn from_value(value: &Value) -> Result<Self> { value.get_date()?.to_naive_date() }
+}
+
+impl FromValue for NaiveTime {
+    fn from_value(value: &Value) -> Result<Self> { value.get_time()?.to_naive_time() }
+}
+
+impl FromValue for DateTime<Utc> {
+    fn from_value(value: &Value) -> Result<Self> { value.get_timestamp()?.to_datetime() }
+}
+
+impl FromValue for Uuid {
+    fn from_value(value: &Value) -> Result<Self> { value.get_uuid() }
+}
+
+impl FromValue for Inet {
+    fn from_value(value: &Value) -> Result<Self> { value.get_inet() }
+}
+
+/// A null value is read as `None`
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self> {
+        if value.is_null() { Ok(None) } else { T::from_value(value).map(Some) }
+    }
+}
+
+/// Reads a list or a set, element by element
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self> { collection_items(value)?.map(|item| T::from_value(&item)).collect() }
+}
+
+impl<T: FromValue + Eq + Hash> FromValue for HashSet<T> {
+    fn from_value(value: &Value) -> Result<Self> { collection_items(value)?.map(|item| T::from_value(&item)).collect() }
+}
+
+impl<T: FromValue + Ord> FromValue for BTreeSet<T> {
+    fn from_value(value: &Value) -> Result<Self> { collection_items(value)?.map(|item| T::from_value(&item)).collect() }
+}
+
+impl<K: FromValue + Eq + Hash, V: FromValue> FromValue for HashMap<K, V> {
+    fn from_value(value: &Value) -> Result<Self> {
+        value.get_map()?.map(|(key, value)| Ok((K::from_value(&key)?, V::from_value(&value)?))).collect()
+    }
+}
+
+impl<K: FromValue + Ord, V: FromValue> FromValue for BTreeMap<K, V> {
+    fn from_value(value: &Value) -> Result<Self> {
+        value.get_map()?.map(|(key, value)| Ok((K::from_value(&key)?, V::from_value(&value)?))).collect()
+    }
+}
+
+macro_rules! tuple_from_value {
+    ( $count:expr; $( $item:ident ),+ ) => {
+        impl<$( $item: FromValue ),+> FromValue for ( $( $item, )+ ) {
+            fn from_value(value: &Value) -> Result<Self> {
+                let mut items = value.get_tuple()?;
+                let tuple = ( $( <$item as FromValue>::from_value(&items.next().ok_or_else(|| tuple_arity($count))?)?, )+ );
+                match items.next() {
+                    Some(_) => Err(tuple_arity($count)),
+                    None => Ok(tuple),
+                }
+            }
+        }
+    };
+}
+
+tuple_from_value!(1; A);
+tuple_from_value!(2; A, B);
+tuple_from_value!(3; A, B, C);
+tuple_from_value!(4; A, B, C, D);
+tuple_from_value!(5; A, B, C, D, E);
+tuple_from_value!(6; A, B, C, D, E, F);
+tuple_from_value!(7; A, B, C, D, E, F, G);
+tuple_from_value!(8; A, B, C, D, E, F, G, H);
+tuple_from_value!(9; A, B, C, D, E, F, G, H, I);
+tuple_from_value!(10; A, B, C, D, E, F, G, H, I, J);
+tuple_from_value!(11; A, B, C, D, E, F, G, H, I, J, K);
+tuple_from_value!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+tuple_from_value!(13; A, B, C, D, E, F, G, H, I, J, K, L, M);
+tuple_from_value!(14; A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+tuple_from_value!(15; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+tuple_from_value!(16; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+fn tuple_arity(count: usize) -> Error {
+    CassError::with_message(CASS_ERROR_LIB_INVALID_ITEM_COUNT,
+                            format!("expected a tuple of {} items", count))
+        .into()
+}
+
+/// Iterates the elements of a list or a set
+fn collection_items<'a>(value: &Value<'a>) -> Result<SetIterator<'a>> {
+    match value.get_type().0 {
+        CASS_VALUE_TYPE_LIST | CASS_VALUE_TYPE_SET => unsafe {
+            Ok(SetIterator::build(cass_iterator_from_collection(value.0)))
+        },
+        _ => Err(CASS_ERROR_LIB_INVALID_VALUE_TYPE.into()),
+    }
 }
@@ -6,7 +6,7 @@ use cassandra_sys::CassWriteType;
 
 
 /// The write type of a request
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct WriteType(pub CassWriteType);
 
 impl WriteType {
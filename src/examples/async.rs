@@ -1,7 +1,6 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
 extern crate num;
 
 use std::str::FromStr;
@@ -1,7 +1,6 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
 use std::str::FromStr;
 
 
@@ -17,12 +16,7 @@ struct Basic {
 fn insert_into_basic(session: &mut Session, key: &str, basic: Basic) -> Result<CassResult> {
 
     let mut statement = stmt!("INSERT INTO examples.basic (key, bln, flt, dbl, i32, i64) VALUES (?, ?, ?, ?, ?, ?);");
-    statement.bind(0, key)?;
-    statement.bind(1, basic.bln)?;
-    statement.bind(2, basic.flt)?;
-    statement.bind(3, basic.dbl)?;
-    statement.bind(4, basic.i32)?;
-    statement.bind(5, basic.i64)?;
+    statement.bind_values((key, basic.bln, basic.flt, basic.dbl, basic.i32, basic.i64))?;
     session.execute(&statement).wait()
 }
 
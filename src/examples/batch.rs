@@ -1,7 +1,6 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
 use std::str::FromStr;
 
 struct Pair<'a> {
@@ -22,7 +22,8 @@ fn main() {
             let schema_query = Statement::new(&query, 0);
             for _ in 0..1000 {
                 let result = session.execute(&schema_query).wait().unwrap();
-                for row in result {
+                let mut rows = result.iter();
+                while let Some(row) = rows.next() {
                     let name: String = row.get_col_by_name("column_name").unwrap();
                     let ftype: String = row.get_col_by_name("type").unwrap();
 
@@ -1,18 +1,14 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
+use std::collections::HashSet;
 use std::str::FromStr;
 
 
 fn insert_into_collections(session: &mut Session, key: &str, items: Vec<&str>) -> Result<CassResult> {
     let mut statement = stmt!("INSERT INTO examples.collections (key, items) VALUES (?, ?);");
     statement.bind(0, key)?;
-    let mut set = Set::new(2);
-    for item in items {
-        set.append_string(item)?;
-    }
-    statement.bind_set(1, set)?;
+    statement.bind(1, items.into_iter().collect::<HashSet<_>>())?;
     session.execute(&statement).wait()
 }
 
@@ -21,10 +17,11 @@ fn select_from_collections(session: &mut Session, key: &str) -> Result<()> {
     statement.bind(0, key)?;
     let result = session.execute(&statement).wait()?;
     println!("{:?}", result);
-    for row in result.iter() {
+    let mut rows = result.iter();
+    while let Some(row) = rows.next() {
         let column = row.get_column(0);
-        let items_iterator: SetIterator = column?.set_iter()?;
-        for item in items_iterator {
+        let mut items_iterator: SetIterator = column?.set_iter()?;
+        while let Some(item) = items_iterator.next() {
             println!("item: {:?}", item);
         }
     }
@@ -1,7 +1,6 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
 use std::error::Error;
 use std::str::FromStr;
 
@@ -34,10 +33,11 @@ fn select_from_maps(session: &mut Session, key: &str) -> Result<()> {
     statement.bind(0, key)?;
     let result = session.execute(&statement).wait()?;
     // println!("{:?}", result);
-    for row in result.iter() {
+    let mut rows = result.iter();
+    while let Some(row) = rows.next() {
         let column = row.get_column(0).unwrap(); //FIXME
-        let items_iterator: MapIterator = column.map_iter().unwrap();
-        for item in items_iterator {
+        let mut items_iterator: MapIterator = column.map_iter().unwrap();
+        while let Some(item) = items_iterator.next() {
             println!("item: {:?}", item);
         }
     }
@@ -1,17 +1,15 @@
-#[macro_use(stmt)]
 extern crate cassandra;
 
 use cassandra::*;
-use errors::*;
 use std::str::FromStr;
 
 static NUM_CONCURRENT_REQUESTS: isize = 100;
-static CREATE_KEYSPACE: &'static str = "CREATE KEYSPACE IF NOT EXISTS examples WITH replication = { \'class\': \
+static CREATE_KEYSPACE: &str = "CREATE KEYSPACE IF NOT EXISTS examples WITH replication = { \'class\': \
                                         \'SimpleStrategy\', \'replication_factor\': \'1\' };";
-static CREATE_TABLE: &'static str = "CREATE TABLE IF NOT EXISTS examples.paging (key ascii, value text, PRIMARY KEY \
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.paging (key ascii, value text, PRIMARY KEY \
                                      (key));";
-static SELECT_QUERY: &'static str = "SELECT * FROM paging";
-static INSERT_QUERY: &'static str = "INSERT INTO paging (key, value) VALUES (?, ?);";
+static SELECT_QUERY: &str = "SELECT * FROM paging";
+static INSERT_QUERY: &str = "INSERT INTO paging (key, value) VALUES (?, ?);";
 
 // FIXME uuids not yet working
 fn insert_into_paging(session: &mut Session /* , uuid_gen:&mut UuidGen */) -> Result<Vec<Option<ResultFuture>>> {
@@ -34,35 +32,40 @@ fn insert_into_paging(session: &mut Session /* , uuid_gen:&mut UuidGen */) -> Re
     Ok(results)
 }
 
+#[derive(Debug, FromRow)]
+struct Pair {
+    key: String,
+    value: String,
+}
+
 fn select_from_paging(session: &mut Session) -> Result<()> {
-    let has_more_pages = true;
     let mut statement = Statement::new(SELECT_QUERY, 0);
-    statement.set_paging_size(100).unwrap();
+    statement.set_paging_size(10)?;
 
-    // FIXME must understand statement lifetime better for paging
-    while has_more_pages {
-        let result = session.execute(&statement).wait()?;
-        // println!("{:?}", result);
-        for row in result.iter() {
-            match row.get_column(0)?.get_string() {
-                Ok(key) => {
-                    let key_str = key.to_string();
-                    let value = row.get_column(1)?;
-                    println!("key: '{:?}' value: '{:?}'",
-                             key_str,
-                             &value.get_string().unwrap());
-                }
-                Err(err) => panic!(err),
-            }
-        }
-        //        if result.has_more_pages() {
-        //            try!(statement.set_paging_state(&result));
-        //        }
-        //        has_more_pages = result.has_more_pages();
+    for pair in session.execute_paged(statement).rows::<Pair>() {
+        let pair = pair?;
+        println!("key: '{:?}' value: '{:?}'", pair.key, pair.value);
     }
     Ok(())
 }
 
+// Fetches a single page, returning the token a client would hand back to resume after it
+fn select_page_from_token(session: &mut Session, token: Option<Vec<u8>>) -> Result<Option<Vec<u8>>> {
+    let mut statement = Statement::new(SELECT_QUERY, 0);
+    statement.set_paging_size(10)?;
+    if let Some(token) = token {
+        statement.set_paging_state_token(&token)?;
+    }
+
+    let result = session.execute(&statement).wait()?;
+    let mut rows = result.iter();
+    while let Some(row) = rows.next() {
+        let key: String = row.get(0)?;
+        println!("page key: '{:?}'", key);
+    }
+    result.paging_state_token()
+}
+
 fn main() {
     // let uuid_gen = &mut UuidGen::new();
 
@@ -80,6 +83,11 @@ fn main() {
                 print!("{:?}", result.unwrap().wait().unwrap());
             }
             select_from_paging(session).unwrap();
+
+            let mut token = select_page_from_token(session, None).unwrap();
+            while token.is_some() {
+                token = select_page_from_token(session, token).unwrap();
+            }
         }
         err => println!("{:?}", err),
     }
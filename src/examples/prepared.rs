@@ -1,7 +1,6 @@
 #[macro_use(stmt)]
 extern crate cassandra;
 use cassandra::*;
-use errors::*;
 use std::str::FromStr;
 
 static CREATE_KEYSPACE: &'static str = "CREATE KEYSPACE IF NOT EXISTS examples WITH replication = { \'class\': \
@@ -45,7 +44,8 @@ fn select_from_basic(session: &mut Session, prepared: &PreparedStatement, key: &
     match future.wait() {
         Ok(result) => {
             println!("{:?}", result);
-            for row in result.iter() {
+            let mut rows = result.iter();
+            while let Some(row) = rows.next() {
                 basic.bln = row.get_col(1)?;
                 basic.dbl = row.get_col(2)?;
                 basic.flt = row.get_col(3)?;
@@ -3,7 +3,6 @@ extern crate cassandra;
 
 
 use cassandra::*;
-use errors::*;
 use std::str::FromStr;
 
 
@@ -18,7 +17,7 @@ fn print_function(session: &Session, keyspace: &str, function: &str, arguments:
 
 fn print_function_meta(meta: FunctionMeta, indent: i32) {
     print_indent(indent);
-    let name = meta.get_name();
+    let name = meta.get_name().unwrap();
     println!("Function \"name\": {}", name);
 
     print_meta_fields(meta.fields_iter(), indent + 1);
@@ -56,7 +55,7 @@ fn print_function_meta(meta: FunctionMeta, indent: i32) {
 
 fn print_aggregate_meta(meta: AggregateMeta, indent: i32) {
     print_indent(indent);
-    println!("Aggregate \"{}\":", meta.get_name());
+    println!("Aggregate \"{}\":", meta.get_name().unwrap());
     print_meta_fields(meta.fields_iter(), indent + 1);
     println!("");
 }
@@ -177,7 +176,7 @@ fn print_aggregate(session: &Session, keyspace: &str, aggregate: &str, arguments
 
 fn print_table_meta(meta: &mut TableMeta, indent: i32) {
     print_indent(indent);
-    let name = meta.get_name();
+    let name = meta.get_name().unwrap();
     println!("Table \"{}\":\n", name);
 
     print_meta_fields(meta.field_iter(), indent + 1);
@@ -191,7 +190,7 @@ fn print_table_meta(meta: &mut TableMeta, indent: i32) {
 
 fn print_column_meta(meta: &mut ColumnMeta, indent: i32) {
     print_indent(indent);
-    let name = meta.name();
+    let name = meta.name().unwrap();
     println!("Column \"{}\":", name);
     print_meta_fields(meta.field_iter(), indent + 1);
     println!("");
@@ -211,7 +210,7 @@ fn print_keyspace(session: &Session, keyspace: &str) {
 
 fn print_keyspace_meta(keyspace_meta: &mut KeyspaceMeta, indent: i32) {
     print_indent(indent);
-    let name = keyspace_meta.name();
+    let name = keyspace_meta.name().unwrap();
     println!("Keyspace \"{}\":\n", name);
 
     print_meta_fields(keyspace_meta.fields_iter(), indent + 1);
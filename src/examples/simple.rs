@@ -18,7 +18,8 @@ fn main() {
         Ok(ref mut session) => {
             let result = session.execute(&query).wait().unwrap();
             println!("{}", result);
-            for row in result.iter() {
+            let mut rows = result.iter();
+            while let Some(row) = rows.next() {
                 let col: String = row.get_col_by_name(col_name).unwrap();
                 println!("ks name = {}", col);
             }
@@ -2,39 +2,52 @@
 #![deny(missing_docs)]
 #![allow(unknown_lints)]
 #![allow(doc_markdown)]
-// `error_chain!` can recurse deeply
-#![recursion_limit = "1024"]
 
 extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate decimal;
+extern crate num;
 extern crate chrono;
 extern crate time;
 extern crate ip;
 extern crate uuid;
+extern crate cassandra_derive;
+extern crate futures_core;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
 
+pub use cassandra::authenticator::{AuthProvider, Authenticator, PlainTextAuthProvider};
 pub use cassandra::batch::{Batch, BatchType, CustomPayload};
-pub use cassandra::cluster::{Cluster, ContactPoints, CqlProtocol}; //FIXME this should not be exported
-pub use cassandra::collection::{CassCollection, List, Map, Set};
+pub use cassandra::cluster::{Cluster, ContactPoint, ContactPoints, CqlProtocol}; //FIXME this should not be exported
+pub use cassandra::collection::{CassCollection, CollectionItem, List, Map, Set};
 pub use cassandra::column::Column;
 pub use cassandra::consistency::Consistency;
+pub use cassandra::cql_value::CqlValue;
 pub use cassandra::data_type::DataType;
-// pub use cassandra::write_type::*;
+pub use cassandra::write_type::WriteType;
+pub use cassandra::execution_profile::ExecutionProfile;
 pub use cassandra::field::Field;
 pub use cassandra::future::{CloseFuture, Future, FutureCallback, PreparedFuture, ResultFuture, SessionFuture};
-pub use cassandra::inet::Inet;
+pub use cassandra::inet::{FromInet, Inet};
 // pub use cassandra::util::*;
-// pub use cassandra::metrics::*;
 pub use cassandra::iterator::{AggregateIterator, ColumnIterator, FieldIterator, FunctionIterator, KeyspaceIterator,
-                              MapIterator, SetIterator, TableIterator, UserTypeIterator};
+                              LendingItem, LendingIterator, LendingMap, MapIterator, SetIterator, TableIterator,
+                              TupleIterator, UserTypeFieldIterator, UserTypeIterator};
 pub use cassandra::log::{LogLevel, set_callback, set_level};
-pub use cassandra::policy::retry::RetryPolicy;
+pub use cassandra::metrics::{ConnectionStats, RequestMetrics, SessionMetrics, SpeculativeExecutionMetrics,
+                             TimeoutMetrics};
+pub use cassandra::paging::{PagedRows, ResultPages};
+pub use cassandra::policy::load_balancing::{BalancedFuture, HostDistance, LoadBalancingPolicy};
+pub use cassandra::policy::retry::{RetryAction, RetryDecision, RetryFuture, RetryPolicy};
+pub use cassandra::policy::speculative::{ConstantSpeculativeExecutionPolicy, SpeculativeFuture};
 pub use cassandra::prepared::PreparedStatement;
 pub use cassandra::result::CassResult;
 pub use cassandra::row::AsRustType;
-pub use cassandra::row::Row;
+pub use cassandra::row::{FromRow, Row};
+pub use cassandra_derive::{FromRow, ToCqlValues};
 pub use cassandra::schema::aggregate_meta::AggregateMeta;
 pub use cassandra::schema::column_meta::ColumnMeta;
 pub use cassandra::schema::function_meta::FunctionMeta;
@@ -44,35 +57,27 @@ pub use cassandra::schema::table_meta::TableMeta;
 pub use cassandra::session::Session;
 pub use cassandra::ssl::Ssl;
 pub use cassandra::statement::BindRustType;
-pub use cassandra::statement::Statement;
+pub use cassandra::statement::{Statement, ToCqlValues};
 // pub use cassandra::custom_payload::CustomPayload;
-pub use cassandra::time::TimestampGen;
+pub use cassandra::time::{CqlDate, CqlDuration, CqlTime, CqlTimestamp, TimestampGen};
 pub use cassandra::tuple::Tuple;
 pub use cassandra::user_type::UserType;
 pub use cassandra::uuid::{Uuid, UuidGen};
-pub use cassandra::value::{Value, ValueType};
+pub use cassandra::value::{FromValue, Value, ValueType};
 // pub use cassandra::inet::{Inet};
 pub use cassandra_sys::CASS_BATCH_TYPE_LOGGED;
+pub use decimal::d128;
+pub use num::BigInt;
 pub use cassandra_sys::CassBatchType;
 
 extern crate cassandra_sys;
 
 
-// Import the macro. Don't forget to add `error-chain` in your
-// `Cargo.toml`!
-#[macro_use]
-extern crate error_chain;
-
-
 pub use cassandra::error::*;
 
-/// A still clumsy use of error-chain. needs work
-pub mod errors {
-    error_chain!{}
-}
-
 // #[macro_use]
 mod cassandra {
+    pub mod authenticator;
     pub mod consistency;
     pub mod field;
     pub mod inet;
@@ -92,8 +97,10 @@ mod cassandra {
     pub mod schema;
     pub mod log;
     pub mod error;
+    pub mod execution_profile;
     pub mod helpers;
     pub mod column;
+    pub mod cql_value;
     pub mod user_type;
     pub mod data_type;
     pub mod tuple;
@@ -102,6 +109,10 @@ mod cassandra {
     pub mod time;
     pub mod util;
     pub mod metrics;
+    pub mod numeric;
+    pub mod paging;
+    #[cfg(feature = "serde")]
+    pub mod serialization;
     pub mod write_type;
 }
 
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+extern crate cassandra;
+extern crate cassandra_sys;
+
+use cassandra::{Error, FromRow, FromValue, Statement, ToCqlValues};
+use cassandra_sys::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
+
+#[derive(Debug, FromRow)]
+struct Address {
+    street: String,
+    zip: i32,
+}
+
+#[derive(Debug, FromRow)]
+struct User {
+    id: i32,
+    #[cass(rename = "user_name")]
+    name: String,
+    email: Option<String>,
+    address: Address,
+    r#type: Option<String>,
+}
+
+#[derive(ToCqlValues)]
+struct NewUser {
+    id: i32,
+    #[cass(rename = "user_name")]
+    name: String,
+}
+
+fn from_row<T: FromRow>() {}
+
+fn from_value<T: FromValue>() {}
+
+#[test]
+fn test_derive_from_row() {
+    from_row::<User>();
+    from_row::<Address>();
+    // the derive reads a struct from a user defined type as well, which is how `address` is read
+    from_value::<Address>();
+    from_value::<Option<User>>();
+}
+
+#[test]
+fn test_derive_to_cql_values() {
+    let user = || {
+        NewUser {
+            id: 1,
+            name: "kim".to_owned(),
+        }
+    };
+    assert_eq!(user().value_count(), 2);
+
+    let mut statement = Statement::new("INSERT INTO users (id, user_name) VALUES (:id, :user_name)", 2);
+    statement.bind_values(user()).unwrap();
+
+    // the values are counted before any of them is bound
+    let mut statement = Statement::new("INSERT INTO users (id) VALUES (:id)", 1);
+    match statement.bind_values(user()) {
+        Err(Error::Lib(ref err)) if matches!(err.code(), CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS) => {
+            assert_eq!(err.message(), "expected 1 values to bind, got 2")
+        }
+        other => panic!("expected too many values to be refused, got {:?}", other),
+    }
+}